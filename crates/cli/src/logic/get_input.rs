@@ -34,6 +34,15 @@ pub struct OpenArgs {
         help = "Path to the sealed secret file, if not provided the default data local directory will be used."
     )]
     sealed_path: Option<PathBuf>,
+
+    /// An optional path to write the opened secret to, if not provided the
+    /// user will be asked if the secret should be printed in the terminal.
+    #[arg(
+        long,
+        short = 'o',
+        help = "Path to write the opened secret to, if not provided you will be asked if the secret should be printed in the terminal."
+    )]
+    secret_path: Option<PathBuf>,
 }
 
 impl OpenArgs {
@@ -51,10 +60,16 @@ impl OpenArgs {
 
     pub fn to_input(self) -> Result<OpenInput> {
         if let Some(path) = self.sealed_path {
-            Ok(OpenInput { sealed_path: path })
+            Ok(OpenInput {
+                sealed_path: path,
+                secret_path: self.secret_path,
+            })
         } else {
             let dir = default_path_for_sealed_secret(false)?;
-            Ok(OpenInput { sealed_path: dir })
+            Ok(OpenInput {
+                sealed_path: dir,
+                secret_path: self.secret_path,
+            })
         }
     }
 }
@@ -62,11 +77,16 @@ impl OpenArgs {
 #[derive(Debug, PartialEq)]
 pub struct OpenInput {
     sealed_path: PathBuf,
+    secret_path: Option<PathBuf>,
 }
 impl OpenInput {
     pub fn sealed_path(&self) -> &PathBuf {
         &self.sealed_path
     }
+
+    pub fn secret_path(&self) -> Option<PathBuf> {
+        self.secret_path.clone()
+    }
 }
 
 #[derive(Debug, Args, PartialEq)]
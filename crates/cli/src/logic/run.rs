@@ -21,7 +21,7 @@ fn prompt_answer(
         .prompt()
         .map(|answer| SecurityQuestionAnswerAndSalt {
             question: question.question,
-            answer,
+            answer: answer.into(),
             salt: question.salt,
         })
         .map_err(|e| Error::InvalidAnswer {
@@ -95,52 +95,93 @@ fn get_answers_from_questions(
     Ok(answers)
 }
 
+/// The security questions to seal a new secret with, with newly generated
+/// salts.
+fn generate_questions() -> SecurityQuestionsAndSalts<QUESTION_COUNT> {
+    type Q = SecurityQuestionAndSalt;
+    SecurityQuestionsAndSalts::<QUESTION_COUNT>::try_from_iter([
+        Q::generate_salt(SecurityQuestion::q00()),
+        Q::generate_salt(SecurityQuestion::q01()),
+        Q::generate_salt(SecurityQuestion::q02()),
+        Q::generate_salt(SecurityQuestion::q03()),
+    ])
+    .unwrap()
+}
+
+/// Protects the file at `input_path_secret` by prompting the user for answers
+/// to security questions, sealing it as a stream so that it is never read
+/// into memory as a whole.
+fn protect_file(
+    input_path_secret: impl AsRef<Path>,
+    output_path_sealed: impl AsRef<Path>,
+) -> Result<()> {
+    let input_path_secret = input_path_secret.as_ref();
+    let output_path_sealed = output_path_sealed.as_ref();
+
+    let secret_file = fs::File::open(input_path_secret).map_err(|e| {
+        Error::FailedToReadSecretFromFile {
+            file_path: input_path_secret.display().to_string(),
+            underlying: e.to_string(),
+        }
+    })?;
+
+    let answers = get_answers_from_questions(generate_questions())?;
+    info!("All answers received, now sealing the secret...");
+
+    let sealed_file = fs::File::create(output_path_sealed).map_err(|e| {
+        Error::FailedToWriteSealedSecretToFile {
+            file_path: output_path_sealed.display().to_string(),
+            underlying: e.to_string(),
+        }
+    })?;
+
+    debug!(
+        "Sealing secret from file: {} to file: {}",
+        input_path_secret.display(),
+        output_path_sealed.display()
+    );
+    SecurityQuestionsSealedStream::seal(
+        std::io::BufReader::new(secret_file),
+        std::io::BufWriter::new(sealed_file),
+        answers.iter().cloned(),
+        MIN_ANSWER_COUNT,
+    )?;
+    info!(
+        "Saved sealed secret to file: {}",
+        output_path_sealed.display()
+    );
+
+    Ok(())
+}
+
 /// Protects a new secret by prompting the user for a secret and security
 /// questions and answers.
 fn protect_new_secret(
     maybe_input_path_secret: Option<PathBuf>,
     output_path_sealed: impl AsRef<Path>,
 ) -> Result<()> {
-    let secret_to_protect = {
-        if let Some(path) = maybe_input_path_secret {
-            std::fs::read_to_string(path.clone()).map_err(|e| {
-                Error::FailedToReadSecretFromFile {
-                    file_path: path.display().to_string(),
-                    underlying: e.to_string(),
-                }
-            })
-        } else {
-            inquire::Password::new("Enter the secret to protect:")
-                .with_display_toggle_enabled()
-                .with_display_mode(PasswordDisplayMode::Hidden)
-                .without_confirmation()
-                .with_formatter(&|_| String::from("Input received"))
-                .with_help_message(
-                    "Press CTRL+R to toggle reveal/hide your input.",
-                )
-                .prompt()
-                .map_err(|e| Error::FailedToInputSecret {
-                    underlying: e.to_string(),
-                })
-        }
-    }?;
+    if let Some(path) = maybe_input_path_secret {
+        return protect_file(path, output_path_sealed);
+    }
+
+    let secret_to_protect =
+        inquire::Password::new("Enter the secret to protect:")
+            .with_display_toggle_enabled()
+            .with_display_mode(PasswordDisplayMode::Hidden)
+            .without_confirmation()
+            .with_formatter(&|_| String::from("Input received"))
+            .with_help_message("Press CTRL+R to toggle reveal/hide your input.")
+            .prompt()
+            .map_err(|e| Error::FailedToInputSecret {
+                underlying: e.to_string(),
+            })?;
 
     info!(
         "Secret to protect received: #{} chars",
         secret_to_protect.len()
     );
 
-    type Q = SecurityQuestionAndSalt;
-    let questions =
-        SecurityQuestionsAndSalts::<QUESTION_COUNT>::try_from_iter([
-            Q::generate_salt(SecurityQuestion::q00()),
-            Q::generate_salt(SecurityQuestion::q01()),
-            Q::generate_salt(SecurityQuestion::q02()),
-            Q::generate_salt(SecurityQuestion::q03()),
-        ])
-        .unwrap();
-
-    let answers = get_answers_from_questions(questions)?;
+    let answers = get_answers_from_questions(generate_questions())?;
     info!("All answers received, now sealing the secret...");
 
     debug!("Sealing the secret with questions and answers...");
@@ -181,11 +222,120 @@ fn protect_new_secret(
     Ok(())
 }
 
+/// Warns about each question whose answer was incorrect.
+fn warn_about_incorrect_answers(
+    questions: &[SecurityQuestionAndSalt],
+    incorrect_question_ids: Vec<u16>,
+) {
+    for question_and_salt in questions {
+        let question = &question_and_salt.question;
+        if incorrect_question_ids.contains(&question.id) {
+            warn!(
+                "Your answer to \"{}\" was incorrect, consider sealing your secret again with an updated answer.",
+                question.question
+            );
+        }
+    }
+}
+
+/// Opens a secret sealed as a stream from `sealed_file`, writing it to
+/// `output_path_secret` if provided, otherwise printing it in the terminal
+/// if the user wants to.
+fn open_sealed_stream(
+    mut sealed_file: impl Read,
+    output_path_secret: Option<PathBuf>,
+) -> Result<()> {
+    debug!("Reading header of sealed secret...");
+    let stream = SecurityQuestionsSealedStream::read_header(&mut sealed_file)?;
+    let questions = SecurityQuestionsAndSalts::<QUESTION_COUNT>::try_from_iter(
+        stream.security_questions_and_salts().iter().cloned(),
+    )?;
+
+    let answers = get_answers_from_questions(questions)?;
+
+    let report = if let Some(path) = output_path_secret {
+        info!("All answers received, now decrypting the sealed secret...");
+        let secret_file = fs::File::create(&path).map_err(|e| {
+            Error::FailedToWriteSecretToFile {
+                file_path: path.display().to_string(),
+                underlying: e.to_string(),
+            }
+        })?;
+        let report = stream
+            .open_with_report(
+                sealed_file,
+                std::io::BufWriter::new(secret_file),
+                answers.iter().cloned(),
+            )
+            .inspect_err(|_| {
+                // Do not leave a partially decrypted secret behind.
+                let _ = fs::remove_file(&path);
+            })?;
+        info!("Saved opened secret to file: {}", path.display());
+        report
+    } else {
+        // The secret is decrypted chunk by chunk, so we must ask before
+        // decrypting if it should be printed.
+        let reveal_secret = inquire::Confirm::new(
+            "Do you want to print it in the terminal once decrypted?",
+        )
+        .with_default(false)
+        .prompt()
+        .unwrap_or_default();
+
+        info!("All answers received, now decrypting the sealed secret...");
+        if reveal_secret {
+            stream.open_with_report(
+                sealed_file,
+                std::io::stdout().lock(),
+                answers.iter().cloned(),
+            )?
+        } else {
+            stream.open_with_report(
+                sealed_file,
+                std::io::sink(),
+                answers.iter().cloned(),
+            )?
+        }
+    };
+    info!("Sealed secret decrypted successfully.");
+
+    warn_about_incorrect_answers(
+        stream.security_questions_and_salts(),
+        report.incorrect_question_ids(),
+    );
+
+    Ok(())
+}
+
 /// Opens a secret by prompting the user for answers to security questions.
-fn open_sealed_secret_at(file_path: impl AsRef<Path>) -> Result<()> {
+fn open_sealed_secret_at(
+    file_path: impl AsRef<Path>,
+    output_path_secret: Option<PathBuf>,
+) -> Result<()> {
     let file_path = file_path.as_ref();
     info!("Opening sealed secret from file: {}", file_path.display());
 
+    let read_failed =
+        |e: std::io::Error| Error::FailedToReadSealedSecretFromFile {
+            file_path: file_path.display().to_string(),
+            underlying: e.to_string(),
+        };
+    let mut sealed_file = fs::File::open(file_path).map_err(read_failed)?;
+    let mut magic = Vec::new();
+    sealed_file
+        .by_ref()
+        .take(8)
+        .read_to_end(&mut magic)
+        .map_err(read_failed)?;
+    sealed_file.rewind().map_err(read_failed)?;
+    if SecurityQuestionsSealedStream::is_sealed_stream(&magic) {
+        return open_sealed_stream(
+            std::io::BufReader::new(sealed_file),
+            output_path_secret,
+        );
+    }
+
     let sealed_json = fs::read_to_string(file_path).map_err(|e| {
         Error::FailedToWriteSealedSecretToFile {
             file_path: file_path.display().to_string(),
@@ -210,9 +360,25 @@ fn open_sealed_secret_at(file_path: impl AsRef<Path>) -> Result<()> {
     )?;
 
     info!("All answers received, now decrypting the sealed secret...");
-    let opened = sealed.open(answers)?;
+    let (opened, report) = sealed.open_with_report(answers.iter().cloned())?;
     info!("Sealed secret decrypted successfully.");
 
+    warn_about_incorrect_answers(
+        &*sealed.security_questions_and_salts,
+        report.incorrect_question_ids(),
+    );
+
+    if let Some(path) = output_path_secret {
+        fs::write(&path, opened).map_err(|e| {
+            Error::FailedToWriteSecretToFile {
+                file_path: path.display().to_string(),
+                underlying: e.to_string(),
+            }
+        })?;
+        info!("Saved opened secret to file: {}", path.display());
+        return Ok(());
+    }
+
     let reveal_secret =
         inquire::Confirm::new("Do you want to print it in the terminal?")
             .with_default(false)
@@ -227,7 +393,7 @@ fn open_sealed_secret_at(file_path: impl AsRef<Path>) -> Result<()> {
 }
 
 fn open(input: OpenInput) -> Result<()> {
-    open_sealed_secret_at(input.sealed_path())
+    open_sealed_secret_at(input.sealed_path(), input.secret_path())
 }
 
 fn ask_if_override_existing_sealed_secret(input: &SealInput) -> Result<()> {
@@ -44,8 +44,10 @@
 //! ```
 //!
 //! When the `-i` flag is provided, the program will not prompt you to input
-//! the secret, but will read it from the specified file instead. The sealed
-//! secret will be written to the path specified by the `-o` flag.
+//! the secret, but will read it from the specified file instead. The file is
+//! sealed as a stream, chunk by chunk, so it can be of any size and need not
+//! be text. The sealed secret will be written to the path specified by the
+//! `-o` flag.
 //!
 //! ## Open (Decrypt)
 //! You can open a sealed secret using the `open` command:
@@ -69,6 +71,12 @@
 //! svar open -i /path/to/sealed_secret.json
 //! ```
 //!
+//! A secret sealed from a file using the `-i` flag of `seal` can be written to
+//! a file using the `-o` flag:
+//! ```sh,no_run
+//! svar open -i /path/to/sealed_secret.json -o /path/to/your/secret.txt
+//! ```
+//!
 //! > [!TIP]
 //! > When decrypting a sealed secret, try inputting an incorrect answer to any
 //! > of the questions and it will still decrypt the secret. You can also notice
@@ -93,10 +101,12 @@ pub mod prelude {
         SecurityQuestion, SecurityQuestionAndSalt,
         SecurityQuestionAnswerAndSalt, SecurityQuestionsAndSalts,
         SecurityQuestionsAnswersAndSalts, SecurityQuestionsSealed,
+        SecurityQuestionsSealedStream,
     };
 
     pub use std::{
         fs,
+        io::{Read, Seek},
         path::{Path, PathBuf},
     };
 
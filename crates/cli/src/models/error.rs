@@ -42,4 +42,20 @@ pub enum Error {
         file_path: String,
         underlying: String,
     },
+
+    #[error(
+        "Failed to read sealed secret from file: '{file_path}', underlying: {underlying}"
+    )]
+    FailedToReadSealedSecretFromFile {
+        file_path: String,
+        underlying: String,
+    },
+
+    #[error(
+        "Failed to write opened secret to file: '{file_path}', underlying: {underlying}"
+    )]
+    FailedToWriteSecretToFile {
+        file_path: String,
+        underlying: String,
+    },
 }
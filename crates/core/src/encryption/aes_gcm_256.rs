@@ -2,7 +2,7 @@ use crate::prelude::*;
 
 use aes_gcm::{
     Key,
-    aead::{Aead, AeadCore, KeyInit, OsRng},
+    aead::{Aead, AeadCore, KeyInit, OsRng, Payload},
 };
 
 /// AES GCM 256 encryption
@@ -23,10 +23,12 @@ impl AesGcm256 {
 }
 
 impl AesGcm256 {
-    /// Encrypts the provided plaintext using the given encryption key.
+    /// Encrypts the provided plaintext using the given encryption key,
+    /// authenticating `associated_data`.
     fn seal(
         plaintext: impl AsRef<[u8]>,
         encryption_key: impl Into<Key<aes_gcm::Aes256Gcm>>,
+        associated_data: impl AsRef<[u8]>,
     ) -> AesGcmSealedBox {
         let encryption_key = encryption_key.into();
         let cipher = aes_gcm::Aes256Gcm::new(&encryption_key);
@@ -34,7 +36,13 @@ impl AesGcm256 {
         let nonce = aes_gcm::Aes256Gcm::generate_nonce(&mut OsRng);
 
         let cipher_text = cipher
-            .encrypt(&nonce, plaintext.as_ref())
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: plaintext.as_ref(),
+                    aad: associated_data.as_ref(),
+                },
+            )
             .expect("AES encrypt never fails for valid nonce.");
 
         let nonce = ExactlyNBytes::<NONCE_LEN>::try_from(nonce.as_slice())
@@ -43,16 +51,24 @@ impl AesGcm256 {
         AesGcmSealedBox { nonce, cipher_text }
     }
 
-    /// Decrypts the provided sealed box using the given decryption key.
+    /// Decrypts the provided sealed box using the given decryption key,
+    /// failing if `associated_data` is not the one it was sealed with.
     fn open(
         sealed_box: AesGcmSealedBox,
         decryption_key: impl Into<Key<aes_gcm::Aes256Gcm>>,
+        associated_data: impl AsRef<[u8]>,
     ) -> Result<Vec<u8>> {
         let decryption_key = decryption_key.into();
         let cipher = aes_gcm::Aes256Gcm::new(&decryption_key);
         let cipher_text = sealed_box.cipher_text;
         cipher
-            .decrypt(sealed_box.nonce.as_ref().into(), cipher_text.as_ref())
+            .decrypt(
+                sealed_box.nonce.as_ref().into(),
+                Payload {
+                    msg: cipher_text.as_ref(),
+                    aad: associated_data.as_ref(),
+                },
+            )
             .map_err(|e| Error::AESDecryptionFailed {
                 underlying: e.to_string(),
             })
@@ -73,23 +89,25 @@ impl VersionOfAlgorithm for AesGcm256 {
 
 impl VersionedEncryption for AesGcm256 {
     /// Zeroizes `encryption_key` after usage.
-    fn encrypt(
+    fn encrypt_with_associated_data(
         &self,
         plaintext: impl AsRef<[u8]>,
         encryption_key: EncryptionKey,
+        associated_data: impl AsRef<[u8]>,
     ) -> Vec<u8> {
-        let sealed_box = Self::seal(plaintext, encryption_key);
+        let sealed_box = Self::seal(plaintext, encryption_key, associated_data);
         sealed_box.combined()
     }
 
     /// Zeroizes `decryption_key` after usage.
-    fn decrypt(
+    fn decrypt_with_associated_data(
         &self,
         cipher_text: impl AsRef<[u8]>,
         decryption_key: EncryptionKey,
+        associated_data: impl AsRef<[u8]>,
     ) -> Result<Vec<u8>> {
         let sealed_box = AesGcmSealedBox::try_from(cipher_text.as_ref())?;
-        Self::open(sealed_box, decryption_key)
+        Self::open(sealed_box, decryption_key, associated_data)
     }
 }
 
@@ -116,7 +134,8 @@ mod tests {
                 },
                 Key::<aes_gcm::Aes256Gcm>::from(
                     *Exactly32Bytes::sample_aced().bytes()
-                )
+                ),
+                []
             ),
             Err(Error::AESDecryptionFailed {
                 underlying: "aead::Error".to_owned()
@@ -0,0 +1,187 @@
+use crate::prelude::*;
+
+use aes_gcm_siv::{
+    Aes256GcmSiv, Key, Nonce,
+    aead::{Aead, AeadCore, KeyInit, OsRng, Payload},
+};
+
+/// AES GCM SIV 256 encryption, a nonce-misuse resistant AEAD: encrypting with
+/// a reused nonce only reveals whether two plaintexts are equal, instead of
+/// compromising confidentiality and authenticity like AES GCM does.
+///
+/// Uses the same layout as [`AesGcm256`], a 12 bytes nonce followed by the
+/// cipher text and 16 bytes authentication tag, see [`AesGcmSealedBox`].
+#[derive(
+    Clone,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    derive_more::Display,
+    derive_more::Debug,
+)]
+pub struct AesGcmSiv256;
+
+impl AesGcmSiv256 {
+    /// The description of the AES GCM SIV 256 encryption scheme.
+    pub const DESCRIPTION: &'static str = "AESGCMSIV-256";
+}
+
+impl AesGcmSiv256 {
+    /// Encrypts the provided plaintext using the given encryption key,
+    /// authenticating `associated_data`.
+    fn seal(
+        plaintext: impl AsRef<[u8]>,
+        encryption_key: EncryptionKey,
+        associated_data: impl AsRef<[u8]>,
+    ) -> AesGcmSealedBox {
+        let encryption_key =
+            Key::<Aes256GcmSiv>::from(*encryption_key.0.bytes());
+        let cipher = Aes256GcmSiv::new(&encryption_key);
+
+        let nonce = Aes256GcmSiv::generate_nonce(&mut OsRng);
+
+        let cipher_text = cipher
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: plaintext.as_ref(),
+                    aad: associated_data.as_ref(),
+                },
+            )
+            .expect("AES GCM SIV encrypt never fails for valid nonce.");
+
+        let nonce = ExactlyNBytes::<NONCE_LEN>::try_from(nonce.as_slice())
+            .expect("AesGcmSiv should always use fixed nonce byte count");
+
+        AesGcmSealedBox { nonce, cipher_text }
+    }
+
+    /// Decrypts the provided sealed box using the given decryption key,
+    /// failing if `associated_data` is not the one it was sealed with.
+    fn open(
+        sealed_box: AesGcmSealedBox,
+        decryption_key: EncryptionKey,
+        associated_data: impl AsRef<[u8]>,
+    ) -> Result<Vec<u8>> {
+        let decryption_key =
+            Key::<Aes256GcmSiv>::from(*decryption_key.0.bytes());
+        let cipher = Aes256GcmSiv::new(&decryption_key);
+        let cipher_text = sealed_box.cipher_text;
+        cipher
+            .decrypt(
+                Nonce::from_slice(sealed_box.nonce.as_ref()),
+                Payload {
+                    msg: cipher_text.as_ref(),
+                    aad: associated_data.as_ref(),
+                },
+            )
+            .map_err(|e| Error::AESDecryptionFailed {
+                underlying: e.to_string(),
+            })
+    }
+}
+
+impl VersionOfAlgorithm for AesGcmSiv256 {
+    type Version = EncryptionSchemeVersion;
+
+    fn version(&self) -> Self::Version {
+        Self::Version::Version3
+    }
+
+    fn description(&self) -> String {
+        Self::DESCRIPTION.to_owned()
+    }
+}
+
+impl VersionedEncryption for AesGcmSiv256 {
+    /// Zeroizes `encryption_key` after usage.
+    fn encrypt_with_associated_data(
+        &self,
+        plaintext: impl AsRef<[u8]>,
+        encryption_key: EncryptionKey,
+        associated_data: impl AsRef<[u8]>,
+    ) -> Vec<u8> {
+        let sealed_box = Self::seal(plaintext, encryption_key, associated_data);
+        sealed_box.combined()
+    }
+
+    /// Zeroizes `decryption_key` after usage.
+    fn decrypt_with_associated_data(
+        &self,
+        cipher_text: impl AsRef<[u8]>,
+        decryption_key: EncryptionKey,
+        associated_data: impl AsRef<[u8]>,
+    ) -> Result<Vec<u8>> {
+        let sealed_box = AesGcmSealedBox::try_from(cipher_text.as_ref())?;
+        Self::open(sealed_box, decryption_key, associated_data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    type Sut = AesGcmSiv256;
+
+    #[test]
+    fn test_fail() {
+        assert_eq!(
+            Sut::open(
+                AesGcmSealedBox {
+                    nonce: Exactly12Bytes::sample(),
+                    cipher_text: hex_decode("deadbeef").unwrap(),
+                },
+                EncryptionKey::from(Exactly32Bytes::sample_aced()),
+                []
+            ),
+            Err(Error::AESDecryptionFailed {
+                underlying: "aead::Error".to_owned()
+            })
+        )
+    }
+
+    #[test]
+    fn encryption_roundtrip() {
+        let sut = Sut::default();
+        let encrypted = sut.encrypt(b"open zesame", EncryptionKey::sample());
+        assert_eq!(encrypted.len(), NONCE_LEN + AUTH_TAG_LEN + 11);
+        let decrypted =
+            sut.decrypt(encrypted, EncryptionKey::sample()).unwrap();
+        assert_eq!(decrypted, b"open zesame");
+    }
+
+    #[test]
+    fn reused_nonce_only_reveals_equal_plaintexts() {
+        let nonce_bytes = Exactly12Bytes::sample();
+        let nonce = Nonce::from_slice(nonce_bytes.as_ref());
+        let key = Key::<Aes256GcmSiv>::from(*Exactly32Bytes::sample().bytes());
+        let cipher = Aes256GcmSiv::new(&key);
+        let encrypt = |plaintext: &[u8]| cipher.encrypt(nonce, plaintext);
+
+        assert_eq!(encrypt(b"abba").unwrap(), encrypt(b"abba").unwrap());
+        // Unlike AES GCM, the key stream depends on the plaintext, so XOR of
+        // the cipher texts of two plaintexts is not the XOR of the plaintexts
+        let a = encrypt(b"abba").unwrap();
+        let b = encrypt(b"baba").unwrap();
+        let xor = a.iter().zip(b.iter()).map(|(a, b)| a ^ b).collect_vec();
+        let plaintext_xor = b"abba"
+            .iter()
+            .zip(b"baba".iter())
+            .map(|(a, b)| a ^ b)
+            .collect_vec();
+        assert_ne!(&xor[..4], plaintext_xor.as_slice());
+    }
+
+    #[test]
+    fn aes_gcm_cannot_decrypt() {
+        let encrypted =
+            Sut::default().encrypt(b"abba", EncryptionKey::sample());
+        assert!(
+            AesGcm256
+                .decrypt(encrypted, EncryptionKey::sample())
+                .is_err()
+        );
+    }
+}
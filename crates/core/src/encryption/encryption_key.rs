@@ -4,7 +4,16 @@ use crate::prelude::*;
 
 /// A 32 bytes encryption key used for symmetric encryption.
 ///
-/// Zeroizes its contents when dropped.
+/// Zeroizes its contents when dropped and never prints them, both
+/// [`Display`] and [`Debug`] are redacted.
+///
+/// ```
+/// use svar_core::*;
+///
+/// let key = EncryptionKey::sample();
+/// assert_eq!(key.to_string(), "EncryptionKey(<redacted>)");
+/// assert_eq!(format!("{key:?}"), "EncryptionKey(<redacted>)");
+/// ```
 #[derive(
     ZeroizeOnDrop,
     Zeroize,
@@ -19,6 +28,8 @@ use crate::prelude::*;
     Hash,
 )]
 #[serde(transparent)]
+#[display("EncryptionKey(<redacted>)")]
+#[debug("EncryptionKey(<redacted>)")]
 pub struct EncryptionKey(pub Exactly32Bytes);
 
 impl EncryptionKey {
@@ -34,6 +45,24 @@ impl From<Exactly32Bytes> for EncryptionKey {
     }
 }
 
+/// Allows sealing a key, e.g. the data key of a
+/// [`SecurityQuestionsSealedStream`].
+impl IsSecret for EncryptionKey {
+    fn from_bytes(
+        bytes: SecretBytes,
+    ) -> std::result::Result<Self, Box<dyn std::error::Error>> {
+        Exactly32Bytes::try_from(bytes.as_ref())
+            .map(Self::from)
+            .map_err(|e| e.into())
+    }
+
+    fn to_bytes(
+        &self,
+    ) -> std::result::Result<SecretBytes, Box<dyn std::error::Error>> {
+        Ok(SecretBytes::from(self.0.bytes().as_slice()))
+    }
+}
+
 impl HasSampleValues for EncryptionKey {
     fn sample() -> Self {
         Self::from(Exactly32Bytes::sample())
@@ -60,4 +89,24 @@ mod tests {
     fn inequality() {
         assert_ne!(Sut::sample(), Sut::sample_other());
     }
+
+    #[test]
+    fn formatting_does_not_reveal_key() {
+        let sut = Sut::sample();
+        let hex = sut.0.to_hex();
+        for formatted in [sut.to_string(), format!("{sut:?}")] {
+            assert!(!formatted.contains(&hex[..8]), "{formatted}");
+        }
+    }
+
+    #[test]
+    fn secret_bytes_roundtrip() {
+        let bytes = Sut::sample().to_bytes().unwrap();
+        assert_eq!(Sut::from_bytes(bytes).unwrap(), Sut::sample());
+    }
+
+    #[test]
+    fn from_bytes_wrong_length_is_err() {
+        assert!(Sut::from_bytes(SecretBytes::from(vec![0xab; 31])).is_err());
+    }
 }
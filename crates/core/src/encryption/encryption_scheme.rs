@@ -9,12 +9,22 @@ use crate::prelude::*;
 /// algorithm upgrades while maintaining backwards compatibility with older
 /// encrypted data.
 ///
-/// Currently, only AES-256-GCM is supported, but the versioned design allows
-/// for future algorithm additions without breaking existing implementations.
+/// AES-256-GCM is the default, XChaCha20-Poly1305 is available for targets
+/// without AES hardware acceleration and AES-256-GCM-SIV for nonce-misuse
+/// resistance. The versioned design allows for future algorithm additions
+/// without breaking existing implementations.
 ///
 /// # Supported Algorithms
 ///
 /// - **Version 1**: AES-256-GCM with 96-bit IV and 128-bit authentication tag
+/// - **Version 2**: XChaCha20-Poly1305 with 192-bit nonce and 128-bit
+///   authentication tag
+/// - **Version 3**: AES-256-GCM-SIV with 96-bit nonce and 128-bit
+///   authentication tag
+///
+/// Each version records the [`PaddingPolicy`] the plaintext is padded with
+/// before it is encrypted, which is [`PaddingPolicy::None`] by default, see
+/// [`with_padding`](Self::with_padding).
 ///
 /// # Examples
 ///
@@ -41,6 +51,12 @@ use crate::prelude::*;
 ///
 /// let scheme_v1 = EncryptionScheme::version1();
 /// assert_eq!(scheme_v1.version(), EncryptionSchemeVersion::Version1);
+///
+/// let scheme_v2 = EncryptionScheme::version2();
+/// assert_eq!(scheme_v2.version(), EncryptionSchemeVersion::Version2);
+///
+/// let scheme_v3 = EncryptionScheme::version3();
+/// assert_eq!(scheme_v3.version(), EncryptionSchemeVersion::Version3);
 /// ```
 ///
 /// ## Version Comparison
@@ -83,6 +99,23 @@ use crate::prelude::*;
 /// - **Security**: Provides both confidentiality and authenticity
 /// - **Performance**: Hardware-accelerated on most modern processors
 ///
+/// ## XChaCha20-Poly1305 (Version 2)
+/// - **Key Size**: 256 bits (32 bytes)
+/// - **Nonce Size**: 192 bits (24 bytes) - randomly generated per encryption,
+///   large enough that random nonces never collide in practice
+/// - **Tag Size**: 128 bits (16 bytes) - provides authentication
+/// - **Security**: Provides both confidentiality and authenticity
+/// - **Performance**: Fast in software, without hardware acceleration
+///
+/// ## AES-256-GCM-SIV (Version 3)
+/// - **Key Size**: 256 bits (32 bytes)
+/// - **Nonce Size**: 96 bits (12 bytes) - randomly generated per encryption
+/// - **Tag Size**: 128 bits (16 bytes) - also used as synthetic IV
+/// - **Security**: Provides both confidentiality and authenticity, a reused
+///   nonce only reveals whether two plaintexts are equal
+/// - **Performance**: Hardware-accelerated on most modern processors, slower
+///   than AES-256-GCM since the plaintext is processed twice
+///
 /// # Security Considerations
 ///
 /// - Each encryption operation uses a fresh random IV
@@ -106,7 +139,27 @@ pub enum EncryptionScheme {
     /// - 96-bit initialization vector (IV)
     /// - 128-bit authentication tag
     /// - AEAD (Authenticated Encryption with Associated Data) properties
-    Version1(AesGcm256),
+    Version1(AesGcm256, PaddingPolicy),
+
+    /// XChaCha20-Poly1305 encryption (Version 2).
+    ///
+    /// Uses the ChaCha20 stream cipher with an extended nonce and the
+    /// Poly1305 authenticator with:
+    /// - 256-bit key size for strong security
+    /// - 192-bit nonce
+    /// - 128-bit authentication tag
+    /// - AEAD (Authenticated Encryption with Associated Data) properties
+    Version2(XChaCha20Poly1305, PaddingPolicy),
+
+    /// AES-256-GCM-SIV encryption (Version 3).
+    ///
+    /// Uses AES in Galois/Counter Mode with Synthetic Initialization Vector,
+    /// a nonce-misuse resistant variant of AES-256-GCM, with:
+    /// - 256-bit key size for strong security
+    /// - 96-bit nonce
+    /// - 128-bit authentication tag
+    /// - AEAD (Authenticated Encryption with Associated Data) properties
+    Version3(AesGcmSiv256, PaddingPolicy),
 }
 
 /// Display implementation for `EncryptionScheme`.
@@ -116,7 +169,8 @@ pub enum EncryptionScheme {
 ///
 /// # Format
 ///
-/// `EncryptionScheme: {version} ({description})`
+/// `EncryptionScheme: {version} ({description})`, followed by
+/// `, padding: {padding}` if the plaintext is padded.
 ///
 /// # Examples
 ///
@@ -137,7 +191,11 @@ impl std::fmt::Display for EncryptionScheme {
             "EncryptionScheme: {} ({})",
             self.version(),
             self.description()
-        )
+        )?;
+        if !self.padding().is_none() {
+            write!(f, ", padding: {}", self.padding())?;
+        }
+        Ok(())
     }
 }
 
@@ -146,9 +204,16 @@ impl Serialize for EncryptionScheme {
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct("EncryptionScheme", 2)?;
+        let mut state = serializer.serialize_struct("EncryptionScheme", 3)?;
         state.serialize_field("description", &self.description())?;
         state.serialize_field("version", &self.version())?;
+        // Omitted when not padding, so that the JSON of schemes from before
+        // padding was introduced is unchanged
+        if self.padding().is_none() {
+            state.skip_field("padding")?;
+        } else {
+            state.serialize_field("padding", &self.padding())?;
+        }
         state.end()
     }
 }
@@ -160,9 +225,14 @@ impl<'de> Deserialize<'de> for EncryptionScheme {
         #[derive(Deserialize, Serialize)]
         struct Wrapper {
             version: EncryptionSchemeVersion,
+            #[serde(default)]
+            padding: PaddingPolicy,
         }
-        Wrapper::deserialize(deserializer)
-            .and_then(|w| Self::try_from(w.version).map_err(de::Error::custom))
+        Wrapper::deserialize(deserializer).and_then(|w| {
+            Self::try_from(w.version)
+                .map(|scheme| scheme.with_padding(w.padding))
+                .map_err(de::Error::custom)
+        })
     }
 }
 
@@ -200,7 +270,82 @@ impl EncryptionScheme {
     /// - 128-bit (16-byte) authentication tag
     /// - Authenticated Encryption with Associated Data (AEAD)
     pub fn version1() -> Self {
-        Self::Version1(AesGcm256)
+        Self::Version1(AesGcm256, PaddingPolicy::None)
+    }
+
+    /// Creates a Version 2 encryption scheme using XChaCha20-Poly1305.
+    ///
+    /// Prefer this over [`version1`](Self::version1) on targets without AES
+    /// hardware acceleration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svar_core::*;
+    ///
+    /// let scheme = EncryptionScheme::version2();
+    /// assert_eq!(scheme.version(), EncryptionSchemeVersion::Version2);
+    ///
+    /// let key = EncryptionKey::generate();
+    /// let encrypted = scheme.encrypt(b"test data", key.clone());
+    /// assert_eq!(scheme.decrypt(&encrypted, key)?, b"test data");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn version2() -> Self {
+        Self::Version2(XChaCha20Poly1305, PaddingPolicy::None)
+    }
+
+    /// Creates a Version 3 encryption scheme using AES-256-GCM-SIV.
+    ///
+    /// Prefer this over [`version1`](Self::version1) for long-lived secrets
+    /// which may be re-encrypted many times, since it remains secure even if
+    /// a nonce is reused.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svar_core::*;
+    ///
+    /// let scheme = EncryptionScheme::version3();
+    /// assert_eq!(scheme.version(), EncryptionSchemeVersion::Version3);
+    ///
+    /// let key = EncryptionKey::generate();
+    /// let encrypted = scheme.encrypt(b"test data", key.clone());
+    /// assert_eq!(scheme.decrypt(&encrypted, key)?, b"test data");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn version3() -> Self {
+        Self::Version3(AesGcmSiv256, PaddingPolicy::None)
+    }
+
+    /// Returns this scheme padding plaintexts according to `padding` before
+    /// encrypting them, hiding their exact length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svar_core::*;
+    ///
+    /// let scheme =
+    ///     EncryptionScheme::version2().with_padding(PaddingPolicy::sample());
+    /// assert_eq!(scheme.version(), EncryptionSchemeVersion::Version2);
+    /// assert_eq!(scheme.padding(), PaddingPolicy::sample());
+    /// ```
+    pub fn with_padding(self, padding: PaddingPolicy) -> Self {
+        match self {
+            Self::Version1(scheme, _) => Self::Version1(scheme, padding),
+            Self::Version2(scheme, _) => Self::Version2(scheme, padding),
+            Self::Version3(scheme, _) => Self::Version3(scheme, padding),
+        }
+    }
+
+    /// The policy plaintexts are padded with before being encrypted.
+    pub fn padding(&self) -> PaddingPolicy {
+        match self {
+            Self::Version1(_, padding)
+            | Self::Version2(_, padding)
+            | Self::Version3(_, padding) => *padding,
+        }
     }
 }
 
@@ -235,32 +380,75 @@ impl Default for EncryptionScheme {
 
 impl VersionedEncryption for EncryptionScheme {
     /// Encrypts `plaintext` using `encryption_key` using
-    /// the `self` `EncryptionScheme`, returning the cipher text as `Vec<u8>`.
-    fn encrypt(
+    /// the `self` `EncryptionScheme`, authenticating `associated_data`,
+    /// returning the cipher text as `Vec<u8>`.
+    ///
+    /// The plaintext is first padded according to the
+    /// [`padding`](Self::padding) of the scheme.
+    fn encrypt_with_associated_data(
         &self,
         plaintext: impl AsRef<[u8]>,
         encryption_key: EncryptionKey,
+        associated_data: impl AsRef<[u8]>,
     ) -> Vec<u8> {
-        match self {
-            EncryptionScheme::Version1(scheme) => {
-                scheme.encrypt(plaintext, encryption_key)
-            }
-        }
+        let mut plaintext = self.padding().pad(plaintext.as_ref());
+        let encrypted = match self {
+            EncryptionScheme::Version1(scheme, _) => scheme
+                .encrypt_with_associated_data(
+                    &plaintext,
+                    encryption_key,
+                    associated_data,
+                ),
+            EncryptionScheme::Version2(scheme, _) => scheme
+                .encrypt_with_associated_data(
+                    &plaintext,
+                    encryption_key,
+                    associated_data,
+                ),
+            EncryptionScheme::Version3(scheme, _) => scheme
+                .encrypt_with_associated_data(
+                    &plaintext,
+                    encryption_key,
+                    associated_data,
+                ),
+        };
+        plaintext.zeroize();
+        encrypted
     }
 
     /// Tries to decrypt the `cipher_text` using the `decryption_key` according
     /// to the `self` `EncryptionScheme`, returning the plaintext if operation
-    /// was successful.
-    fn decrypt(
+    /// was successful and `associated_data` is the one it was encrypted with.
+    ///
+    /// The padding added when encrypting is removed, failing with
+    /// [`Error::InvalidPadding`] if it is not valid.
+    fn decrypt_with_associated_data(
         &self,
         cipher_text: impl AsRef<[u8]>,
         decryption_key: EncryptionKey,
+        associated_data: impl AsRef<[u8]>,
     ) -> Result<Vec<u8>> {
-        match self {
-            EncryptionScheme::Version1(scheme) => {
-                scheme.decrypt(cipher_text, decryption_key)
-            }
-        }
+        let padded = match self {
+            EncryptionScheme::Version1(scheme, _) => scheme
+                .decrypt_with_associated_data(
+                    cipher_text,
+                    decryption_key,
+                    associated_data,
+                ),
+            EncryptionScheme::Version2(scheme, _) => scheme
+                .decrypt_with_associated_data(
+                    cipher_text,
+                    decryption_key,
+                    associated_data,
+                ),
+            EncryptionScheme::Version3(scheme, _) => scheme
+                .decrypt_with_associated_data(
+                    cipher_text,
+                    decryption_key,
+                    associated_data,
+                ),
+        }?;
+        self.padding().unpad(padded)
     }
 }
 
@@ -270,6 +458,8 @@ impl TryFrom<EncryptionSchemeVersion> for EncryptionScheme {
     fn try_from(value: EncryptionSchemeVersion) -> Result<Self> {
         match value {
             EncryptionSchemeVersion::Version1 => Ok(Self::version1()),
+            EncryptionSchemeVersion::Version2 => Ok(Self::version2()),
+            EncryptionSchemeVersion::Version3 => Ok(Self::version3()),
         }
     }
 }
@@ -279,13 +469,17 @@ impl VersionOfAlgorithm for EncryptionScheme {
 
     fn version(&self) -> Self::Version {
         match self {
-            Self::Version1(scheme) => scheme.version(),
+            Self::Version1(scheme, _) => scheme.version(),
+            Self::Version2(scheme, _) => scheme.version(),
+            Self::Version3(scheme, _) => scheme.version(),
         }
     }
 
     fn description(&self) -> String {
         match self {
-            EncryptionScheme::Version1(scheme) => scheme.description(),
+            EncryptionScheme::Version1(scheme, _) => scheme.description(),
+            EncryptionScheme::Version2(scheme, _) => scheme.description(),
+            EncryptionScheme::Version3(scheme, _) => scheme.description(),
         }
     }
 }
@@ -308,6 +502,92 @@ mod tests {
         assert_json_snapshot!(Sut::default())
     }
 
+    #[test]
+    fn display_version2() {
+        assert_snapshot!(Sut::version2())
+    }
+
+    #[test]
+    fn json_snapshot_version2() {
+        assert_json_snapshot!(Sut::version2())
+    }
+
+    #[test]
+    fn json_roundtrip_version2() {
+        let sut = Sut::version2();
+        let json = serde_json::to_string(&sut).unwrap();
+        let deserialized: Sut = serde_json::from_str(&json).unwrap();
+        assert_eq!(sut, deserialized);
+    }
+
+    #[test]
+    fn display_version3() {
+        assert_snapshot!(Sut::version3())
+    }
+
+    #[test]
+    fn json_snapshot_version3() {
+        assert_json_snapshot!(Sut::version3())
+    }
+
+    #[test]
+    fn json_roundtrip_version3() {
+        let sut = Sut::version3();
+        let json = serde_json::to_string(&sut).unwrap();
+        let deserialized: Sut = serde_json::from_str(&json).unwrap();
+        assert_eq!(sut, deserialized);
+    }
+
+    #[test]
+    fn encryption_roundtrip_version3() {
+        let sut = Sut::version3();
+        let encryption_key = EncryptionKey::generate();
+        let decryption_key = encryption_key.clone();
+        let msg = "open zesame";
+
+        let encrypted = sut.encrypt(msg, encryption_key);
+        let decrypted_bytes = sut.decrypt(encrypted, decryption_key).unwrap();
+
+        assert_eq!(msg, String::from_utf8(decrypted_bytes).unwrap());
+    }
+
+    #[test]
+    fn version1_and_version3_cannot_decrypt_each_other() {
+        let key = EncryptionKey::sample();
+        let encrypted = Sut::version3().encrypt("abba", key.clone());
+        assert!(Sut::version1().decrypt(encrypted, key.clone()).is_err());
+        let encrypted = Sut::version1().encrypt("abba", key.clone());
+        assert!(Sut::version3().decrypt(encrypted, key).is_err());
+    }
+
+    #[test]
+    fn deserialize_unknown_version_is_err() {
+        let result = serde_json::from_str::<Sut>(
+            r#"{"description": "unknown", "version": 99}"#,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn encryption_roundtrip_version2() {
+        let sut = Sut::version2();
+        let encryption_key = EncryptionKey::generate();
+        let decryption_key = encryption_key.clone();
+        let msg = "open zesame";
+
+        let encrypted = sut.encrypt(msg, encryption_key);
+        let decrypted_bytes = sut.decrypt(encrypted, decryption_key).unwrap();
+
+        assert_eq!(msg, String::from_utf8(decrypted_bytes).unwrap());
+    }
+
+    #[test]
+    fn version1_cannot_decrypt_version2() {
+        let key = EncryptionKey::sample();
+        let encrypted = Sut::version2().encrypt("abba", key.clone());
+        assert!(Sut::version1().decrypt(encrypted, key).is_err());
+    }
+
     #[test]
     fn encryption_roundtrip() {
         let sut = Sut::default();
@@ -323,6 +603,60 @@ mod tests {
         assert_eq!(msg, decrypted);
     }
 
+    #[test]
+    fn encryption_roundtrip_with_associated_data() {
+        for sut in [Sut::version1(), Sut::version2(), Sut::version3()] {
+            let encrypted = sut.encrypt_with_associated_data(
+                "open zesame",
+                EncryptionKey::sample(),
+                "header",
+            );
+            let decrypted = sut
+                .decrypt_with_associated_data(
+                    &encrypted,
+                    EncryptionKey::sample(),
+                    "header",
+                )
+                .unwrap();
+            assert_eq!(decrypted, b"open zesame");
+        }
+    }
+
+    #[test]
+    fn decrypt_with_other_associated_data_is_err() {
+        for sut in [Sut::version1(), Sut::version2(), Sut::version3()] {
+            let encrypted = sut.encrypt_with_associated_data(
+                "open zesame",
+                EncryptionKey::sample(),
+                "header",
+            );
+            assert!(
+                sut.decrypt_with_associated_data(
+                    &encrypted,
+                    EncryptionKey::sample(),
+                    "tampered header",
+                )
+                .is_err()
+            );
+            assert!(sut.decrypt(&encrypted, EncryptionKey::sample()).is_err());
+        }
+    }
+
+    #[test]
+    fn empty_associated_data_is_same_as_none() {
+        for sut in [Sut::version1(), Sut::version2(), Sut::version3()] {
+            let encrypted = sut.encrypt("open zesame", EncryptionKey::sample());
+            let decrypted = sut
+                .decrypt_with_associated_data(
+                    encrypted,
+                    EncryptionKey::sample(),
+                    [],
+                )
+                .unwrap();
+            assert_eq!(decrypted, b"open zesame");
+        }
+    }
+
     #[test]
     fn decrypt_known() {
         let sut = Sut::default();
@@ -341,6 +675,48 @@ mod tests {
         );
     }
 
+    #[test]
+    fn decrypt_known_version2() {
+        let sut = Sut::version2();
+        let decryption_key = EncryptionKey::from_str(
+            "deadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeef",
+        )
+        .unwrap();
+        let encrypted = hex_decode(
+            "b687110952f2ba7dfd67b869b996e27f6ac95d4b7cacdd80126f317b3933635dd68c2f4a198557b757e4",
+        )
+        .unwrap();
+        let decrypted = sut.decrypt(encrypted, decryption_key).unwrap();
+        assert_eq!(hex::encode(decrypted), "abba");
+    }
+
+    #[test]
+    fn decrypt_known_version3() {
+        let sut = Sut::version3();
+        let decryption_key = EncryptionKey::from_str(
+            "deadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeef",
+        )
+        .unwrap();
+        let encrypted = hex_decode(
+            "c069a7bcf8448b094035375947773b686e1d6841d9f14f36a5fd4c6ca9be",
+        )
+        .unwrap();
+        let decrypted = sut.decrypt(encrypted, decryption_key).unwrap();
+        assert_eq!(hex::encode(decrypted), "abba");
+    }
+
+    #[test]
+    fn decrypt_invalid_sealed_box_version2_is_err() {
+        let sut = Sut::version2();
+        assert_eq!(
+            sut.decrypt(Vec::new(), EncryptionKey::sample()),
+            Err(Error::InvalidXChaCha20Poly1305BytesTooShort {
+                expected_at_least: XChaCha20Poly1305SealedBox::LOWER_BOUND_LEN,
+                found: 0
+            })
+        );
+    }
+
     #[test]
     fn decrypt_invalid_sealed_box_is_err() {
         let sut = Sut::default();
@@ -352,4 +728,71 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn default_has_no_padding() {
+        for sut in [Sut::version1(), Sut::version2(), Sut::version3()] {
+            assert_eq!(sut.padding(), PaddingPolicy::None);
+        }
+    }
+
+    #[test]
+    fn with_padding_keeps_version() {
+        for sut in [Sut::version1(), Sut::version2(), Sut::version3()] {
+            let padded = sut.clone().with_padding(PaddingPolicy::sample());
+            assert_eq!(padded.version(), sut.version());
+            assert_eq!(padded.padding(), PaddingPolicy::sample());
+            assert_ne!(padded, sut);
+        }
+    }
+
+    #[test]
+    fn display_padded() {
+        assert_snapshot!(Sut::default().with_padding(PaddingPolicy::sample()))
+    }
+
+    #[test]
+    fn json_snapshot_padded() {
+        assert_json_snapshot!(
+            Sut::version2().with_padding(PaddingPolicy::sample())
+        )
+    }
+
+    #[test]
+    fn json_roundtrip_padded() {
+        for padding in [PaddingPolicy::sample(), PaddingPolicy::sample_other()]
+        {
+            let sut = Sut::version3().with_padding(padding);
+            let json = serde_json::to_string(&sut).unwrap();
+            let deserialized: Sut = serde_json::from_str(&json).unwrap();
+            assert_eq!(sut, deserialized);
+        }
+    }
+
+    #[test]
+    fn encryption_roundtrip_padded() {
+        for sut in [Sut::version1(), Sut::version2(), Sut::version3()] {
+            let sut = sut.with_padding(PaddingPolicy::sample());
+            let encrypted = sut.encrypt("open zesame", EncryptionKey::sample());
+            let unpadded = sut
+                .clone()
+                .with_padding(PaddingPolicy::None)
+                .encrypt("open zesame", EncryptionKey::sample());
+            assert_eq!(encrypted.len(), unpadded.len() - 11 + 256);
+            let decrypted =
+                sut.decrypt(encrypted, EncryptionKey::sample()).unwrap();
+            assert_eq!(decrypted, b"open zesame");
+        }
+    }
+
+    #[test]
+    fn decrypt_unpadded_with_padded_scheme_is_err() {
+        let encrypted = Sut::default().encrypt("abba", EncryptionKey::sample());
+        assert_eq!(
+            Sut::default()
+                .with_padding(PaddingPolicy::sample())
+                .decrypt(encrypted, EncryptionKey::sample()),
+            Err(Error::InvalidPadding)
+        );
+    }
 }
@@ -15,4 +15,10 @@ use serde_repr::{Deserialize_repr, Serialize_repr};
 pub enum EncryptionSchemeVersion {
     /// AES GCM 256 encryption
     Version1 = 1,
+
+    /// XChaCha20-Poly1305 encryption
+    Version2 = 2,
+
+    /// AES GCM SIV 256 encryption
+    Version3 = 3,
 }
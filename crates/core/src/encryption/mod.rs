@@ -1,15 +1,23 @@
 mod aes_gcm_256;
 mod aes_gcm_sealed_box;
+mod aes_gcm_siv_256;
 mod encryption_key;
 mod encryption_scheme;
 mod encryption_scheme_version;
+mod padding_policy;
 mod version_of_algorithm;
 mod versioned_encryption;
+mod xchacha20_poly1305;
+mod xchacha20_poly1305_sealed_box;
 
 pub use aes_gcm_256::*;
 pub use aes_gcm_sealed_box::*;
+pub use aes_gcm_siv_256::*;
 pub use encryption_key::*;
 pub use encryption_scheme::*;
 pub use encryption_scheme_version::*;
+pub use padding_policy::*;
 pub use version_of_algorithm::*;
 pub use versioned_encryption::*;
+pub use xchacha20_poly1305::*;
+pub use xchacha20_poly1305_sealed_box::*;
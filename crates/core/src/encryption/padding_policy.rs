@@ -0,0 +1,286 @@
+use crate::prelude::*;
+
+use std::num::NonZeroU32;
+
+/// How a plaintext is padded before it is encrypted, hiding its exact length
+/// from the length of the cipher text.
+///
+/// E.g. the length of an encrypted BIP39 mnemonic reveals whether it is 12
+/// or 24 words, unless it is padded to a length longer than the longest
+/// mnemonic.
+///
+/// Padding is applied inside the authenticated encryption using the
+/// ISO/IEC 7816-4 scheme: the plaintext is followed by a single `0x80` byte
+/// and as many `0x00` bytes as needed, which are removed when decrypting.
+///
+/// # Examples
+///
+/// ```
+/// use std::num::NonZeroU32;
+/// use svar_core::*;
+///
+/// let scheme =
+///     EncryptionScheme::default().with_padding(PaddingPolicy::Bucket {
+///         bucket_size: NonZeroU32::new(64).unwrap(),
+///     });
+///
+/// let short = scheme.encrypt(b"short", EncryptionKey::sample());
+/// let long = scheme.encrypt(b"a little bit longer", EncryptionKey::sample());
+/// assert_eq!(short.len(), long.len());
+///
+/// let decrypted = scheme.decrypt(short, EncryptionKey::sample())?;
+/// assert_eq!(decrypted, b"short");
+/// # Ok::<(), svar_core::Error>(())
+/// ```
+#[derive(
+    Serialize,
+    Deserialize,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    Debug,
+    Display,
+)]
+pub enum PaddingPolicy {
+    /// No padding, the cipher text reveals the exact length of the plaintext.
+    #[default]
+    #[display("None")]
+    None,
+
+    /// Pads to the next multiple of `bucket_size` bytes, revealing only in
+    /// which bucket the length of the plaintext is.
+    #[display("Bucket({bucket_size})")]
+    Bucket {
+        /// The padded length is a multiple of this many bytes.
+        bucket_size: NonZeroU32,
+    },
+
+    /// Pads to exactly `len` bytes, hiding the length of the plaintext
+    /// entirely. Plaintexts of `len` bytes or longer cannot be sealed, since
+    /// padding adds at least one byte.
+    #[display("Fixed({len})")]
+    Fixed {
+        /// The length of every padded plaintext.
+        len: NonZeroU32,
+    },
+}
+
+impl PaddingPolicy {
+    /// The first byte of the padding, followed by zero bytes.
+    const PADDING_START: u8 = 0x80;
+
+    /// Returns `true` if this policy does not pad.
+    pub fn is_none(&self) -> bool {
+        matches!(self, Self::None)
+    }
+
+    /// The byte count of a plaintext of `len` bytes once padded.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`Error::SecretTooLongForPadding`] if the policy is
+    /// [`Fixed`](Self::Fixed) and the plaintext does not fit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroU32;
+    /// use svar_core::*;
+    ///
+    /// let bucket = PaddingPolicy::Bucket {
+    ///     bucket_size: NonZeroU32::new(16).unwrap(),
+    /// };
+    /// assert_eq!(bucket.padded_len(0), Ok(16));
+    /// assert_eq!(bucket.padded_len(15), Ok(16));
+    /// assert_eq!(bucket.padded_len(16), Ok(32));
+    ///
+    /// let fixed = PaddingPolicy::Fixed {
+    ///     len: NonZeroU32::new(16).unwrap(),
+    /// };
+    /// assert_eq!(fixed.padded_len(15), Ok(16));
+    /// assert!(fixed.padded_len(16).is_err());
+    ///
+    /// assert_eq!(PaddingPolicy::None.padded_len(15), Ok(15));
+    /// ```
+    pub fn padded_len(&self, len: usize) -> Result<usize> {
+        match self {
+            Self::None => Ok(len),
+            Self::Bucket { bucket_size } => {
+                let bucket_size = bucket_size.get() as usize;
+                Ok((len / bucket_size + 1) * bucket_size)
+            }
+            Self::Fixed { len: fixed_len } => {
+                let fixed_len = fixed_len.get() as usize;
+                if len < fixed_len {
+                    Ok(fixed_len)
+                } else {
+                    Err(Error::SecretTooLongForPadding {
+                        max: fixed_len - 1,
+                        found: len,
+                    })
+                }
+            }
+        }
+    }
+
+    /// Pads `plaintext` according to this policy.
+    ///
+    /// A plaintext too long for a [`Fixed`](Self::Fixed) policy is padded
+    /// with a single byte, callers which must not reveal its length should
+    /// check [`padded_len`](Self::padded_len) first.
+    pub(crate) fn pad(&self, plaintext: &[u8]) -> Vec<u8> {
+        if self.is_none() {
+            return plaintext.to_vec();
+        }
+        let padded_len = self
+            .padded_len(plaintext.len())
+            .unwrap_or(plaintext.len() + 1);
+        let mut padded = Vec::with_capacity(padded_len);
+        padded.extend_from_slice(plaintext);
+        padded.push(Self::PADDING_START);
+        padded.resize(padded_len, 0);
+        padded
+    }
+
+    /// Removes the padding added by [`pad`](Self::pad).
+    pub(crate) fn unpad(&self, mut padded: Vec<u8>) -> Result<Vec<u8>> {
+        if self.is_none() {
+            return Ok(padded);
+        }
+        let Some(start) = padded.iter().rposition(|byte| *byte != 0) else {
+            return Err(Error::InvalidPadding);
+        };
+        if padded[start] != Self::PADDING_START {
+            padded.zeroize();
+            return Err(Error::InvalidPadding);
+        }
+        padded.truncate(start);
+        Ok(padded)
+    }
+}
+
+impl HasSampleValues for PaddingPolicy {
+    fn sample() -> Self {
+        Self::Bucket {
+            bucket_size: NonZeroU32::new(256).unwrap(),
+        }
+    }
+
+    fn sample_other() -> Self {
+        Self::Fixed {
+            len: NonZeroU32::new(64).unwrap(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::assert_json_snapshot;
+
+    use super::*;
+
+    type Sut = PaddingPolicy;
+
+    fn bucket(bucket_size: u32) -> Sut {
+        Sut::Bucket {
+            bucket_size: NonZeroU32::new(bucket_size).unwrap(),
+        }
+    }
+
+    fn fixed(len: u32) -> Sut {
+        Sut::Fixed {
+            len: NonZeroU32::new(len).unwrap(),
+        }
+    }
+
+    #[test]
+    fn equality() {
+        assert_eq!(Sut::sample(), Sut::sample());
+        assert_eq!(Sut::sample_other(), Sut::sample_other());
+        assert_ne!(Sut::sample(), Sut::sample_other());
+    }
+
+    #[test]
+    fn default_is_none() {
+        assert_eq!(Sut::default(), Sut::None);
+        assert!(Sut::default().is_none());
+    }
+
+    #[test]
+    fn pad_unpad_roundtrip() {
+        for sut in [Sut::None, bucket(1), bucket(16), fixed(64)] {
+            for len in 0..64 {
+                let plaintext = vec![0u8; len];
+                let padded = sut.pad(&plaintext);
+                assert_eq!(padded.len(), sut.padded_len(len).unwrap());
+                assert_eq!(sut.unpad(padded).unwrap(), plaintext);
+            }
+        }
+    }
+
+    #[test]
+    fn bucket_hides_length_within_bucket() {
+        let sut = bucket(16);
+        assert_eq!(sut.pad(b"").len(), 16);
+        assert_eq!(sut.pad(&[0xab; 15]).len(), 16);
+        assert_eq!(sut.pad(&[0xab; 16]).len(), 32);
+    }
+
+    #[test]
+    fn fixed_too_long_is_err() {
+        assert_eq!(
+            fixed(16).padded_len(16),
+            Err(Error::SecretTooLongForPadding { max: 15, found: 16 })
+        );
+    }
+
+    #[test]
+    fn fixed_too_long_is_padded_with_one_byte() {
+        let padded = fixed(4).pad(b"abcdef");
+        assert_eq!(padded, b"abcdef\x80");
+        assert_eq!(fixed(4).unpad(padded).unwrap(), b"abcdef");
+    }
+
+    #[test]
+    fn unpad_without_padding_start_is_err() {
+        assert_eq!(
+            bucket(4).unpad(vec![1, 2, 0, 0]),
+            Err(Error::InvalidPadding)
+        );
+        assert_eq!(
+            bucket(4).unpad(vec![0, 0, 0, 0]),
+            Err(Error::InvalidPadding)
+        );
+        assert_eq!(bucket(4).unpad(Vec::new()), Err(Error::InvalidPadding));
+    }
+
+    #[test]
+    fn none_does_not_pad() {
+        assert_eq!(Sut::None.pad(b"abba"), b"abba");
+        assert_eq!(Sut::None.unpad(b"abba".to_vec()).unwrap(), b"abba");
+    }
+
+    #[test]
+    fn json_snapshot() {
+        assert_json_snapshot!([Sut::None, Sut::sample(), Sut::sample_other()]);
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        for sut in [Sut::None, Sut::sample(), Sut::sample_other()] {
+            let json = serde_json::to_string(&sut).unwrap();
+            assert_eq!(serde_json::from_str::<Sut>(&json).unwrap(), sut);
+        }
+    }
+
+    #[test]
+    fn deserialize_zero_bucket_size_is_err() {
+        assert!(
+            serde_json::from_str::<Sut>(r#"{"Bucket":{"bucket_size":0}}"#)
+                .is_err()
+        );
+    }
+}
@@ -2,15 +2,39 @@ use crate::prelude::*;
 
 /// Versioning of encryption algorithms.
 pub trait VersionedEncryption: VersionOfAlgorithm {
-    fn encrypt(
+    /// Encrypts `plaintext` authenticating `associated_data`, which is not
+    /// part of the cipher text and MUST be passed again to decrypt.
+    fn encrypt_with_associated_data(
         &self,
         plaintext: impl AsRef<[u8]>,
         encryption_key: EncryptionKey,
+        associated_data: impl AsRef<[u8]>,
     ) -> Vec<u8>;
 
-    fn decrypt(
+    /// Decrypts `cipher_text`, failing if `associated_data` differs from the
+    /// one passed to encrypt.
+    fn decrypt_with_associated_data(
         &self,
         cipher_text: impl AsRef<[u8]>,
         decryption_key: EncryptionKey,
+        associated_data: impl AsRef<[u8]>,
     ) -> Result<Vec<u8>>;
+
+    /// Encrypts `plaintext` without any associated data.
+    fn encrypt(
+        &self,
+        plaintext: impl AsRef<[u8]>,
+        encryption_key: EncryptionKey,
+    ) -> Vec<u8> {
+        self.encrypt_with_associated_data(plaintext, encryption_key, [])
+    }
+
+    /// Decrypts `cipher_text` encrypted without any associated data.
+    fn decrypt(
+        &self,
+        cipher_text: impl AsRef<[u8]>,
+        decryption_key: EncryptionKey,
+    ) -> Result<Vec<u8>> {
+        self.decrypt_with_associated_data(cipher_text, decryption_key, [])
+    }
 }
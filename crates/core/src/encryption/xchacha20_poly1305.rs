@@ -0,0 +1,164 @@
+use crate::prelude::*;
+
+use chacha20poly1305::{
+    Key, XNonce,
+    aead::{Aead, AeadCore, KeyInit, OsRng, Payload},
+};
+
+/// XChaCha20-Poly1305 encryption, fast in software on targets without AES
+/// hardware acceleration. The 192-bit nonce is large enough to be generated
+/// at random without risk of reuse.
+#[derive(
+    Clone,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    derive_more::Display,
+    derive_more::Debug,
+)]
+pub struct XChaCha20Poly1305;
+
+impl XChaCha20Poly1305 {
+    /// The description of the XChaCha20-Poly1305 encryption scheme.
+    pub const DESCRIPTION: &'static str = "XChaCha20-Poly1305";
+}
+
+impl XChaCha20Poly1305 {
+    /// Encrypts the provided plaintext using the given encryption key,
+    /// authenticating `associated_data`.
+    fn seal(
+        plaintext: impl AsRef<[u8]>,
+        encryption_key: EncryptionKey,
+        associated_data: impl AsRef<[u8]>,
+    ) -> XChaCha20Poly1305SealedBox {
+        let encryption_key = Key::from(*encryption_key.0.bytes());
+        let cipher = chacha20poly1305::XChaCha20Poly1305::new(&encryption_key);
+
+        let nonce =
+            chacha20poly1305::XChaCha20Poly1305::generate_nonce(&mut OsRng);
+
+        let cipher_text = cipher
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: plaintext.as_ref(),
+                    aad: associated_data.as_ref(),
+                },
+            )
+            .expect("XChaCha20-Poly1305 encrypt never fails for valid nonce.");
+
+        let nonce = Exactly24Bytes::try_from(nonce.as_slice()).expect(
+            "XChaCha20-Poly1305 should always use fixed nonce byte count",
+        );
+
+        XChaCha20Poly1305SealedBox { nonce, cipher_text }
+    }
+
+    /// Decrypts the provided sealed box using the given decryption key,
+    /// failing if `associated_data` is not the one it was sealed with.
+    fn open(
+        sealed_box: XChaCha20Poly1305SealedBox,
+        decryption_key: EncryptionKey,
+        associated_data: impl AsRef<[u8]>,
+    ) -> Result<Vec<u8>> {
+        let decryption_key = Key::from(*decryption_key.0.bytes());
+        let cipher = chacha20poly1305::XChaCha20Poly1305::new(&decryption_key);
+        let cipher_text = sealed_box.cipher_text;
+        cipher
+            .decrypt(
+                XNonce::from_slice(sealed_box.nonce.as_ref()),
+                Payload {
+                    msg: cipher_text.as_ref(),
+                    aad: associated_data.as_ref(),
+                },
+            )
+            .map_err(|e| Error::XChaCha20Poly1305DecryptionFailed {
+                underlying: e.to_string(),
+            })
+    }
+}
+
+impl VersionOfAlgorithm for XChaCha20Poly1305 {
+    type Version = EncryptionSchemeVersion;
+
+    fn version(&self) -> Self::Version {
+        Self::Version::Version2
+    }
+
+    fn description(&self) -> String {
+        Self::DESCRIPTION.to_owned()
+    }
+}
+
+impl VersionedEncryption for XChaCha20Poly1305 {
+    /// Zeroizes `encryption_key` after usage.
+    fn encrypt_with_associated_data(
+        &self,
+        plaintext: impl AsRef<[u8]>,
+        encryption_key: EncryptionKey,
+        associated_data: impl AsRef<[u8]>,
+    ) -> Vec<u8> {
+        let sealed_box = Self::seal(plaintext, encryption_key, associated_data);
+        sealed_box.combined()
+    }
+
+    /// Zeroizes `decryption_key` after usage.
+    fn decrypt_with_associated_data(
+        &self,
+        cipher_text: impl AsRef<[u8]>,
+        decryption_key: EncryptionKey,
+        associated_data: impl AsRef<[u8]>,
+    ) -> Result<Vec<u8>> {
+        let sealed_box =
+            XChaCha20Poly1305SealedBox::try_from(cipher_text.as_ref())?;
+        Self::open(sealed_box, decryption_key, associated_data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    type Sut = XChaCha20Poly1305;
+
+    #[test]
+    fn test_fail() {
+        assert_eq!(
+            Sut::open(
+                XChaCha20Poly1305SealedBox {
+                    nonce: Exactly24Bytes::sample(),
+                    cipher_text: hex_decode("deadbeef").unwrap(),
+                },
+                EncryptionKey::from(Exactly32Bytes::sample_aced()),
+                []
+            ),
+            Err(Error::XChaCha20Poly1305DecryptionFailed {
+                underlying: "aead::Error".to_owned()
+            })
+        )
+    }
+
+    #[test]
+    fn encryption_roundtrip() {
+        let sut = Sut::default();
+        let encrypted = sut.encrypt(b"open zesame", EncryptionKey::sample());
+        assert_eq!(
+            encrypted.len(),
+            XCHACHA20_POLY1305_NONCE_LEN + POLY1305_TAG_LEN + 11
+        );
+        let decrypted =
+            sut.decrypt(encrypted, EncryptionKey::sample()).unwrap();
+        assert_eq!(decrypted, b"open zesame");
+    }
+
+    #[test]
+    fn nonces_are_random() {
+        let sut = Sut::default();
+        assert_ne!(
+            sut.encrypt(b"abba", EncryptionKey::sample()),
+            sut.encrypt(b"abba", EncryptionKey::sample())
+        );
+    }
+}
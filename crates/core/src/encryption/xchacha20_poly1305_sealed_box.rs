@@ -0,0 +1,55 @@
+use crate::prelude::*;
+
+/// The cipher of an XChaCha20-Poly1305 encryption alongside the nonce used.
+/// The cipher contains the encrypted payload and the authentication tag.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+pub struct XChaCha20Poly1305SealedBox {
+    /// Nonce is 24 bytes
+    pub(super) nonce: Exactly24Bytes,
+
+    /// Auth tag and encrypted payload
+    pub(super) cipher_text: Vec<u8>,
+}
+
+/// The length of the nonce used in XChaCha20-Poly1305.
+pub const XCHACHA20_POLY1305_NONCE_LEN: usize = 24;
+
+/// The length of the Poly1305 authentication tag.
+pub const POLY1305_TAG_LEN: usize = 16;
+
+impl XChaCha20Poly1305SealedBox {
+    /// At least 1 byte cipher. VERY much LOWER bound
+    pub const LOWER_BOUND_LEN: usize =
+        POLY1305_TAG_LEN + XCHACHA20_POLY1305_NONCE_LEN + 1;
+
+    pub(super) fn combined(self) -> Vec<u8> {
+        let mut combined = Vec::<u8>::new();
+        let mut nonce = self.nonce.to_vec();
+        let mut cipher_text = self.cipher_text;
+        combined.append(&mut nonce);
+        combined.append(&mut cipher_text);
+        assert!(combined.len() >= Self::LOWER_BOUND_LEN);
+        combined
+    }
+}
+
+impl TryFrom<&[u8]> for XChaCha20Poly1305SealedBox {
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        if bytes.len() < Self::LOWER_BOUND_LEN {
+            return Err(Error::InvalidXChaCha20Poly1305BytesTooShort {
+                expected_at_least: Self::LOWER_BOUND_LEN,
+                found: bytes.len(),
+            });
+        }
+
+        let nonce_bytes = &bytes[..XCHACHA20_POLY1305_NONCE_LEN];
+        let nonce = Exactly24Bytes::try_from(nonce_bytes).unwrap();
+        let cipher_text = &bytes[XCHACHA20_POLY1305_NONCE_LEN..];
+        Ok(Self {
+            nonce,
+            cipher_text: cipher_text.to_owned(),
+        })
+    }
+}
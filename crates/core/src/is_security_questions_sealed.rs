@@ -0,0 +1,718 @@
+use crate::prelude::*;
+
+/// The parts of a newly sealed secret, shared by [`SecurityQuestionsSealed`]
+/// and [`SecurityQuestionsSealedDyn`].
+pub(crate) struct SealedParts {
+    pub(crate) version: SealedSecretVersion,
+    pub(crate) security_questions_and_salts: Vec<SecurityQuestionAndSalt>,
+    pub(crate) encryptions: IndexSet<HexBytes>,
+    pub(crate) combinations: Vec<Vec<u16>>,
+    pub(crate) shares: Vec<Exactly32Bytes>,
+    pub(crate) wrapped_keys: Vec<HexBytes>,
+}
+
+impl SealedParts {
+    /// Seals `secret` such that any `min_correct_answers` of the
+    /// `questions_answers_and_salts` can open it, according to
+    /// `sealing_mode`.
+    pub(crate) fn seal<Secret: IsSecret>(
+        secret: Secret,
+        questions_answers_and_salts: &[SecurityQuestionAnswerAndSalt],
+        min_correct_answers: usize,
+        kdf_scheme: &SecurityQuestionsKdfScheme,
+        encryption_scheme: &EncryptionScheme,
+        sealing_mode: SealingMode,
+    ) -> Result<Self> {
+        let question_count = questions_answers_and_salts.len();
+        if min_correct_answers == 0 || min_correct_answers > question_count {
+            return Err(Error::InvalidMinCorrectAnswers {
+                min_correct_answers,
+                question_count,
+            });
+        }
+
+        kdf_scheme.validate_questions(
+            questions_answers_and_salts.iter().map(|qas| &qas.question),
+        )?;
+
+        // Each encryption is recorded with the ids of the questions of its
+        // combination, so the ids must be unique.
+        let mut question_ids = IndexSet::<u16>::new();
+        if let Some(duplicate) = questions_answers_and_salts
+            .iter()
+            .find(|qa| !question_ids.insert(qa.question.id))
+        {
+            return Err(Error::DuplicateAnswersToQuestion {
+                question_id: duplicate.question.id,
+            });
+        }
+
+        // Clone the security questions from the answers and salts, we need to
+        // store them in the sealed secret
+        let security_questions_and_salts = questions_answers_and_salts
+            .iter()
+            .map(|qa| qa.question_and_salt())
+            .collect_vec();
+
+        let secret_bytes = secret.to_bytes().map_err(|e| {
+            Error::FailedToConvertSecretToBytes {
+                underlying: e.to_string(),
+            }
+        })?;
+
+        // Fail rather than reveal the length of a secret which does not fit
+        // the padding
+        encryption_scheme.padding().padded_len(secret_bytes.len())?;
+
+        // Each encryption authenticates the header, so that tampering with
+        // it can be detected when opening
+        let header = SealedSecretHeader {
+            security_questions_and_salts: &security_questions_and_salts,
+            min_correct_answers,
+            kdf_scheme,
+            encryption_scheme,
+            sealing_mode,
+        };
+
+        let parts = match sealing_mode {
+            SealingMode::Combinations => Self::seal_combinations(
+                secret_bytes,
+                questions_answers_and_salts,
+                &header,
+            ),
+            SealingMode::Shamir => Self::seal_shamir(
+                secret_bytes,
+                questions_answers_and_salts,
+                &header,
+            ),
+            SealingMode::Envelope => Self::seal_envelope(
+                secret_bytes,
+                questions_answers_and_salts,
+                &header,
+            ),
+        }?;
+
+        Ok(Self {
+            security_questions_and_salts,
+            ..parts
+        })
+    }
+
+    /// Encrypts `secret_bytes` with the key of every combination of
+    /// `min_correct_answers` of the `questions_answers_and_salts`, leaving
+    /// `security_questions_and_salts` empty.
+    fn seal_combinations(
+        secret_bytes: SecretBytes,
+        questions_answers_and_salts: &[SecurityQuestionAnswerAndSalt],
+        header: &SealedSecretHeader<'_>,
+    ) -> Result<Self> {
+        // Derive the encryption keys from the questions, answers and salts
+        let encryption_keys = header
+            .kdf_scheme
+            .derive_encryption_keys_by_combination_from_subset_of_questions_answers_and_salts(
+                questions_answers_and_salts,
+                header.min_correct_answers,
+            )?;
+
+        // Encrypt the secret with each of the derived encryption keys,
+        // recording the combination of each encryption
+        let (combinations, encryptions) = encryption_keys
+            .into_iter()
+            .map(|(combination, encryption_key)| {
+                let encrypted = header.encrypt(&secret_bytes, encryption_key);
+                (combination, HexBytes::from(encrypted))
+            })
+            .unzip();
+
+        Ok(Self {
+            version: SealedSecretVersion::Version2,
+            security_questions_and_salts: Vec::new(),
+            encryptions,
+            combinations,
+            shares: Vec::new(),
+            wrapped_keys: Vec::new(),
+        })
+    }
+
+    /// Encrypts `secret_bytes` once with a random data key, split into one
+    /// share per question, each masked with the entropy of its answer,
+    /// leaving `security_questions_and_salts` empty.
+    fn seal_shamir(
+        secret_bytes: SecretBytes,
+        questions_answers_and_salts: &[SecurityQuestionAnswerAndSalt],
+        header: &SealedSecretHeader<'_>,
+    ) -> Result<Self> {
+        let question_count = questions_answers_and_salts.len();
+        if question_count > ShamirShare::MAX_SHARE_COUNT {
+            return Err(Error::TooManyQuestionsForShamir {
+                max: ShamirShare::MAX_SHARE_COUNT,
+                found: question_count,
+            });
+        }
+
+        let data_key = EncryptionKey::generate();
+
+        // The share of the question at index `i` has x coordinate `i + 1`
+        let shares = ShamirShare::split(
+            &data_key.0,
+            question_count,
+            header.min_correct_answers,
+        )
+        .into_iter()
+        .zip(questions_answers_and_salts)
+        .map(|(share, qas)| {
+            header
+                .kdf_scheme
+                .derive_security_question_entropy(qas)
+                .map(|entropy| share.masked(&entropy))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+        let encrypted = header.encrypt(&secret_bytes, data_key);
+
+        Ok(Self {
+            version: SealedSecretVersion::Version2,
+            security_questions_and_salts: Vec::new(),
+            encryptions: IndexSet::from([HexBytes::from(encrypted)]),
+            combinations: Vec::new(),
+            shares,
+            wrapped_keys: Vec::new(),
+        })
+    }
+
+    /// Encrypts `secret_bytes` once with a random data key, which is wrapped
+    /// with the key of every combination of `min_correct_answers` of the
+    /// `questions_answers_and_salts`, leaving `security_questions_and_salts`
+    /// empty.
+    fn seal_envelope(
+        secret_bytes: SecretBytes,
+        questions_answers_and_salts: &[SecurityQuestionAnswerAndSalt],
+        header: &SealedSecretHeader<'_>,
+    ) -> Result<Self> {
+        let encryption_keys = header
+            .kdf_scheme
+            .derive_encryption_keys_by_combination_from_subset_of_questions_answers_and_salts(
+                questions_answers_and_salts,
+                header.min_correct_answers,
+            )?;
+
+        let data_key = EncryptionKey::generate();
+
+        // Wrap the data key with each of the derived encryption keys,
+        // recording the combination of each wrapped key
+        let (combinations, wrapped_keys) = encryption_keys
+            .into_iter()
+            .map(|(combination, encryption_key)| {
+                let wrapped =
+                    header.encrypt(data_key.0.bytes(), encryption_key);
+                (combination, HexBytes::from(wrapped))
+            })
+            .unzip();
+
+        let encrypted = header.encrypt(&secret_bytes, data_key);
+
+        Ok(Self {
+            version: SealedSecretVersion::Version2,
+            security_questions_and_salts: Vec::new(),
+            encryptions: IndexSet::from([HexBytes::from(encrypted)]),
+            combinations,
+            shares: Vec::new(),
+            wrapped_keys,
+        })
+    }
+}
+
+/// Opening logic shared by [`SecurityQuestionsSealed`] and
+/// [`SecurityQuestionsSealedDyn`], which only differ in whether the number of
+/// questions and the minimum number of correct answers are known at compile
+/// time or at runtime.
+pub(crate) trait IsSecurityQuestionsSealed {
+    type Secret: IsSecret;
+
+    fn version(&self) -> SealedSecretVersion;
+    fn security_questions_and_salts(&self) -> &[SecurityQuestionAndSalt];
+    fn min_correct_answers(&self) -> usize;
+    fn kdf_scheme(&self) -> &SecurityQuestionsKdfScheme;
+    fn encryption_scheme(&self) -> &EncryptionScheme;
+    fn encryptions(&self) -> &IndexSet<HexBytes>;
+    fn combinations(&self) -> &[Vec<u16>];
+    fn sealing_mode(&self) -> SealingMode;
+    fn shares(&self) -> &[Exactly32Bytes];
+    fn wrapped_keys(&self) -> &[HexBytes];
+
+    /// The header authenticated by the encryptions of
+    /// [`SealedSecretVersion::Version2`] sealed secrets.
+    fn header(&self) -> SealedSecretHeader<'_> {
+        SealedSecretHeader {
+            security_questions_and_salts: self.security_questions_and_salts(),
+            min_correct_answers: self.min_correct_answers(),
+            kdf_scheme: self.kdf_scheme(),
+            encryption_scheme: self.encryption_scheme(),
+            sealing_mode: self.sealing_mode(),
+        }
+    }
+
+    /// Decrypts `encrypted` with `decryption_key` according to the version
+    /// of the sealed secret.
+    fn decrypt_encryption(
+        &self,
+        encrypted: &HexBytes,
+        decryption_key: EncryptionKey,
+    ) -> Result<Vec<u8>> {
+        match self.version() {
+            SealedSecretVersion::Version1 => self
+                .encryption_scheme()
+                .decrypt(encrypted.as_ref(), decryption_key),
+            SealedSecretVersion::Version2 => {
+                self.header().decrypt(encrypted.as_ref(), decryption_key)
+            }
+        }
+    }
+
+    /// Checks if the provided answers to security questions are relevant by
+    /// checking if they answer the questions that were used to encrypt the
+    /// secret.
+    fn are_all_answers_relevant<'a>(
+        &self,
+        answers_to_question: impl IntoIterator<
+            Item = &'a SecurityQuestionAnswerAndSalt,
+        >,
+    ) -> Result<()> {
+        let irrelevant_question = answers_to_question.into_iter().find(|qa| {
+            !self
+                .security_questions_and_salts()
+                .iter()
+                .any(|saved| saved.question == qa.question)
+        });
+
+        if let Some(qa) = irrelevant_question {
+            return Err(Error::UnrelatedQuestionProvided {
+                question: qa.question.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Returns the id of the first question for which the salt of the
+    /// provided answer differs from the salt used to seal the secret, if any.
+    fn first_salt_mismatch<'a>(
+        &self,
+        answers_to_question: impl IntoIterator<
+            Item = &'a SecurityQuestionAnswerAndSalt,
+        >,
+    ) -> Option<u16> {
+        answers_to_question
+            .into_iter()
+            .find(|qa| {
+                self.security_questions_and_salts().iter().any(|saved| {
+                    saved.question == qa.question && saved.salt != qa.salt
+                })
+            })
+            .map(|qa| qa.question.id)
+    }
+
+    /// Validates that `answers` are answers to at least
+    /// `min_correct_answers` distinct questions used to seal the secret.
+    fn validate_subset(
+        &self,
+        answers: impl IntoIterator<Item = SecurityQuestionAnswerAndSalt>,
+    ) -> Result<Vec<SecurityQuestionAnswerAndSalt>> {
+        let answers = answers.into_iter().collect_vec();
+
+        self.are_all_answers_relevant(&answers)?;
+
+        let mut question_ids = IndexSet::<u16>::new();
+        if let Some(duplicate) = answers
+            .iter()
+            .find(|qa| !question_ids.insert(qa.question.id))
+        {
+            return Err(Error::DuplicateAnswersToQuestion {
+                question_id: duplicate.question.id,
+            });
+        }
+
+        if answers.len() < self.min_correct_answers() {
+            return Err(Error::TooFewAnswers {
+                expected_at_least: self.min_correct_answers(),
+                found: answers.len(),
+            });
+        }
+
+        Ok(answers)
+    }
+
+    /// Decrypts the secret using answers to (a subset of) the questions,
+    /// without validating that there are no duplicates or too few answers.
+    fn open_unvalidated(
+        &self,
+        answers: &[SecurityQuestionAnswerAndSalt],
+    ) -> Result<Self::Secret> {
+        self.are_all_answers_relevant(answers)?;
+        let salt_mismatch = self.first_salt_mismatch(answers);
+
+        match self.sealing_mode() {
+            SealingMode::Combinations | SealingMode::Envelope => {
+                let decryption_keys = self
+                    .kdf_scheme()
+                    .derive_encryption_keys_by_combination_from_subset_of_questions_answers_and_salts(
+                        answers,
+                        self.min_correct_answers(),
+                    )?;
+
+                self.decrypt_with_keys(decryption_keys, salt_mismatch)
+            }
+            SealingMode::Shamir => {
+                let shares = self.unmasked_shares(answers)?;
+                self.decrypt_with_keys(
+                    self.data_keys_by_combination(&shares),
+                    salt_mismatch,
+                )
+            }
+        }
+    }
+
+    /// Unmasks the share of the question of each of the `answers`, keyed by
+    /// question id, in the order of `answers`.
+    ///
+    /// The shares of incorrectly answered questions are garbage.
+    fn unmasked_shares(
+        &self,
+        answers: &[SecurityQuestionAnswerAndSalt],
+    ) -> Result<Vec<(u16, ShamirShare)>> {
+        if self.shares().len() != self.security_questions_and_salts().len() {
+            return Err(Error::InvalidSealedSecret {
+                underlying: format!(
+                    "Expected {} shares, found {}",
+                    self.security_questions_and_salts().len(),
+                    self.shares().len()
+                ),
+            });
+        }
+
+        answers
+            .iter()
+            .map(|qa| {
+                let index = self
+                    .security_questions_and_salts()
+                    .iter()
+                    .position(|saved| saved.question == qa.question)
+                    .ok_or_else(|| Error::UnrelatedQuestionProvided {
+                        question: qa.question.to_string(),
+                    })?;
+                let entropy =
+                    self.kdf_scheme().derive_security_question_entropy(qa)?;
+                let share = ShamirShare::unmasked(
+                    (index + 1) as u8,
+                    &self.shares()[index],
+                    &entropy,
+                );
+                Ok((qa.question.id, share))
+            })
+            .collect()
+    }
+
+    /// Lazily recovers the data key from every combination of
+    /// `min_correct_answers` of the `shares`, keyed by the question ids of
+    /// the combination.
+    fn data_keys_by_combination<'a>(
+        &self,
+        shares: &'a [(u16, ShamirShare)],
+    ) -> impl Iterator<Item = (Vec<u16>, EncryptionKey)> + 'a {
+        shares.iter().combinations(self.min_correct_answers()).map(
+            |combination| {
+                let question_ids =
+                    combination.iter().map(|(id, _)| *id).collect_vec();
+                let data_key = ShamirShare::combine(
+                    combination.into_iter().map(|(_, share)| share),
+                );
+                (question_ids, EncryptionKey::from(data_key))
+            },
+        )
+    }
+
+    fn open_with_subset(
+        &self,
+        answers: impl IntoIterator<Item = SecurityQuestionAnswerAndSalt>,
+    ) -> Result<Self::Secret> {
+        let answers = self.validate_subset(answers)?;
+        self.open_unvalidated(&answers)
+    }
+
+    fn open_with_answers<Answer: Into<SecurityQuestionAnswer>>(
+        &self,
+        answers: impl IntoIterator<Item = (u16, Answer)>,
+    ) -> Result<Self::Secret> {
+        let answers = answers
+            .into_iter()
+            .map(|(question_id, answer)| {
+                self.security_questions_and_salts()
+                    .iter()
+                    .find(|saved| saved.question.id == question_id)
+                    .map(|saved| SecurityQuestionAnswerAndSalt {
+                        question: saved.question.clone(),
+                        answer: answer.into(),
+                        salt: saved.salt,
+                    })
+                    .ok_or(Error::UnknownQuestionId { question_id })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        self.open_with_subset(answers)
+    }
+
+    fn open_with_report(
+        &self,
+        answers: impl IntoIterator<Item = SecurityQuestionAnswerAndSalt>,
+    ) -> Result<(Self::Secret, SecurityQuestionsAnswersReport)> {
+        let answers = self.validate_subset(answers)?;
+
+        if self.sealing_mode() == SealingMode::Shamir {
+            return self.open_shamir_with_report(&answers);
+        }
+
+        let keys_by_combination = self
+            .kdf_scheme()
+            .derive_encryption_keys_by_combination_from_subset_of_questions_answers_and_salts(
+                &answers,
+                self.min_correct_answers(),
+            )?;
+
+        let (successful, failed): (Vec<_>, Vec<_>) = keys_by_combination
+            .into_iter()
+            .partition(|(combination, key)| {
+                self.can_decrypt_with_key(combination, key)
+            });
+
+        // If no combination is successful, trying the failed ones surfaces
+        // why, e.g. tampering.
+        let secret = self.decrypt_with_keys(
+            successful.iter().chain(failed.iter()).cloned(),
+            self.first_salt_mismatch(&answers),
+        )?;
+
+        let report = SecurityQuestionsAnswersReport::new(
+            self.security_questions_and_salts()
+                .iter()
+                .map(|saved| saved.question.id),
+            successful.iter().map(|(combination, _)| combination),
+            failed.iter().map(|(combination, _)| combination),
+        );
+
+        Ok((secret, report))
+    }
+
+    /// Like [`open_with_report`](Self::open_with_report) but for secrets
+    /// sealed with [`SealingMode::Shamir`].
+    ///
+    /// Once a combination of shares recovers the data key, every other
+    /// provided share is checked against the polynomials interpolated from
+    /// that combination, a share is correct if and only if it lies on them.
+    fn open_shamir_with_report(
+        &self,
+        answers: &[SecurityQuestionAnswerAndSalt],
+    ) -> Result<(Self::Secret, SecurityQuestionsAnswersReport)> {
+        let shares = self.unmasked_shares(answers)?;
+
+        let successful = self.data_keys_by_combination(&shares).find(
+            |(combination, key)| self.can_decrypt_with_key(combination, key),
+        );
+
+        // If no combination is successful, trying all of them again surfaces
+        // why, e.g. tampering.
+        let secret = self.decrypt_with_keys(
+            successful
+                .clone()
+                .into_iter()
+                .chain(self.data_keys_by_combination(&shares)),
+            self.first_salt_mismatch(answers),
+        )?;
+
+        // Decryption succeeded, so there is a successful combination.
+        let (successful, _) =
+            successful.expect("Successful combination when decrypted");
+        let successful_shares = shares
+            .iter()
+            .filter(|(id, _)| successful.contains(id))
+            .map(|(_, share)| share)
+            .collect_vec();
+
+        let (correct, incorrect): (Vec<_>, Vec<_>) =
+            shares.iter().partition(|(_, share)| {
+                ShamirShare::interpolate(
+                    successful_shares.iter().copied(),
+                    share.x,
+                ) == share.y
+            });
+
+        // A share which does not lie on the polynomials is incorrect on its
+        // own, so each is reported as a failed combination of one.
+        let correct = vec![correct.iter().map(|(id, _)| *id).collect_vec()];
+        let incorrect = incorrect.iter().map(|(id, _)| vec![*id]).collect_vec();
+
+        let report = SecurityQuestionsAnswersReport::new(
+            self.security_questions_and_salts()
+                .iter()
+                .map(|saved| saved.question.id),
+            &correct,
+            &incorrect,
+        );
+
+        Ok((secret, report))
+    }
+
+    /// Returns `true` if `key`, derived from the answers to the questions
+    /// with ids `combination`, decrypts the encryption of the combination.
+    fn can_decrypt_with_key(
+        &self,
+        combination: &[u16],
+        key: &EncryptionKey,
+    ) -> bool {
+        self.decryptions_with_key(combination, key)
+            .into_iter()
+            .any(|decrypted| decrypted.is_ok())
+    }
+
+    /// Decrypts each encryption which might have been encrypted with `key`,
+    /// the key of the combination of questions with ids `combination`.
+    ///
+    /// With [`SealingMode::Envelope`] `key` unwraps the data key, which
+    /// decrypts the single encryption.
+    fn decryptions_with_key(
+        &self,
+        combination: &[u16],
+        key: &EncryptionKey,
+    ) -> Vec<Result<Vec<u8>>> {
+        match self.sealing_mode() {
+            SealingMode::Envelope => self
+                .of_combination(combination, self.wrapped_keys().iter())
+                .into_iter()
+                .map(|wrapped| {
+                    let data_key = self.unwrap_data_key(wrapped, key)?;
+                    let encrypted =
+                        self.encryptions().first().ok_or_else(|| {
+                            Error::InvalidSealedSecret {
+                                underlying: "Expected 1 encryption, found 0"
+                                    .to_owned(),
+                            }
+                        })?;
+                    self.decrypt_encryption(encrypted, data_key)
+                })
+                .collect(),
+            SealingMode::Combinations | SealingMode::Shamir => self
+                .of_combination(combination, self.encryptions().iter())
+                .into_iter()
+                .map(|encrypted| {
+                    self.decrypt_encryption(encrypted, key.clone())
+                })
+                .collect(),
+        }
+    }
+
+    /// Decrypts the data key `wrapped` with `key`.
+    fn unwrap_data_key(
+        &self,
+        wrapped: &HexBytes,
+        key: &EncryptionKey,
+    ) -> Result<EncryptionKey> {
+        let mut data_key = self.decrypt_encryption(wrapped, key.clone())?;
+        let unwrapped = Exactly32Bytes::try_from(data_key.as_slice())
+            .map(EncryptionKey::from)
+            .map_err(|_| Error::InvalidSealedSecret {
+                underlying: format!(
+                    "Expected wrapped key of 32 bytes, found {}",
+                    data_key.len()
+                ),
+            });
+        data_key.zeroize();
+        unwrapped
+    }
+
+    /// The entries of `recorded`, one per combination, which might have been
+    /// encrypted with the key of the combination of questions with ids
+    /// `combination`, in any order.
+    ///
+    /// That is the single matching entry if the combinations were recorded
+    /// at sealing, otherwise all entries.
+    fn of_combination<'a>(
+        &self,
+        combination: &[u16],
+        recorded: impl ExactSizeIterator<Item = &'a HexBytes>,
+    ) -> Vec<&'a HexBytes> {
+        if self.combinations().len() != recorded.len() {
+            return recorded.collect();
+        }
+        let combination = combination.iter().sorted().collect_vec();
+        self.combinations()
+            .iter()
+            .zip(recorded)
+            .filter(|(recorded, _)| {
+                recorded.iter().sorted().collect_vec() == combination
+            })
+            .map(|(_, encrypted)| encrypted)
+            .collect()
+    }
+
+    /// Tries to decrypt the encryptions of each combination with the key of
+    /// the combination, returning the first secret which is successfully
+    /// decrypted and deserialized.
+    ///
+    /// If decryption fails because a key was correct but the sealed secret
+    /// has been tampered with, [`Error::SealedSecretTampered`] is returned.
+    /// Otherwise if `salt_mismatch` is the id of a question whose answer was
+    /// provided with a different salt than the one used to seal the secret,
+    /// [`Error::SaltMismatch`] is returned, since that is most likely the
+    /// cause of the failure.
+    fn decrypt_with_keys(
+        &self,
+        decryption_keys: impl IntoIterator<Item = (Vec<u16>, EncryptionKey)>,
+        salt_mismatch: Option<u16>,
+    ) -> Result<Self::Secret> {
+        let mut successful_decryption_failure_deserializing: Option<Error> =
+            None;
+        let mut tampered = false;
+
+        for (combination, decryption_key) in decryption_keys.into_iter() {
+            for decrypted in
+                self.decryptions_with_key(&combination, &decryption_key)
+            {
+                match decrypted {
+                    Ok(decrypted) => {
+                        match Self::Secret::from_bytes(SecretBytes::from(
+                            decrypted,
+                        )) {
+                            Ok(secret) => return Ok(secret),
+                            Err(deserialize_fail) => {
+                                successful_decryption_failure_deserializing =
+                                    Some(Error::FailedToConvertBytesToSecret {
+                                        underlying: deserialize_fail
+                                            .to_string(),
+                                    });
+                            }
+                        }
+                    }
+                    Err(Error::SealedSecretTampered) => tampered = true,
+                    // Else continue to the next encrypted/key combination
+                    Err(_) => {}
+                }
+            }
+        }
+
+        // Failure
+        if let Some(deserialize_err) =
+            successful_decryption_failure_deserializing
+        {
+            // We actual did successful **decrypt** at least one combination,
+            // but we failed to deserialize the bytes into the Secret type,
+            // so instead of throwing a generic `FailedToDecryptSealedSecret`
+            // error we throw the deserialization one.
+            Err(deserialize_err)
+        } else if tampered {
+            Err(Error::SealedSecretTampered)
+        } else if let Some(question_id) = salt_mismatch {
+            Err(Error::SaltMismatch { question_id })
+        } else {
+            Err(Error::FailedToDecryptSealedSecret)
+        }
+    }
+}
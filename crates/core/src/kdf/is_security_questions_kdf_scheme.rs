@@ -10,4 +10,65 @@ pub trait IsSecurityQuestionsKdfScheme {
             QUESTION_COUNT,
         >,
     ) -> Result<EncryptionKeys<QUESTION_COUNT, MIN_CORRECT_ANSWERS>>;
+
+    /// Derives the entropy of a single answer, bound to the id of its
+    /// question.
+    fn derive_security_question_entropy(
+        &self,
+        question_answer_and_salt: &SecurityQuestionAnswerAndSalt,
+    ) -> Result<SecurityQuestionEntropy>;
+
+    /// Derives the encryption key of a single combination of entropies, the
+    /// order of `combination` does not matter.
+    fn derive_encryption_key_from_combination(
+        &self,
+        combination: Vec<&SecurityQuestionEntropy>,
+    ) -> EncryptionKey;
+
+    /// Derives the encryption keys of every combination of
+    /// `min_correct_answers` answers fully covered by
+    /// `questions_answers_and_salts`, which can be any subset of the
+    /// questions used to seal a secret.
+    ///
+    /// If all questions are answered, the keys are the same as - and in the
+    /// same order as - those derived by
+    /// [`derive_encryption_keys_from_questions_answers_and_salts`](Self::derive_encryption_keys_from_questions_answers_and_salts).
+    fn derive_encryption_keys_from_subset_of_questions_answers_and_salts(
+        &self,
+        questions_answers_and_salts: &[SecurityQuestionAnswerAndSalt],
+        min_correct_answers: usize,
+    ) -> Result<IndexSet<EncryptionKey>> {
+        self.derive_encryption_keys_by_combination_from_subset_of_questions_answers_and_salts(
+            questions_answers_and_salts,
+            min_correct_answers,
+        )
+        .map(|keys| keys.into_values().collect())
+    }
+
+    /// Like
+    /// [`derive_encryption_keys_from_subset_of_questions_answers_and_salts`](Self::derive_encryption_keys_from_subset_of_questions_answers_and_salts)
+    /// but keyed by the question ids of the combination each key was derived
+    /// from, in the order of `questions_answers_and_salts`.
+    fn derive_encryption_keys_by_combination_from_subset_of_questions_answers_and_salts(
+        &self,
+        questions_answers_and_salts: &[SecurityQuestionAnswerAndSalt],
+        min_correct_answers: usize,
+    ) -> Result<IndexMap<Vec<u16>, EncryptionKey>> {
+        let entropies = questions_answers_and_salts
+            .iter()
+            .map(|qas| self.derive_security_question_entropy(qas))
+            .collect::<Result<Vec<SecurityQuestionEntropy>>>()?;
+
+        Ok(entropies
+            .iter()
+            .combinations(min_correct_answers)
+            .map(|combination| {
+                let question_ids =
+                    combination.iter().map(|e| e.question_id).collect_vec();
+                let key =
+                    self.derive_encryption_key_from_combination(combination);
+                (question_ids, key)
+            })
+            .collect())
+    }
 }
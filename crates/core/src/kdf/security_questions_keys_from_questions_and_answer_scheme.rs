@@ -6,11 +6,51 @@ use crate::prelude::*;
 pub enum SecurityQuestionsKdfScheme {
     /// First iteration of KDF for SecurityQuestions
     Version1(SecurityQuestionsKDFSchemeVersion1),
+
+    /// Second iteration of KDF for SecurityQuestions, which stretches each
+    /// answer using Argon2id before expanding it into an entropy.
+    Version2(SecurityQuestionsKDFSchemeVersion2),
+}
+
+impl SecurityQuestionsKdfScheme {
+    /// The first iteration of the KDF, not using any memory-hard stretching
+    /// of the answers, kept so that old sealed secrets can still be opened.
+    pub fn version1() -> Self {
+        Self::Version1(SecurityQuestionsKDFSchemeVersion1::default())
+    }
+
+    /// The second iteration of the KDF, stretching answers using Argon2id.
+    pub fn version2() -> Self {
+        Self::Version2(SecurityQuestionsKDFSchemeVersion2::default())
+    }
+
+    /// Fails with [`Error::AnswerProcessingNotSupportedByKdfScheme`] if any
+    /// of `questions` has a [`Transliteration`] or [`PhoneticEncoding`],
+    /// which [`Version1`](Self::Version1) does not apply.
+    pub(crate) fn validate_questions<'a>(
+        &self,
+        questions: impl IntoIterator<Item = &'a SecurityQuestion>,
+    ) -> Result<()> {
+        let Self::Version1(_) = self else {
+            return Ok(());
+        };
+        match questions.into_iter().find(|question| {
+            question.transliteration.is_some()
+                || question.expected_answer_format.phonetic_encoding.is_some()
+        }) {
+            Some(question) => {
+                Err(Error::AnswerProcessingNotSupportedByKdfScheme {
+                    question_id: question.id,
+                })
+            }
+            None => Ok(()),
+        }
+    }
 }
 
 impl Default for SecurityQuestionsKdfScheme {
     fn default() -> Self {
-        Self::Version1(SecurityQuestionsKDFSchemeVersion1::default())
+        Self::version2()
     }
 }
 
@@ -28,6 +68,37 @@ impl IsSecurityQuestionsKdfScheme for SecurityQuestionsKdfScheme {
             Self::Version1(kdf) => kdf.derive_encryption_keys_from_questions_answers_and_salts::<QUESTION_COUNT, MIN_CORRECT_ANSWERS>(
                 questions_answers_and_salts,
             ),
+            Self::Version2(kdf) => kdf.derive_encryption_keys_from_questions_answers_and_salts::<QUESTION_COUNT, MIN_CORRECT_ANSWERS>(
+                questions_answers_and_salts,
+            ),
+        }
+    }
+
+    fn derive_security_question_entropy(
+        &self,
+        question_answer_and_salt: &SecurityQuestionAnswerAndSalt,
+    ) -> Result<SecurityQuestionEntropy> {
+        match self {
+            Self::Version1(kdf) => {
+                kdf.derive_security_question_entropy(question_answer_and_salt)
+            }
+            Self::Version2(kdf) => {
+                kdf.derive_security_question_entropy(question_answer_and_salt)
+            }
+        }
+    }
+
+    fn derive_encryption_key_from_combination(
+        &self,
+        combination: Vec<&SecurityQuestionEntropy>,
+    ) -> EncryptionKey {
+        match self {
+            Self::Version1(kdf) => {
+                kdf.derive_encryption_key_from_combination(combination)
+            }
+            Self::Version2(kdf) => {
+                kdf.derive_encryption_key_from_combination(combination)
+            }
         }
     }
 }
@@ -75,11 +146,153 @@ impl IsSecurityQuestionsKdfScheme for SecurityQuestionsKDFSchemeVersion1 {
             })
             .collect::<Result<Vec<Exactly32Bytes>>>()?;
 
-        let entropies: [Exactly32Bytes; QUESTION_COUNT] = entropies
+        let mut entropies: [Exactly32Bytes; QUESTION_COUNT] = entropies
             .try_into()
             .expect("It is not possible to have a different number of entropies than QUESTION_COUNT");
 
-        encryption_keys_kdf.derive_encryption_keys_from(entropies)
+        let keys = encryption_keys_kdf.derive_encryption_keys_from(entropies);
+        entropies.zeroize();
+        keys
+    }
+
+    fn derive_security_question_entropy(
+        &self,
+        question_answer_and_salt: &SecurityQuestionAnswerAndSalt,
+    ) -> Result<SecurityQuestionEntropy> {
+        self.entropies_from_questions_answer_and_salt
+            .derive_entropies_from_question_answer_and_salt(
+                question_answer_and_salt,
+            )
+            .map(|entropy| {
+                SecurityQuestionEntropy::new(
+                    question_answer_and_salt.question.id,
+                    entropy,
+                )
+            })
+    }
+
+    fn derive_encryption_key_from_combination(
+        &self,
+        combination: Vec<&SecurityQuestionEntropy>,
+    ) -> EncryptionKey {
+        self.kdf_encryption_keys_from_key_exchange_keys
+            .key_from_combination(
+                combination.into_iter().map(|e| &e.entropy).collect(),
+            )
+    }
+}
+
+/// Version2 of SecurityQuestions KDF, derives encryption keys from security
+/// questions and answers, using three "sub-KDFs": the answer is first
+/// normalized (or encoded, for structured answers), then stretched using a
+/// memory-hard function, and then expanded into an entropy, which are finally
+/// combined into encryption keys by a selectable
+/// [`SecurityQuestionsEncryptionKeysKdf`].
+#[derive(
+    Serialize, Deserialize, Clone, PartialEq, Eq, Hash, Debug, Default,
+)]
+pub struct SecurityQuestionsKDFSchemeVersion2 {
+    /// Used for answers to [`SecurityQuestionKind::Freeform`] questions.
+    pub entropies_from_questions_answer_and_salt: SecurityQuestionsEntropiesKdf,
+    /// Used for answers to [`SecurityQuestionKind::Structured`] questions.
+    pub entropies_from_structured_questions_answer_and_salt:
+        SecurityQuestionsKeyExchangeKeysFromQandAsStructured,
+    pub answer_stretching: SecurityQuestionsAnswerStretchingArgon2id,
+    pub kdf_encryption_keys_from_key_exchange_keys:
+        SecurityQuestionsEncryptionKeysKdf,
+}
+
+impl SecurityQuestionsKDFSchemeVersion2 {
+    /// Normalizes the answer, stretches it using Argon2id with the salt and
+    /// finally expands it into an entropy bound to the question.
+    ///
+    /// Fails with [`Error::AnswerDoesNotMatchQuestionKind`] if a freeform
+    /// answer is given to a structured question or vice versa.
+    pub fn derive_entropy_from_question_answer_and_salt(
+        &self,
+        question_answer_and_salt: &SecurityQuestionAnswerAndSalt,
+    ) -> Result<Exactly32Bytes> {
+        let salt = &question_answer_and_salt.salt;
+        match (
+            &question_answer_and_salt.question.kind,
+            &question_answer_and_salt.answer,
+        ) {
+            (
+                SecurityQuestionKind::Freeform,
+                SecurityQuestionAnswer::Freeform(_),
+            ) => {
+                let kdf = &self.entropies_from_questions_answer_and_salt;
+                let ikm = kdf.input_key_material(question_answer_and_salt)?;
+                let mut stretched =
+                    self.answer_stretching.stretch(ikm, salt)?;
+                let entropy = kdf.expand(question_answer_and_salt, stretched);
+                stretched.zeroize();
+                Ok(entropy)
+            }
+            (
+                SecurityQuestionKind::Structured { .. },
+                SecurityQuestionAnswer::Structured(_),
+            ) => {
+                let kdf =
+                    &self.entropies_from_structured_questions_answer_and_salt;
+                let ikm = kdf.input_key_material(question_answer_and_salt)?;
+                let mut stretched =
+                    self.answer_stretching.stretch(ikm, salt)?;
+                let entropy = kdf.expand(question_answer_and_salt, stretched);
+                stretched.zeroize();
+                entropy
+            }
+            _ => Err(Error::AnswerDoesNotMatchQuestionKind {
+                question_id: question_answer_and_salt.question.id,
+            }),
+        }
+    }
+}
+
+impl IsSecurityQuestionsKdfScheme for SecurityQuestionsKDFSchemeVersion2 {
+    fn derive_encryption_keys_from_questions_answers_and_salts<
+        const QUESTION_COUNT: usize,
+        const MIN_CORRECT_ANSWERS: usize,
+    >(
+        &self,
+        questions_answers_and_salts: SecurityQuestionsAnswersAndSalts<
+            QUESTION_COUNT,
+        >,
+    ) -> Result<EncryptionKeys<QUESTION_COUNT, MIN_CORRECT_ANSWERS>> {
+        let entropies = questions_answers_and_salts
+            .iter()
+            .map(|qas| self.derive_security_question_entropy(qas))
+            .collect::<Result<Vec<SecurityQuestionEntropy>>>()?;
+
+        let entropies: [SecurityQuestionEntropy; QUESTION_COUNT] = entropies
+            .try_into()
+            .expect("It is not possible to have a different number of entropies than QUESTION_COUNT");
+
+        self.kdf_encryption_keys_from_key_exchange_keys
+            .derive_encryption_keys_from(entropies)
+    }
+
+    fn derive_security_question_entropy(
+        &self,
+        question_answer_and_salt: &SecurityQuestionAnswerAndSalt,
+    ) -> Result<SecurityQuestionEntropy> {
+        self.derive_entropy_from_question_answer_and_salt(
+            question_answer_and_salt,
+        )
+        .map(|entropy| {
+            SecurityQuestionEntropy::new(
+                question_answer_and_salt.question.id,
+                entropy,
+            )
+        })
+    }
+
+    fn derive_encryption_key_from_combination(
+        &self,
+        combination: Vec<&SecurityQuestionEntropy>,
+    ) -> EncryptionKey {
+        self.kdf_encryption_keys_from_key_exchange_keys
+            .derive_encryption_key_from_combination(combination)
     }
 }
 
@@ -90,6 +303,17 @@ mod tests {
 
     type Sut = SecurityQuestionsKdfScheme;
     type SutV1 = SecurityQuestionsKDFSchemeVersion1;
+    type SutV2 = SecurityQuestionsKDFSchemeVersion2;
+
+    /// Cheap Argon2id parameters, to keep tests fast.
+    fn cheap_v2() -> SutV2 {
+        SutV2 {
+            answer_stretching: SecurityQuestionsAnswerStretchingArgon2id::new(
+                64, 1, 1,
+            ),
+            ..SutV2::default()
+        }
+    }
 
     #[test]
     fn serialize() {
@@ -97,9 +321,42 @@ mod tests {
     }
 
     #[test]
-    fn default_creates_version1() {
+    fn default_creates_version2() {
         let sut = Sut::default();
-        assert!(matches!(sut, Sut::Version1(_)));
+        assert!(matches!(sut, Sut::Version2(_)));
+    }
+
+    #[test]
+    fn serialize_version1() {
+        assert_json_snapshot!(Sut::version1());
+    }
+
+    #[test]
+    fn validate_questions() {
+        let plain = SecurityQuestion::first_concert();
+        let transliterated = SecurityQuestion::first_concert()
+            .with_transliteration(Transliteration::ToLatinVersion1);
+        let mut phonetic = SecurityQuestion::first_concert();
+        phonetic.expected_answer_format.phonetic_encoding =
+            Some(PhoneticEncoding::MetaphoneVersion1);
+
+        assert_eq!(Sut::version1().validate_questions([&plain]), Ok(()));
+        assert_eq!(
+            Sut::version2().validate_questions([
+                &plain,
+                &transliterated,
+                &phonetic
+            ]),
+            Ok(())
+        );
+        for question in [&transliterated, &phonetic] {
+            assert_eq!(
+                Sut::version1().validate_questions([&plain, question]),
+                Err(Error::AnswerProcessingNotSupportedByKdfScheme {
+                    question_id: question.id
+                })
+            );
+        }
     }
 
     #[test]
@@ -145,7 +402,7 @@ mod tests {
 
     #[test]
     fn derive_encryption_keys_version1_delegates() {
-        let sut = Sut::default();
+        let sut = Sut::version1();
         let questions_answers_and_salts =
             SecurityQuestionsAnswersAndSalts::sample();
 
@@ -156,7 +413,9 @@ mod tests {
         assert!(result.is_ok());
 
         // Also test with version1 directly to ensure same result
-        let Sut::Version1(v1) = sut;
+        let Sut::Version1(v1) = sut else {
+            panic!("Expected Version1")
+        };
         let v1_result = v1
             .derive_encryption_keys_from_questions_answers_and_salts::<6, 4>(
                 questions_answers_and_salts,
@@ -341,4 +600,336 @@ mod tests {
         let entropies = entropies.unwrap();
         assert_eq!(entropies.len(), 6); // Sample has 6 questions
     }
+
+    #[test]
+    fn derive_encryption_keys_version2_delegates() {
+        let v2 = cheap_v2();
+        let sut = Sut::Version2(v2.clone());
+        let questions_answers_and_salts =
+            SecurityQuestionsAnswersAndSalts::sample();
+
+        let result = sut
+            .derive_encryption_keys_from_questions_answers_and_salts::<6, 4>(
+                questions_answers_and_salts.clone(),
+            )
+            .unwrap();
+        let v2_result = v2
+            .derive_encryption_keys_from_questions_answers_and_salts::<6, 4>(
+                questions_answers_and_salts,
+            )
+            .unwrap();
+        assert_eq!(result, v2_result);
+    }
+
+    #[test]
+    fn version2_derives_different_keys_than_version1() {
+        let questions_answers_and_salts =
+            SecurityQuestionsAnswersAndSalts::sample();
+        let v1 = SutV1::default()
+            .derive_encryption_keys_from_questions_answers_and_salts::<6, 4>(
+                questions_answers_and_salts.clone(),
+            )
+            .unwrap();
+        let v2 = cheap_v2()
+            .derive_encryption_keys_from_questions_answers_and_salts::<6, 4>(
+                questions_answers_and_salts,
+            )
+            .unwrap();
+        assert_ne!(v1, v2);
+    }
+
+    #[test]
+    fn version2_stretching_params_affect_keys() {
+        let questions_answers_and_salts =
+            SecurityQuestionsAnswersAndSalts::sample();
+        let cheap = cheap_v2()
+            .derive_encryption_keys_from_questions_answers_and_salts::<6, 4>(
+                questions_answers_and_salts.clone(),
+            )
+            .unwrap();
+        let other = SutV2 {
+            answer_stretching: SecurityQuestionsAnswerStretchingArgon2id::new(
+                128, 1, 1,
+            ),
+            ..SutV2::default()
+        }
+        .derive_encryption_keys_from_questions_answers_and_salts::<6, 4>(
+            questions_answers_and_salts,
+        )
+        .unwrap();
+        assert_ne!(cheap, other);
+    }
+
+    #[test]
+    fn version2_entropy_ignores_white_space() {
+        let sut = cheap_v2();
+        let first = sut
+            .derive_entropy_from_question_answer_and_salt(
+                &SecurityQuestionAnswerAndSalt {
+                    question: SecurityQuestion::first_concert(),
+                    answer: "Jean-Michel Jarre, Paris La Défense, 1990".into(),
+                    salt: Exactly32Bytes::sample_aced(),
+                },
+            )
+            .unwrap();
+        let second = sut
+            .derive_entropy_from_question_answer_and_salt(
+                &SecurityQuestionAnswerAndSalt {
+                    question: SecurityQuestion::first_concert(),
+                    answer: "Jean-MichelJarre,ParisLaDéfense,1990".into(),
+                    salt: Exactly32Bytes::sample_aced(),
+                },
+            )
+            .unwrap();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn version2_invalid_stretching_params_is_err() {
+        let sut = SutV2 {
+            answer_stretching: SecurityQuestionsAnswerStretchingArgon2id::new(
+                0, 0, 0,
+            ),
+            ..SutV2::default()
+        };
+        let result = sut
+            .derive_encryption_keys_from_questions_answers_and_salts::<6, 4>(
+                SecurityQuestionsAnswersAndSalts::sample(),
+            );
+        assert!(matches!(result, Err(Error::AnswerStretchingFailed { .. })));
+    }
+
+    #[test]
+    fn version2_default_combines_by_hkdf() {
+        assert_eq!(
+            SutV2::default().kdf_encryption_keys_from_key_exchange_keys,
+            SecurityQuestionsEncryptionKeysKdf::ByHkdfOfSortedEntropies(
+                SecurityQuestionsEncryptionKeysByHkdfOfSortedEntropies
+            )
+        );
+    }
+
+    #[test]
+    fn version2_combiner_affects_keys() {
+        let questions_answers_and_salts =
+            SecurityQuestionsAnswersAndSalts::sample();
+        let hkdf = cheap_v2()
+            .derive_encryption_keys_from_questions_answers_and_salts::<6, 4>(
+                questions_answers_and_salts.clone(),
+            )
+            .unwrap();
+        let xor = SutV2 {
+            kdf_encryption_keys_from_key_exchange_keys:
+                SecurityQuestionsEncryptionKeysKdf::ByXorEntropies(
+                    SecurityQuestionsEncryptionKeysByXorEntropies,
+                ),
+            ..cheap_v2()
+        }
+        .derive_encryption_keys_from_questions_answers_and_salts::<6, 4>(
+            questions_answers_and_salts,
+        )
+        .unwrap();
+        assert_ne!(hkdf, xor);
+    }
+
+    #[test]
+    fn version2_keys_independent_of_answer_order() {
+        let sut = cheap_v2();
+        let questions_answers_and_salts =
+            SecurityQuestionsAnswersAndSalts::sample();
+        let reversed = SecurityQuestionsAnswersAndSalts::<6>::try_from_iter(
+            questions_answers_and_salts.iter().rev().cloned(),
+        )
+        .unwrap();
+        let keys = sut
+            .derive_encryption_keys_from_questions_answers_and_salts::<6, 4>(
+                questions_answers_and_salts,
+            )
+            .unwrap()
+            .into_iter()
+            .collect::<std::collections::HashSet<_>>();
+        let keys_reversed = sut
+            .derive_encryption_keys_from_questions_answers_and_salts::<6, 4>(
+                reversed,
+            )
+            .unwrap()
+            .into_iter()
+            .collect::<std::collections::HashSet<_>>();
+        assert_eq!(keys, keys_reversed);
+    }
+
+    #[test]
+    fn version2_default_binds_question_id() {
+        assert_eq!(
+            SutV2::default().entropies_from_questions_answer_and_salt,
+            SecurityQuestionsEntropiesKdf::LowerTrimUtf8ByQuestionId(
+                SecurityQuestionsKeyExchangeKeysFromQandAsLowerTrimUtf8ByQuestionId
+            )
+        );
+    }
+
+    #[test]
+    fn version2_rewording_question_does_not_change_keys() {
+        let sut = cheap_v2();
+        let questions_answers_and_salts =
+            SecurityQuestionsAnswersAndSalts::sample();
+        let reworded = SecurityQuestionsAnswersAndSalts::<6>::try_from_iter(
+            questions_answers_and_salts.iter().cloned().map(|mut qas| {
+                qas.question.question = qas.question.question.to_uppercase();
+                qas
+            }),
+        )
+        .unwrap();
+        assert_eq!(
+            sut.derive_encryption_keys_from_questions_answers_and_salts::<6, 4>(
+                questions_answers_and_salts
+            )
+            .unwrap(),
+            sut.derive_encryption_keys_from_questions_answers_and_salts::<6, 4>(
+                reworded
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn version2_supports_structured_answers() {
+        let sut = cheap_v2();
+        let questions_answers_and_salts =
+            SecurityQuestionsAnswersAndSalts::<3>::try_from_iter([
+                SecurityQuestionAnswerAndSalt::sample(),
+                SecurityQuestionAnswerAndSalt::sample_other(),
+                SecurityQuestionAnswerAndSalt::sample_structured(),
+            ])
+            .unwrap();
+        let keys = sut
+            .derive_encryption_keys_from_questions_answers_and_salts::<3, 2>(
+                questions_answers_and_salts.clone(),
+            )
+            .unwrap();
+
+        let mut wrong_selection = questions_answers_and_salts.clone();
+        wrong_selection[2].answer =
+            SecurityQuestionAnswer::Structured(vec![42, 74]);
+        let other_keys = sut
+            .derive_encryption_keys_from_questions_answers_and_salts::<3, 2>(
+                wrong_selection,
+            )
+            .unwrap();
+        assert_ne!(keys, other_keys);
+    }
+
+    #[test]
+    fn version2_rejects_answer_not_matching_question_kind() {
+        let sut = cheap_v2();
+
+        let mut freeform_to_structured =
+            SecurityQuestionAnswerAndSalt::sample_structured();
+        freeform_to_structured.answer = "Switzerland".into();
+
+        let mut structured_to_freeform =
+            SecurityQuestionAnswerAndSalt::sample();
+        structured_to_freeform.answer =
+            SecurityQuestionAnswer::Structured(vec![42]);
+
+        for qas in [freeform_to_structured, structured_to_freeform] {
+            assert_eq!(
+                sut.derive_entropy_from_question_answer_and_salt(&qas),
+                Err(Error::AnswerDoesNotMatchQuestionKind {
+                    question_id: qas.question.id
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn version1_rejects_structured_answers() {
+        let questions_answers_and_salts =
+            SecurityQuestionsAnswersAndSalts::<3>::try_from_iter([
+                SecurityQuestionAnswerAndSalt::sample(),
+                SecurityQuestionAnswerAndSalt::sample_other(),
+                SecurityQuestionAnswerAndSalt::sample_structured(),
+            ])
+            .unwrap();
+        let result = SutV1::default()
+            .derive_encryption_keys_from_questions_answers_and_salts::<3, 2>(
+                questions_answers_and_salts,
+            );
+        assert_eq!(
+            result,
+            Err(Error::AnswerDoesNotMatchQuestionKind {
+                question_id: SecurityQuestion::sample_structured().id
+            })
+        );
+    }
+
+    #[test]
+    fn version2_serialization_roundtrip() {
+        let original = cheap_v2();
+        let json = serde_json::to_string(&original).unwrap();
+        let deserialized: SutV2 = serde_json::from_str(&json).unwrap();
+        assert_eq!(original, deserialized);
+    }
+
+    #[test]
+    fn subset_of_all_answers_same_keys_as_all_answers() {
+        let questions_answers_and_salts =
+            SecurityQuestionsAnswersAndSalts::sample();
+        for sut in [Sut::version1(), Sut::Version2(cheap_v2())] {
+            let keys = sut
+                .derive_encryption_keys_from_questions_answers_and_salts::<6, 4>(
+                    questions_answers_and_salts.clone(),
+                )
+                .unwrap()
+                .into_iter()
+                .collect::<IndexSet<_>>();
+            let subset_keys = sut
+                .derive_encryption_keys_from_subset_of_questions_answers_and_salts(
+                    questions_answers_and_salts.as_slice(),
+                    4,
+                )
+                .unwrap();
+            assert_eq!(
+                keys.into_iter().collect_vec(),
+                subset_keys.into_iter().collect_vec()
+            );
+        }
+    }
+
+    #[test]
+    fn subset_derives_only_keys_of_covered_combinations() {
+        let questions_answers_and_salts =
+            SecurityQuestionsAnswersAndSalts::sample();
+        let sut = Sut::Version2(cheap_v2());
+        let keys = sut
+            .derive_encryption_keys_from_questions_answers_and_salts::<6, 4>(
+                questions_answers_and_salts.clone(),
+            )
+            .unwrap()
+            .into_iter()
+            .collect::<IndexSet<_>>();
+
+        // Skip the second question, 5 choose 4 combinations remain.
+        let subset = questions_answers_and_salts
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| *index != 1)
+            .map(|(_, qas)| qas.clone())
+            .collect_vec();
+        let subset_keys = sut
+            .derive_encryption_keys_from_subset_of_questions_answers_and_salts(
+                &subset, 4,
+            )
+            .unwrap();
+
+        assert_eq!(subset_keys.len(), 5);
+        assert!(subset_keys.is_subset(&keys));
+    }
+
+    #[test]
+    fn version1_json_still_deserializes() {
+        let json = r#"{"Version1":{"entropies_from_questions_answer_and_salt":null,"kdf_encryption_keys_from_key_exchange_keys":null}}"#;
+        let deserialized: Sut = serde_json::from_str(json).unwrap();
+        assert_eq!(deserialized, Sut::version1());
+    }
 }
@@ -0,0 +1,306 @@
+use crate::prelude::*;
+
+use std::str::FromStr;
+use unicode_normalization::UnicodeNormalization;
+
+/// The language of the answers to security questions, used by
+/// [`AnswerNormalizationStep::LocaleCaseFold`] to apply the case folding -
+/// and equivalence rules - users of that language expect, which the default
+/// Unicode case conversion used by [`AnswerNormalizationStep::CaseFold`] does
+/// not.
+///
+/// Serialized as its [BCP 47] primary language subtag, e.g. `"tr"`.
+///
+/// [BCP 47]: https://www.rfc-editor.org/info/bcp47
+///
+/// # Examples
+///
+/// ```
+/// use svar_core::*;
+///
+/// let locale: AnswerLocale = "tr-TR".parse()?;
+/// assert_eq!(locale, AnswerLocale::Turkish);
+/// assert_eq!(locale.case_fold("İZMİR"), locale.case_fold("izmir"));
+/// assert_eq!(serde_json::to_string(&locale)?, r#""tr""#);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum AnswerLocale {
+    /// Lowercases `I` to dotless `ı` and `İ` to `i`, then treats `ı` as `i`,
+    /// so that answers typed on keyboards with and without the Turkish
+    /// letters are equal, e.g. "ISPARTA", "Isparta" and "ısparta".
+    #[serde(rename = "tr")]
+    Turkish,
+
+    /// Same rules as [`Turkish`](Self::Turkish).
+    #[serde(rename = "az")]
+    Azerbaijani,
+
+    /// Lowercases, then replaces `ß` with `ss` and the umlauts `ä`, `ö` and
+    /// `ü` with `ae`, `oe` and `ue`, so that e.g. "STRASSE", "Straße",
+    /// "Müller" and "Mueller" are equal.
+    #[serde(rename = "de")]
+    German,
+
+    /// Lowercases, treats final sigma `ς` as `σ` and removes the accents and
+    /// breathings of Greek letters - which are omitted when writing in
+    /// uppercase - so that e.g. "ΑΘΗΝΑ" and "Αθήνα" are equal.
+    #[serde(rename = "el")]
+    Greek,
+}
+
+impl AnswerLocale {
+    /// Returns `true` if `c` is in the Greek or Greek Extended blocks.
+    fn is_greek(c: char) -> bool {
+        matches!(c, '\u{0370}'..='\u{03FF}' | '\u{1F00}'..='\u{1FFF}')
+    }
+
+    /// Returns `true` if `c` is a combining accent or breathing used in
+    /// (polytonic) Greek.
+    fn is_greek_diacritic(c: char) -> bool {
+        matches!(
+            c,
+            '\u{0300}' // varia
+                | '\u{0301}' // tonos, oxia
+                | '\u{0304}' // macron
+                | '\u{0306}' // vrachy
+                | '\u{0308}' // dialytika
+                | '\u{0313}' // psili
+                | '\u{0314}' // dasia
+                | '\u{0342}' // perispomeni
+                | '\u{0345}' // ypogegrammeni
+        )
+    }
+
+    fn fold_turkic(answer: &str) -> String {
+        let mut folded = String::with_capacity(answer.len());
+        for c in answer.chars() {
+            match c {
+                // Lowercasing `I` gives dotless `ı` which we treat as `i`,
+                // just like the lowercase of `İ`.
+                'I' | 'İ' | 'ı' => folded.push('i'),
+                _ => folded.extend(c.to_lowercase()),
+            }
+        }
+        folded
+    }
+
+    fn fold_german(answer: &str) -> String {
+        let mut lowercased = answer.to_lowercase();
+        let mut folded = String::with_capacity(lowercased.len());
+        for c in lowercased.chars() {
+            match c {
+                'ß' => folded.push_str("ss"),
+                'ä' => folded.push_str("ae"),
+                'ö' => folded.push_str("oe"),
+                'ü' => folded.push_str("ue"),
+                _ => folded.push(c),
+            }
+        }
+        lowercased.zeroize();
+        folded
+    }
+
+    fn fold_greek(answer: &str) -> String {
+        let mut lowercased = answer.to_lowercase();
+        let mut is_after_greek_letter = false;
+        let folded = lowercased
+            .nfd()
+            .filter(|c| {
+                if Self::is_greek_diacritic(*c) {
+                    return !is_after_greek_letter;
+                }
+                is_after_greek_letter = Self::is_greek(*c);
+                true
+            })
+            .map(|c| if c == 'ς' { 'σ' } else { c })
+            .nfc()
+            .collect();
+        lowercased.zeroize();
+        folded
+    }
+
+    /// Lowercases `answer` according to the rules of this locale.
+    ///
+    /// The answer is canonically composed first, so that decomposed letters,
+    /// e.g. `u` followed by a combining diaeresis, are folded like their
+    /// composed counterparts. Intermediate results are zeroized.
+    pub fn case_fold(&self, answer: impl AsRef<str>) -> String {
+        let mut composed = answer.as_ref().nfc().collect::<String>();
+        let folded = match self {
+            Self::Turkish | Self::Azerbaijani => Self::fold_turkic(&composed),
+            Self::German => Self::fold_german(&composed),
+            Self::Greek => Self::fold_greek(&composed),
+        };
+        composed.zeroize();
+        folded
+    }
+}
+
+impl FromStr for AnswerLocale {
+    type Err = Error;
+
+    /// Parses a BCP 47 language tag, e.g. `"de"` or `"de-CH"`, by its primary
+    /// language subtag, case insensitively, failing with
+    /// [`Error::UnsupportedAnswerLocale`] if the language is not supported.
+    fn from_str(s: &str) -> Result<Self> {
+        let language = s.split(['-', '_']).next().unwrap_or_default();
+        match language.to_ascii_lowercase().as_str() {
+            "tr" => Ok(Self::Turkish),
+            "az" => Ok(Self::Azerbaijani),
+            "de" => Ok(Self::German),
+            "el" => Ok(Self::Greek),
+            _ => Err(Error::UnsupportedAnswerLocale { tag: s.to_owned() }),
+        }
+    }
+}
+
+impl HasSampleValues for AnswerLocale {
+    fn sample() -> Self {
+        Self::Turkish
+    }
+
+    fn sample_other() -> Self {
+        Self::German
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Sut = AnswerLocale;
+
+    fn assert_all_equal(sut: Sut, answers: &[&str]) {
+        let expected = sut.case_fold(answers[0]);
+        for answer in answers {
+            assert_eq!(sut.case_fold(answer), expected, "{answer:?}");
+        }
+    }
+
+    #[test]
+    fn equality() {
+        assert_eq!(Sut::sample(), Sut::sample());
+        assert_eq!(Sut::sample_other(), Sut::sample_other());
+        assert_ne!(Sut::sample(), Sut::sample_other());
+    }
+
+    #[test]
+    fn turkish() {
+        let sut = Sut::Turkish;
+        assert_eq!(sut.case_fold("\u{130}STANBUL"), "istanbul");
+        assert_all_equal(
+            sut,
+            &["ISPARTA", "Isparta", "\u{131}sparta", "isparta"],
+        );
+        assert_all_equal(
+            sut,
+            &["\u{130}ZM\u{130}R", "I\u{307}ZMI\u{307}R", "izmir"],
+        );
+        assert_all_equal(sut, &["\u{DC}SK\u{DC}DAR", "\u{FC}sk\u{FC}dar"]);
+    }
+
+    #[test]
+    fn turkish_differs_from_default_case_fold() {
+        assert_ne!(
+            "\u{130}stanbul".to_lowercase(),
+            Sut::Turkish.case_fold("\u{130}stanbul")
+        );
+    }
+
+    #[test]
+    fn azerbaijani_is_same_as_turkish() {
+        for answer in ["\u{130}STANBUL", "BAKI", "Bak\u{131}"] {
+            assert_eq!(
+                Sut::Azerbaijani.case_fold(answer),
+                Sut::Turkish.case_fold(answer)
+            );
+        }
+    }
+
+    #[test]
+    fn german() {
+        let sut = Sut::German;
+        assert_all_equal(
+            sut,
+            &["STRASSE", "Stra\u{DF}e", "STRA\u{1E9E}E", "strasse"],
+        );
+        assert_all_equal(sut, &["M\u{FC}ller", "Mu\u{308}ller", "MUELLER"]);
+        assert_all_equal(sut, &["K\u{F6}ln", "Koeln", "K\u{D6}LN"]);
+        assert_all_equal(sut, &["M\u{C4}RZ", "maerz"]);
+    }
+
+    #[test]
+    fn greek() {
+        let sut = Sut::Greek;
+        assert_all_equal(
+            sut,
+            &[
+                "\u{391}\u{398}\u{397}\u{39D}\u{391}",
+                "\u{391}\u{3B8}\u{3AE}\u{3BD}\u{3B1}",
+            ],
+        );
+        // Final sigma
+        assert_all_equal(
+            sut,
+            &[
+                "\u{39F}\u{394}\u{39F}\u{3A3}",
+                "\u{3BF}\u{3B4}\u{3CC}\u{3C2}",
+                "\u{3BF}\u{3B4}\u{3BF}\u{3C3}",
+            ],
+        );
+        // Polytonic
+        assert_all_equal(
+            sut,
+            &[
+                "\u{1F08}\u{3B8}\u{1FC6}\u{3BD}\u{3B1}\u{3B9}",
+                "\u{3B1}\u{3B8}\u{3B7}\u{3BD}\u{3B1}\u{3B9}",
+            ],
+        );
+    }
+
+    #[test]
+    fn greek_keeps_diacritics_of_other_scripts() {
+        assert_eq!(Sut::Greek.case_fold("D\u{E9}fense"), "d\u{E9}fense");
+    }
+
+    #[test]
+    fn from_str() {
+        for (tag, locale) in [
+            ("tr", Sut::Turkish),
+            ("tr-TR", Sut::Turkish),
+            ("TR", Sut::Turkish),
+            ("az-Latn-AZ", Sut::Azerbaijani),
+            ("de", Sut::German),
+            ("de_CH", Sut::German),
+            ("el-GR", Sut::Greek),
+        ] {
+            assert_eq!(tag.parse::<Sut>(), Ok(locale));
+        }
+    }
+
+    #[test]
+    fn from_str_unsupported() {
+        for tag in ["en", "", "tur", "sv-SE"] {
+            assert_eq!(
+                tag.parse::<Sut>(),
+                Err(Error::UnsupportedAnswerLocale {
+                    tag: tag.to_owned()
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        for (sut, json) in [
+            (Sut::Turkish, r#""tr""#),
+            (Sut::Azerbaijani, r#""az""#),
+            (Sut::German, r#""de""#),
+            (Sut::Greek, r#""el""#),
+        ] {
+            assert_eq!(serde_json::to_string(&sut).unwrap(), json);
+            assert_eq!(serde_json::from_str::<Sut>(json).unwrap(), sut);
+        }
+    }
+}
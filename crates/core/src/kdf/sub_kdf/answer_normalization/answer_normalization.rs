@@ -0,0 +1,257 @@
+use crate::prelude::*;
+
+/// The ordered [`AnswerNormalizationStep`]s applied to the answer to a
+/// security question before it is used as input key material.
+///
+/// The normalization is serialized as part of the KDF scheme of a sealed
+/// secret, so that the exact rules used when sealing are replayed when
+/// opening, allowing different products to use different rules.
+///
+/// # Examples
+///
+/// ```
+/// use svar_core::*;
+///
+/// // Like the default rules, but also strips hyphens
+/// let normalization = AnswerNormalization::new([
+///     AnswerNormalizationStep::CaseFold,
+///     AnswerNormalizationStep::StripChars {
+///         chars: " -,.".to_owned(),
+///     },
+/// ]);
+/// assert_eq!(
+///     normalization.normalize("Jean-Michel Jarre"),
+///     normalization.normalize("jean michel jarre")
+/// );
+/// ```
+///
+/// # Serialization
+///
+/// ```
+/// use svar_core::*;
+///
+/// let normalization = AnswerNormalization::new([
+///     AnswerNormalizationStep::Nfkc,
+///     AnswerNormalizationStep::CaseFold,
+///     AnswerNormalizationStep::CollapseWhitespace,
+/// ]);
+/// assert_eq!(
+///     serde_json::to_string(&normalization)?,
+///     r#"{"steps":["Nfkc","CaseFold","CollapseWhitespace"]}"#
+/// );
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct AnswerNormalization {
+    /// The steps, applied in order.
+    steps: Vec<AnswerNormalizationStep>,
+}
+
+impl AnswerNormalization {
+    pub fn new(
+        steps: impl IntoIterator<Item = AnswerNormalizationStep>,
+    ) -> Self {
+        Self {
+            steps: steps.into_iter().collect(),
+        }
+    }
+
+    /// The rules of
+    /// [`SecurityQuestionsKeyExchangeKeysFromQandAsLowerTrimUtf8`]:
+    /// lowercase, then remove `SECURITY_QUESTIONS_TRIMMED_CHARS`.
+    pub fn lower_trim() -> Self {
+        Self::new([
+            AnswerNormalizationStep::CaseFold,
+            AnswerNormalizationStep::StripChars {
+                chars: SECURITY_QUESTIONS_TRIMMED_CHARS.iter().collect(),
+            },
+        ])
+    }
+
+    /// Like [`lower_trim`](Self::lower_trim) but NFKC normalizes the answer
+    /// and lowercases it according to the rules of `locale`, e.g. making
+    /// "İSTANBUL" and "istanbul" equal for [`AnswerLocale::Turkish`].
+    pub fn locale_lower_trim(locale: AnswerLocale) -> Self {
+        Self::new([
+            AnswerNormalizationStep::Nfkc,
+            AnswerNormalizationStep::LocaleCaseFold { locale },
+            AnswerNormalizationStep::StripChars {
+                chars: SECURITY_QUESTIONS_TRIMMED_CHARS.iter().collect(),
+            },
+        ])
+    }
+
+    /// The rules of
+    /// [`SecurityQuestionsKeyExchangeKeysFromQandAsNfkcLowerTrimUtf8ByQuestionId`]:
+    /// NFKC, lowercase, optionally fold diacritics, then remove
+    /// `SECURITY_QUESTIONS_TRIMMED_CHARS`.
+    pub fn nfkc_lower_trim(fold_diacritics: bool) -> Self {
+        // Lowercasing can result in a string which is not normalized, e.g.
+        // "İ" is lowercased to "i" followed by a combining dot above, so we
+        // normalize again after lowercasing.
+        let renormalize = if fold_diacritics {
+            AnswerNormalizationStep::FoldDiacritics
+        } else {
+            AnswerNormalizationStep::Nfkc
+        };
+        Self::new([
+            AnswerNormalizationStep::Nfkc,
+            AnswerNormalizationStep::CaseFold,
+            renormalize,
+            AnswerNormalizationStep::StripChars {
+                chars: SECURITY_QUESTIONS_TRIMMED_CHARS.iter().collect(),
+            },
+        ])
+    }
+
+    /// The steps, applied in order.
+    pub fn steps(&self) -> &[AnswerNormalizationStep] {
+        &self.steps
+    }
+
+    /// Applies all steps to `answer`, in order, zeroizing the intermediate
+    /// results.
+    pub fn normalize(&self, answer: impl AsRef<str>) -> String {
+        self.steps
+            .iter()
+            .fold(answer.as_ref().to_owned(), |answer, step| {
+                step.apply(answer)
+            })
+    }
+}
+
+impl Default for AnswerNormalization {
+    fn default() -> Self {
+        Self::lower_trim()
+    }
+}
+
+impl HasSampleValues for AnswerNormalization {
+    fn sample() -> Self {
+        Self::lower_trim()
+    }
+
+    fn sample_other() -> Self {
+        Self::new([
+            AnswerNormalizationStep::Nfkc,
+            AnswerNormalizationStep::FoldDiacritics,
+            AnswerNormalizationStep::CaseFold,
+            AnswerNormalizationStep::StripChars {
+                chars: "-,.!?'\"".to_owned(),
+            },
+            AnswerNormalizationStep::sample_other(),
+            AnswerNormalizationStep::CollapseNumbers,
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::assert_json_snapshot;
+
+    use super::*;
+
+    type Sut = AnswerNormalization;
+
+    #[test]
+    fn equality() {
+        assert_eq!(Sut::sample(), Sut::sample());
+        assert_eq!(Sut::sample_other(), Sut::sample_other());
+        assert_ne!(Sut::sample(), Sut::sample_other());
+    }
+
+    #[test]
+    fn default_is_lower_trim() {
+        assert_eq!(Sut::default(), Sut::lower_trim());
+    }
+
+    #[test]
+    fn lower_trim_is_same_as_lower_trim_utf8() {
+        let sut = Sut::lower_trim();
+        for answer in [
+            "Jean-Michel Jarre, Paris La D\u{E9}fense, 1990",
+            "FoO\nB.a\tR ' ! FiZz ? \u{2018} B \u{2019} u\u{FF07}ZZ",
+            "\u{130}stanbul",
+        ] {
+            assert_eq!(
+                sut.normalize(answer),
+                SecurityQuestionsKeyExchangeKeysFromQandAsLowerTrimUtf8
+                    .trim_answer(answer)
+            );
+        }
+    }
+
+    #[test]
+    fn locale_lower_trim() {
+        for (locale, lhs, rhs) in [
+            (
+                AnswerLocale::Turkish,
+                "\u{130}STANBUL, 1453",
+                "istanbul 1453",
+            ),
+            (AnswerLocale::German, "Stra\u{DF}e", "STRASSE"),
+            (AnswerLocale::German, "M\u{FC}ller", "Mueller"),
+            (
+                AnswerLocale::Greek,
+                "\u{391}\u{398}\u{397}\u{39D}\u{391}",
+                "\u{3B1}\u{3B8}\u{3AE}\u{3BD}\u{3B1}",
+            ),
+        ] {
+            let sut = Sut::locale_lower_trim(locale);
+            assert_eq!(sut.normalize(lhs), sut.normalize(rhs));
+            assert_ne!(
+                Sut::lower_trim().normalize(lhs),
+                Sut::lower_trim().normalize(rhs)
+            );
+        }
+    }
+
+    #[test]
+    fn steps_are_applied_in_order() {
+        let strip_dash = AnswerNormalizationStep::StripChars {
+            chars: "-".to_owned(),
+        };
+        let articles = AnswerNormalizationStep::RemoveArticles {
+            articles: vec!["the".to_owned()],
+        };
+        let case_fold_first =
+            Sut::new([AnswerNormalizationStep::CaseFold, articles.clone()]);
+        let case_fold_last =
+            Sut::new([articles, AnswerNormalizationStep::CaseFold]);
+        assert_eq!(case_fold_first.normalize("The Who"), "who");
+        assert_eq!(case_fold_last.normalize("The Who"), "the who");
+        assert_eq!(
+            Sut::new([strip_dash, AnswerNormalizationStep::CollapseWhitespace])
+                .normalize("a - b"),
+            "a b"
+        );
+    }
+
+    #[test]
+    fn sample_other() {
+        let sut = Sut::sample_other();
+        assert_eq!(
+            sut.normalize("The Beatles, Shea Stadium, 1965"),
+            sut.normalize("the  beatles shea stadium 01965")
+        );
+        assert_eq!(sut.normalize("Bj\u{F6}rn"), "bjorn");
+    }
+
+    #[test]
+    fn empty_normalization_is_identity() {
+        assert_eq!(Sut::new([]).normalize(" Abc "), " Abc ");
+    }
+
+    #[test]
+    fn json_snapshot() {
+        assert_json_snapshot!([Sut::sample(), Sut::sample_other()]);
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        for sut in [Sut::sample(), Sut::sample_other()] {
+            let json = serde_json::to_string(&sut).unwrap();
+            assert_eq!(serde_json::from_str::<Sut>(&json).unwrap(), sut);
+        }
+    }
+}
@@ -0,0 +1,272 @@
+use crate::prelude::*;
+
+use unicode_normalization::UnicodeNormalization;
+
+/// A single step of an [`AnswerNormalization`], transforming the answer to a
+/// security question so that answers which only differ in ways the user does
+/// not care about - casing, punctuation, accents - derive the same entropy.
+///
+/// # Examples
+///
+/// ```
+/// use svar_core::*;
+///
+/// let step = AnswerNormalizationStep::CollapseWhitespace;
+/// assert_eq!(
+///     step.apply(" Paris \t La  Défense ".to_owned()),
+///     "Paris La Défense"
+/// );
+/// ```
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum AnswerNormalizationStep {
+    /// Unicode Normalization Form KC, making e.g. composed and decomposed
+    /// accented letters, full-width and half-width letters, and ligatures
+    /// and the letters they consist of, equal.
+    Nfkc,
+
+    /// Removes all combining diacritical marks after canonical decomposition,
+    /// making e.g. "Björn" and "Bjorn" equal.
+    ///
+    /// Letters which do not decompose, such as `ø`, `ł`, `æ` or `ß`, are
+    /// kept as is, and so are the combining marks of scripts in which they
+    /// are part of the letter, e.g. the vowel signs of Devanagari.
+    FoldDiacritics,
+
+    /// Lowercases the answer using the default Unicode case conversion.
+    ///
+    /// Use [`LocaleCaseFold`](Self::LocaleCaseFold) for languages whose
+    /// casing rules differ, e.g. Turkish.
+    CaseFold,
+
+    /// Lowercases the answer according to the rules of `locale`, see
+    /// [`AnswerLocale`].
+    LocaleCaseFold {
+        /// The language of the answers.
+        locale: AnswerLocale,
+    },
+
+    /// Removes every occurrence of each of `chars`.
+    StripChars {
+        /// The characters to remove.
+        chars: String,
+    },
+
+    /// Trims leading and trailing whitespace and replaces each run of
+    /// whitespace with a single space.
+    CollapseWhitespace,
+
+    /// Removes the words equal to any of `articles`, words being separated
+    /// by whitespace, which is collapsed like
+    /// [`CollapseWhitespace`](Self::CollapseWhitespace) does.
+    ///
+    /// Words are compared exactly, so this step should come after
+    /// [`CaseFold`](Self::CaseFold) with lowercase `articles`.
+    RemoveArticles {
+        /// The words to remove, e.g. "the", "a" and "an".
+        articles: Vec<String>,
+    },
+
+    /// Removes the leading zeros of each number, i.e. each run of ASCII
+    /// digits, making e.g. "007" and "7" equal.
+    CollapseNumbers,
+}
+
+impl AnswerNormalizationStep {
+    /// Returns `true` if `c` is in one of the Unicode blocks of combining
+    /// diacritical marks.
+    fn is_combining_diacritical_mark(c: char) -> bool {
+        matches!(
+            c,
+            '\u{0300}'..='\u{036F}' // Combining Diacritical Marks
+                | '\u{1AB0}'..='\u{1AFF}' // ... Extended
+                | '\u{1DC0}'..='\u{1DFF}' // ... Supplement
+                | '\u{20D0}'..='\u{20FF}' // ... for Symbols
+                | '\u{FE20}'..='\u{FE2F}' // Combining Half Marks
+        )
+    }
+
+    fn collapse_numbers(answer: &str) -> String {
+        let mut collapsed = String::with_capacity(answer.len());
+        // If we are in a number of which no digit has been kept yet
+        let mut is_leading = true;
+        // If we skipped a zero since the start of the current number
+        let mut skipped_zero = false;
+        for c in answer.chars() {
+            if c.is_ascii_digit() {
+                if is_leading && c == '0' {
+                    skipped_zero = true;
+                    continue;
+                }
+                is_leading = false;
+            } else {
+                if skipped_zero && is_leading {
+                    collapsed.push('0');
+                }
+                is_leading = true;
+            }
+            skipped_zero = false;
+            collapsed.push(c);
+        }
+        if skipped_zero {
+            collapsed.push('0');
+        }
+        collapsed
+    }
+
+    /// Applies this step to `answer`, zeroizing it if the result is a new
+    /// string.
+    pub fn apply(&self, mut answer: String) -> String {
+        let applied = match self {
+            Self::Nfkc => answer.nfkc().collect::<String>(),
+            Self::FoldDiacritics => answer
+                .nfd()
+                .filter(|c| !Self::is_combining_diacritical_mark(*c))
+                .nfc()
+                .collect::<String>(),
+            Self::CaseFold => answer.to_lowercase(),
+            Self::LocaleCaseFold { locale } => locale.case_fold(&answer),
+            Self::StripChars { chars } => {
+                answer.retain(|c| !chars.contains(c));
+                return answer;
+            }
+            Self::CollapseWhitespace => answer.split_whitespace().join(" "),
+            Self::RemoveArticles { articles } => answer
+                .split_whitespace()
+                .filter(|word| !articles.iter().any(|a| a == word))
+                .join(" "),
+            Self::CollapseNumbers => Self::collapse_numbers(&answer),
+        };
+        answer.zeroize();
+        applied
+    }
+}
+
+impl HasSampleValues for AnswerNormalizationStep {
+    fn sample() -> Self {
+        Self::CaseFold
+    }
+
+    fn sample_other() -> Self {
+        Self::RemoveArticles {
+            articles: vec!["the".to_owned(), "a".to_owned(), "an".to_owned()],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Sut = AnswerNormalizationStep;
+
+    fn apply(sut: Sut, answer: &str) -> String {
+        sut.apply(answer.to_owned())
+    }
+
+    #[test]
+    fn equality() {
+        assert_eq!(Sut::sample(), Sut::sample());
+        assert_eq!(Sut::sample_other(), Sut::sample_other());
+        assert_ne!(Sut::sample(), Sut::sample_other());
+    }
+
+    #[test]
+    fn nfkc() {
+        assert_eq!(apply(Sut::Nfkc, "De\u{301}fense"), "D\u{E9}fense");
+        assert_eq!(apply(Sut::Nfkc, "\u{FF2A}\u{FB01}"), "Jfi");
+    }
+
+    #[test]
+    fn fold_diacritics() {
+        assert_eq!(apply(Sut::FoldDiacritics, "Bj\u{F6}rn"), "Bjorn");
+        assert_eq!(apply(Sut::FoldDiacritics, "\u{F8}"), "\u{F8}");
+    }
+
+    #[test]
+    fn case_fold() {
+        assert_eq!(apply(Sut::CaseFold, "\u{C9}COLE"), "\u{E9}cole");
+    }
+
+    #[test]
+    fn locale_case_fold() {
+        let sut = Sut::LocaleCaseFold {
+            locale: AnswerLocale::Turkish,
+        };
+        assert_eq!(apply(sut, "\u{130}STANBUL"), "istanbul");
+        assert_eq!(
+            serde_json::to_string(&Sut::LocaleCaseFold {
+                locale: AnswerLocale::German
+            })
+            .unwrap(),
+            r#"{"LocaleCaseFold":{"locale":"de"}}"#
+        );
+    }
+
+    #[test]
+    fn strip_chars() {
+        let sut = Sut::StripChars {
+            chars: "-,".to_owned(),
+        };
+        assert_eq!(
+            apply(sut, "Jean-Michel Jarre, 1990"),
+            "JeanMichel Jarre 1990"
+        );
+    }
+
+    #[test]
+    fn collapse_whitespace() {
+        assert_eq!(
+            apply(Sut::CollapseWhitespace, "\t London \n\n 1973  "),
+            "London 1973"
+        );
+    }
+
+    #[test]
+    fn remove_articles() {
+        assert_eq!(
+            apply(Sut::sample_other(), "the  beatles played an encore"),
+            "beatles played encore"
+        );
+        assert_eq!(apply(Sut::sample_other(), "The Beatles"), "The Beatles");
+        assert_eq!(apply(Sut::sample_other(), "theatre"), "theatre");
+    }
+
+    #[test]
+    fn collapse_numbers() {
+        for (answer, collapsed) in [
+            ("007", "7"),
+            ("1990", "1990"),
+            ("0", "0"),
+            ("000", "0"),
+            ("05/03/1990", "5/3/1990"),
+            ("room 0, floor 00", "room 0, floor 0"),
+            ("agent 007 and 0", "agent 7 and 0"),
+            ("1000", "1000"),
+            ("a0b", "a0b"),
+            ("", ""),
+        ] {
+            assert_eq!(apply(Sut::CollapseNumbers, answer), collapsed);
+        }
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        for sut in [
+            Sut::Nfkc,
+            Sut::FoldDiacritics,
+            Sut::CaseFold,
+            Sut::LocaleCaseFold {
+                locale: AnswerLocale::Greek,
+            },
+            Sut::StripChars {
+                chars: "-".to_owned(),
+            },
+            Sut::CollapseWhitespace,
+            Sut::sample_other(),
+            Sut::CollapseNumbers,
+        ] {
+            let json = serde_json::to_string(&sut).unwrap();
+            assert_eq!(serde_json::from_str::<Sut>(&json).unwrap(), sut);
+        }
+    }
+}
@@ -0,0 +1,14 @@
+mod answer_locale;
+#[allow(clippy::module_inception)]
+mod answer_normalization;
+mod answer_normalization_step;
+mod phonetic_encoding;
+mod phonetic_entropy_loss;
+mod transliteration;
+
+pub use answer_locale::*;
+pub use answer_normalization::*;
+pub use answer_normalization_step::*;
+pub use phonetic_encoding::*;
+pub use phonetic_entropy_loss::*;
+pub use transliteration::*;
@@ -0,0 +1,371 @@
+use crate::prelude::*;
+
+use unicode_normalization::{UnicodeNormalization, char::is_combining_mark};
+
+/// Common given names, one per line, including common spelling variants, used
+/// to estimate the entropy lost by a [`PhoneticEncoding`].
+const REFERENCE_GIVEN_NAMES: &str = include_str!("given_names.txt");
+
+/// A versioned phonetic encoding of names, making names which sound alike
+/// but are spelled differently, e.g. "Catherine" and "Kathryn", equal.
+///
+/// Declared on the
+/// [`phonetic_encoding`](SecurityQuestionExpectedAnswerFormat::phonetic_encoding)
+/// of the expected answer format of questions answered with names, and
+/// applied to answers before they are normalized by the
+/// [`SecurityQuestionsEntropiesKdf`].
+///
+/// Since different names share the same phonetic key, encoding answers
+/// reduces their entropy, see [`entropy_loss`](Self::entropy_loss), trading
+/// security for recall.
+///
+/// The rules of released versions MUST never change, since that would make
+/// secrets sealed with them impossible to open, instead a new version is
+/// added.
+///
+/// # Examples
+///
+/// ```
+/// use svar_core::*;
+///
+/// let encoding = PhoneticEncoding::MetaphoneVersion1;
+/// assert_eq!(encoding.encode("Catherine"), "K0RN");
+/// assert_eq!(encoding.encode("Kathryn"), "K0RN");
+/// assert_eq!(
+///     encoding.encode("Stephen Jon"),
+///     encoding.encode("Steven John")
+/// );
+/// ```
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum PhoneticEncoding {
+    /// The original Metaphone algorithm by Lawrence Philips, with the initial
+    /// vowel always encoded as `A` and `CH` before `R` or `L` encoded as `K`,
+    /// as Double Metaphone does, applied to each token - run of letters and
+    /// digits - of the answer.
+    ///
+    /// Diacritics are removed before encoding, tokens which contain anything
+    /// but the letters `A` to `Z` after that, e.g. numbers or letters of
+    /// other scripts, are only lowercased.
+    MetaphoneVersion1,
+}
+
+impl PhoneticEncoding {
+    /// The Metaphone key of `word`, consisting of uppercase ASCII letters
+    /// and `0` for `TH`, `word` consisting of the uppercase ASCII letters
+    /// `A` to `Z`.
+    ///
+    /// ❗️ MUST NOT be changed ❗️
+    fn metaphone_version1(word: &[u8]) -> String {
+        let len = word.len();
+        let at = |i: usize| word.get(i).copied().unwrap_or_default();
+        let is_vowel = |c: u8| matches!(c, b'A' | b'E' | b'I' | b'O' | b'U');
+        let is_front_vowel = |c: u8| matches!(c, b'E' | b'I' | b'Y');
+
+        let mut key = String::with_capacity(len);
+        let start = match (at(0), at(1)) {
+            (b'A', b'E') | (b'G', b'N') | (b'K', b'N') | (b'P', b'N') => 1,
+            (b'W', b'R') => 1,
+            (b'X', _) => {
+                key.push('S');
+                1
+            }
+            (b'W', b'H') => {
+                key.push('W');
+                2
+            }
+            _ => 0,
+        };
+
+        let mut i = start;
+        while i < len {
+            let c = at(i);
+            let prev = if i > 0 { at(i - 1) } else { 0 };
+            let next = at(i + 1);
+            let after_next = at(i + 2);
+
+            // Double letters are encoded once
+            if i > start && c == prev {
+                i += 1;
+                continue;
+            }
+
+            match c {
+                b'A' | b'E' | b'I' | b'O' | b'U' => {
+                    // Only an initial vowel is encoded, always as `A`
+                    if i == start && key.is_empty() {
+                        key.push('A');
+                    }
+                }
+                b'B' => {
+                    if !(i == len - 1 && prev == b'M') {
+                        key.push('B');
+                    }
+                }
+                b'C' => {
+                    if next == b'I' && after_next == b'A' {
+                        key.push('X');
+                    } else if next == b'H' {
+                        if prev == b'S' || matches!(after_next, b'R' | b'L') {
+                            key.push('K');
+                        } else {
+                            key.push('X');
+                        }
+                        i += 1;
+                    } else if is_front_vowel(next) {
+                        if prev != b'S' {
+                            key.push('S');
+                        }
+                    } else {
+                        key.push('K');
+                    }
+                }
+                b'D' => {
+                    if next == b'G' && is_front_vowel(after_next) {
+                        key.push('J');
+                        i += 1;
+                    } else {
+                        key.push('T');
+                    }
+                }
+                b'G' => {
+                    if next == b'H' {
+                        if is_vowel(after_next) {
+                            key.push('K');
+                        }
+                        i += 1;
+                    } else if next == b'N'
+                        && (i + 2 == len
+                            || (after_next == b'E'
+                                && at(i + 3) == b'D'
+                                && i + 4 == len))
+                    {
+                        // Silent, e.g. "Gnome", "Signed"
+                    } else if is_front_vowel(next) {
+                        key.push('J');
+                    } else {
+                        key.push('K');
+                    }
+                }
+                b'H' => {
+                    if is_vowel(next)
+                        && !matches!(prev, b'C' | b'S' | b'P' | b'T' | b'G')
+                    {
+                        key.push('H');
+                    }
+                }
+                b'K' => {
+                    if prev != b'C' {
+                        key.push('K');
+                    }
+                }
+                b'P' => {
+                    if next == b'H' {
+                        key.push('F');
+                        i += 1;
+                    } else {
+                        key.push('P');
+                    }
+                }
+                b'Q' => key.push('K'),
+                b'S' => {
+                    if next == b'H' {
+                        key.push('X');
+                        i += 1;
+                    } else if next == b'I' && matches!(after_next, b'O' | b'A')
+                    {
+                        key.push('X');
+                    } else if next == b'C' && after_next == b'H' {
+                        key.push_str("SK");
+                        i += 2;
+                    } else {
+                        key.push('S');
+                    }
+                }
+                b'T' => {
+                    if next == b'I' && matches!(after_next, b'O' | b'A') {
+                        key.push('X');
+                    } else if next == b'H' {
+                        key.push('0');
+                        i += 1;
+                    } else if next == b'C' && after_next == b'H' {
+                        // Silent, e.g. "Mitchell"
+                    } else {
+                        key.push('T');
+                    }
+                }
+                b'V' => key.push('F'),
+                b'W' | b'Y' => {
+                    if is_vowel(next) {
+                        key.push(char::from(c));
+                    }
+                }
+                b'X' => key.push_str("KS"),
+                b'Z' => key.push('S'),
+                _ => key.push(char::from(c)),
+            }
+            i += 1;
+        }
+        key
+    }
+
+    /// The phonetic key of a single token, or the lowercased token if it
+    /// cannot be encoded.
+    fn encode_token(&self, token: &str) -> String {
+        let mut folded = token
+            .nfd()
+            .filter(|c| !is_combining_mark(*c))
+            .collect::<String>()
+            .to_ascii_uppercase();
+        let key = if folded.bytes().all(|b| b.is_ascii_uppercase()) {
+            match self {
+                Self::MetaphoneVersion1 => {
+                    Self::metaphone_version1(folded.as_bytes())
+                }
+            }
+        } else {
+            String::new()
+        };
+        folded.zeroize();
+        if key.is_empty() {
+            token.to_lowercase()
+        } else {
+            key
+        }
+    }
+
+    /// Encodes each token of `answer`, i.e. each run of letters and digits,
+    /// separating the keys with a single space.
+    pub fn encode(&self, answer: impl AsRef<str>) -> String {
+        answer
+            .as_ref()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|token| !token.is_empty())
+            .map(|token| self.encode_token(token))
+            .join(" ")
+    }
+
+    /// An estimate of the entropy lost by encoding a name using this
+    /// encoding, based on an embedded list of common given names assumed to
+    /// be equally likely.
+    pub fn entropy_loss(&self) -> PhoneticEntropyLoss {
+        let names = REFERENCE_GIVEN_NAMES
+            .lines()
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .collect::<IndexSet<_>>();
+        let names_by_key =
+            names.iter().into_group_map_by(|name| self.encode(name));
+        PhoneticEntropyLoss::new(names_by_key.values().map(Vec::len))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use insta::assert_json_snapshot;
+
+    type Sut = PhoneticEncoding;
+
+    fn encode(word: &str) -> String {
+        Sut::MetaphoneVersion1.encode(word)
+    }
+
+    #[test]
+    fn spelling_variants_are_equal() {
+        for variants in [
+            &["Catherine", "Kathryn", "Katherine", "Cathryn"][..],
+            &["Stephen", "Steven"],
+            &["Geoffrey", "Jeffrey"],
+            &["Christina", "Kristina"],
+            &["Philip", "Phillip", "Filip"],
+            &["Jon", "John"],
+            &["Sara", "Sarah"],
+            &["Rebecca", "Rebeca"],
+            &["Aaron", "Erin"],
+            &["Mohammed", "Muhammad"],
+            &["Claire", "Clare"],
+            &["Meghan", "Megan"],
+        ] {
+            let key = encode(variants[0]);
+            for variant in variants {
+                assert_eq!(encode(variant), key, "{variant}");
+            }
+        }
+    }
+
+    #[test]
+    fn different_names_are_not_equal() {
+        for (lhs, rhs) in [
+            ("Maria", "Martha"),
+            ("Anna", "Emma"),
+            ("Catherine", "Caroline"),
+            ("Steven", "Stella"),
+        ] {
+            assert_ne!(encode(lhs), encode(rhs), "{lhs} vs {rhs}");
+        }
+    }
+
+    #[test]
+    fn keys() {
+        for (word, key) in [
+            ("Catherine", "K0RN"),
+            ("Charles", "XRLS"),
+            ("Christopher", "KRSTFR"),
+            ("Schmidt", "SKMTT"),
+            ("Knight", "NT"),
+            ("Wright", "RT"),
+            ("Whitney", "WTN"),
+            ("Xavier", "SFR"),
+            ("Dodge", "TJ"),
+            ("Mitchell", "MXL"),
+            ("Patricia", "PTRX"),
+            ("Lamb", "LM"),
+            ("Zoe", "S"),
+        ] {
+            assert_eq!(encode(word), key, "{word}");
+        }
+    }
+
+    #[test]
+    fn case_and_diacritics_are_ignored() {
+        assert_eq!(encode("chloe"), encode("CHLO\u{CB}"));
+        assert_eq!(encode("Ren\u{E9}e"), encode("Renee"));
+    }
+
+    #[test]
+    fn each_token_is_encoded() {
+        assert_eq!(encode("Mary-Kate  Smith"), "MR KT SM0");
+    }
+
+    #[test]
+    fn tokens_which_cannot_be_encoded_are_lowercased() {
+        assert_eq!(encode("Paris 1990"), "PRS 1990");
+        assert_eq!(
+            encode("\u{421}\u{435}\u{440}\u{433}\u{435}\u{439}"),
+            "\u{441}\u{435}\u{440}\u{433}\u{435}\u{439}"
+        );
+        assert_eq!(encode("Stra\u{DF}e"), "stra\u{DF}e");
+    }
+
+    #[test]
+    fn empty_answer() {
+        assert_eq!(encode(""), "");
+        assert_eq!(encode(" - "), "");
+    }
+
+    #[test]
+    fn entropy_loss() {
+        let loss = Sut::MetaphoneVersion1.entropy_loss();
+        assert!(loss.distinct_key_count < loss.reference_name_count);
+        assert!(loss.bits_lost_per_name > 0.0);
+        assert_json_snapshot!(loss);
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        let sut = Sut::MetaphoneVersion1;
+        let json = serde_json::to_string(&sut).unwrap();
+        assert_eq!(json, r#""MetaphoneVersion1""#);
+        assert_eq!(serde_json::from_str::<Sut>(&json).unwrap(), sut);
+    }
+}
@@ -0,0 +1,117 @@
+use crate::prelude::*;
+
+/// An estimate of the entropy lost by encoding names using a
+/// [`PhoneticEncoding`], returned by [`PhoneticEncoding::entropy_loss`], so
+/// that product teams can consciously trade security for recall.
+///
+/// Estimated using a list of reference names assumed to be equally likely,
+/// the loss is the conditional entropy of a name given its phonetic key,
+/// i.e. how many bits of the name an attacker no longer has to guess once
+/// the key is guessed.
+///
+/// # Examples
+///
+/// ```
+/// use svar_core::*;
+///
+/// let loss = PhoneticEncoding::MetaphoneVersion1.entropy_loss();
+/// assert!(loss.distinct_key_count < loss.reference_name_count);
+/// assert!(loss.bits_lost_per_name > 0.0);
+/// assert!(loss.bits_lost_per_name < loss.reference_bits_per_name());
+/// ```
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct PhoneticEntropyLoss {
+    /// The number of distinct reference names.
+    pub reference_name_count: usize,
+
+    /// The number of distinct phonetic keys of the reference names.
+    pub distinct_key_count: usize,
+
+    /// The most reference names sharing a single phonetic key.
+    pub largest_key_collision: usize,
+
+    /// The average number of bits of entropy lost per name, for answers
+    /// consisting of several names the loss is per name.
+    pub bits_lost_per_name: f64,
+}
+
+impl PhoneticEntropyLoss {
+    /// Calculates the loss given the number of reference names sharing each
+    /// phonetic key.
+    pub(crate) fn new(names_per_key: impl IntoIterator<Item = usize>) -> Self {
+        // Sorted so that the floating point sum does not depend on the order
+        // of the keys
+        let names_per_key = names_per_key.into_iter().sorted().collect_vec();
+        let reference_name_count = names_per_key.iter().sum::<usize>();
+
+        // H(name | key) = sum over keys of P(key) * log2(names with key)
+        let bits_lost_per_name = names_per_key
+            .iter()
+            .map(|&count| {
+                count as f64 / reference_name_count as f64
+                    * (count as f64).log2()
+            })
+            .sum();
+
+        Self {
+            reference_name_count,
+            distinct_key_count: names_per_key.len(),
+            largest_key_collision: names_per_key
+                .iter()
+                .copied()
+                .max()
+                .unwrap_or_default(),
+            bits_lost_per_name,
+        }
+    }
+
+    /// The entropy of a name picked uniformly from the reference names,
+    /// before encoding it.
+    pub fn reference_bits_per_name(&self) -> f64 {
+        (self.reference_name_count as f64).log2()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Sut = PhoneticEntropyLoss;
+
+    #[test]
+    fn no_collisions_is_no_loss() {
+        let sut = Sut::new([1, 1, 1, 1]);
+        assert_eq!(sut.bits_lost_per_name, 0.0);
+        assert_eq!(sut.distinct_key_count, 4);
+        assert_eq!(sut.largest_key_collision, 1);
+        assert_eq!(sut.reference_bits_per_name(), 2.0);
+    }
+
+    #[test]
+    fn single_key_loses_everything() {
+        let sut = Sut::new([8]);
+        assert_eq!(sut.bits_lost_per_name, 3.0);
+        assert_eq!(sut.bits_lost_per_name, sut.reference_bits_per_name());
+    }
+
+    #[test]
+    fn pairs_lose_one_bit() {
+        let sut = Sut::new([2, 2, 2, 2]);
+        assert_eq!(sut.bits_lost_per_name, 1.0);
+        assert_eq!(sut.largest_key_collision, 2);
+    }
+
+    #[test]
+    fn uneven_collisions() {
+        // Half the names share a key, the other half are unique
+        let sut = Sut::new([4, 1, 1, 1, 1]);
+        assert_eq!(sut.bits_lost_per_name, 1.0);
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        let sut = Sut::new([4, 1, 1, 1, 1]);
+        let json = serde_json::to_string(&sut).unwrap();
+        assert_eq!(serde_json::from_str::<Sut>(&json).unwrap(), sut);
+    }
+}
@@ -0,0 +1,266 @@
+use crate::prelude::*;
+
+use unicode_normalization::UnicodeNormalization;
+
+/// A versioned table transliterating answers written in another script to a
+/// canonical lowercase Latin form, so that e.g. "Сергей" and "Sergei" derive
+/// the same entropy.
+///
+/// Selected per [`SecurityQuestion`] using its
+/// [`transliteration`](SecurityQuestion::transliteration), typically for
+/// questions about names, and applied to answers before they are normalized
+/// by the [`SecurityQuestionsEntropiesKdf`].
+///
+/// The tables of released versions MUST never change, since that would make
+/// secrets sealed with them impossible to open, instead a new version is
+/// added.
+///
+/// # Examples
+///
+/// ```
+/// use svar_core::*;
+///
+/// let transliteration = Transliteration::ToLatinVersion1;
+/// assert_eq!(transliteration.transliterate("Сергей"), "sergei");
+/// assert_eq!(transliteration.transliterate("Αλέξανδρος"), "alexandros");
+/// assert_eq!(transliteration.transliterate("Sergei"), "sergei");
+/// ```
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Transliteration {
+    /// Transliterates Cyrillic, following the Russian ICAO (Doc 9303)
+    /// passport romanization extended with the letters of Ukrainian,
+    /// Belarusian, Serbian and Macedonian, and Greek, following ELOT 743
+    /// without its digraph rules, letter by letter, after lowercasing the
+    /// answer and removing the accents and breathings of Greek letters.
+    ///
+    /// Letters of other scripts are kept as is.
+    ToLatinVersion1,
+}
+
+impl Transliteration {
+    /// Returns `true` if `c` is in the Greek or Greek Extended blocks.
+    fn is_greek(c: char) -> bool {
+        matches!(c, '\u{0370}'..='\u{03FF}' | '\u{1F00}'..='\u{1FFF}')
+    }
+
+    /// The Latin transliteration of the lowercase letter `c` in version 1 of
+    /// the table, `None` if `c` is kept as is.
+    ///
+    /// ❗️ MUST NOT be changed ❗️
+    fn to_latin_version1(c: char) -> Option<&'static str> {
+        let latin = match c {
+            // Cyrillic
+            'а' => "a",
+            'б' => "b",
+            'в' => "v",
+            'г' => "g",
+            'д' => "d",
+            'е' => "e",
+            'ё' => "e",
+            'ж' => "zh",
+            'з' => "z",
+            'и' => "i",
+            'й' => "i",
+            'к' => "k",
+            'л' => "l",
+            'м' => "m",
+            'н' => "n",
+            'о' => "o",
+            'п' => "p",
+            'р' => "r",
+            'с' => "s",
+            'т' => "t",
+            'у' => "u",
+            'ф' => "f",
+            'х' => "kh",
+            'ц' => "ts",
+            'ч' => "ch",
+            'ш' => "sh",
+            'щ' => "shch",
+            'ъ' => "ie",
+            'ы' => "y",
+            'ь' => "",
+            'э' => "e",
+            'ю' => "iu",
+            'я' => "ia",
+            // Ukrainian and Belarusian
+            'є' => "ie",
+            'і' => "i",
+            'ї' => "i",
+            'ґ' => "g",
+            'ў' => "u",
+            // Serbian and Macedonian
+            'ђ' => "dj",
+            'ј' => "j",
+            'љ' => "lj",
+            'њ' => "nj",
+            'ћ' => "c",
+            'џ' => "dz",
+            'ѓ' => "gj",
+            'ќ' => "kj",
+            'ѕ' => "dz",
+            // Greek
+            'α' => "a",
+            'β' => "v",
+            'γ' => "g",
+            'δ' => "d",
+            'ε' => "e",
+            'ζ' => "z",
+            'η' => "i",
+            'θ' => "th",
+            'ι' => "i",
+            'κ' => "k",
+            'λ' => "l",
+            'μ' => "m",
+            'ν' => "n",
+            'ξ' => "x",
+            'ο' => "o",
+            'π' => "p",
+            'ρ' => "r",
+            'σ' => "s",
+            'ς' => "s",
+            'τ' => "t",
+            'υ' => "y",
+            'φ' => "f",
+            'χ' => "ch",
+            'ψ' => "ps",
+            'ω' => "o",
+            _ => return None,
+        };
+        Some(latin)
+    }
+
+    /// Lowercases and transliterates `answer`, zeroizing the intermediate
+    /// results.
+    pub fn transliterate(&self, answer: impl AsRef<str>) -> String {
+        let mut lowercased = answer.as_ref().to_lowercase();
+
+        // Remove the accents and breathings of Greek letters, but keep the
+        // breve of `й` and the diaeresis of `ё` and `ї`, which are composed
+        // with their letter again.
+        let mut is_after_greek_letter = false;
+        let mut unaccented = lowercased
+            .nfd()
+            .filter(|c| {
+                if ('\u{0300}'..='\u{036F}').contains(c) {
+                    return !is_after_greek_letter;
+                }
+                is_after_greek_letter = Self::is_greek(*c);
+                true
+            })
+            .nfc()
+            .collect::<String>();
+        lowercased.zeroize();
+
+        let mut transliterated = String::with_capacity(unaccented.len());
+        for c in unaccented.chars() {
+            let latin = match self {
+                Self::ToLatinVersion1 => Self::to_latin_version1(c),
+            };
+            match latin {
+                Some(latin) => transliterated.push_str(latin),
+                None => transliterated.push(c),
+            }
+        }
+        unaccented.zeroize();
+        transliterated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use insta::assert_snapshot;
+
+    type Sut = Transliteration;
+
+    #[test]
+    fn russian_names() {
+        let sut = Sut::ToLatinVersion1;
+        for (native, latin) in [
+            ("Сергей", "sergei"),
+            ("СЕРГЕЙ", "sergei"),
+            ("Наталья", "natalia"),
+            ("Юлия", "iuliia"),
+            ("Пётр", "petr"),
+            ("Михаил Хабаров", "mikhail khabarov"),
+            ("Щукин", "shchukin"),
+            ("Цветаева", "tsvetaeva"),
+            ("Жуков", "zhukov"),
+        ] {
+            assert_eq!(sut.transliterate(native), latin, "{native}");
+        }
+    }
+
+    #[test]
+    fn decomposed_cyrillic_is_composed() {
+        let sut = Sut::ToLatinVersion1;
+        assert_eq!(
+            sut.transliterate("Сергеи\u{306}"),
+            sut.transliterate("Сергей")
+        );
+        assert_eq!(sut.transliterate("Пе\u{308}тр"), "petr");
+    }
+
+    #[test]
+    fn ukrainian_serbian_and_macedonian_names() {
+        let sut = Sut::ToLatinVersion1;
+        for (native, latin) in [
+            ("Олексій", "oleksii"),
+            ("Їжак", "izhak"),
+            ("Ґалаґан", "galagan"),
+            ("Ђорђе", "djordje"),
+            ("Љубица", "ljubitsa"),
+            ("Ѓорѓи", "gjorgji"),
+        ] {
+            assert_eq!(sut.transliterate(native), latin, "{native}");
+        }
+    }
+
+    #[test]
+    fn greek_names() {
+        let sut = Sut::ToLatinVersion1;
+        for (native, latin) in [
+            ("Αλέξανδρος", "alexandros"),
+            ("ΑΛΕΞΑΝΔΡΟΣ", "alexandros"),
+            ("Γιώργος", "giorgos"),
+            ("Δημήτρης", "dimitris"),
+            ("Θεοφάνης", "theofanis"),
+            ("Ψυχάρης", "psycharis"),
+            ("\u{1F08}\u{3B8}\u{1FC6}\u{3BD}\u{3B1}\u{3B9}", "athinai"),
+        ] {
+            assert_eq!(sut.transliterate(native), latin, "{native}");
+        }
+    }
+
+    #[test]
+    fn latin_is_only_lowercased() {
+        let sut = Sut::ToLatinVersion1;
+        assert_eq!(sut.transliterate("Sergei"), "sergei");
+        assert_eq!(sut.transliterate("D\u{E9}fense"), "d\u{E9}fense");
+        assert_eq!(sut.transliterate("東京"), "東京");
+    }
+
+    #[test]
+    fn table_version1_is_stable() {
+        let sut = Sut::ToLatinVersion1;
+        assert_snapshot!(
+            [
+                "абвгдеёжзийклмнопрстуфхцчшщъыьэюя",
+                "єіїґў",
+                "ђјљњћџѓќѕ",
+                "αβγδεζηθικλμνξοπρσςτυφχψω",
+            ]
+            .map(|alphabet| sut.transliterate(alphabet))
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        let sut = Sut::ToLatinVersion1;
+        let json = serde_json::to_string(&sut).unwrap();
+        assert_eq!(json, r#""ToLatinVersion1""#);
+        assert_eq!(serde_json::from_str::<Sut>(&json).unwrap(), sut);
+    }
+}
@@ -0,0 +1,247 @@
+use crate::prelude::*;
+
+use argon2::{Algorithm, Argon2, Params, Version};
+
+/// A memory-hard stretching of the (trimmed) answer to a security question,
+/// using Argon2id, performed before the answer is expanded into an entropy.
+///
+/// Answers to security questions are low entropy, without stretching an
+/// attacker in possession of a sealed secret can cheaply guess answers
+/// offline. The parameters are serialized as part of the KDF scheme so that
+/// the exact same stretching can be replayed when opening.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct SecurityQuestionsAnswerStretchingArgon2id {
+    /// Memory size in KiB used by Argon2id.
+    pub memory_cost_kib: u32,
+
+    /// Number of passes over the memory.
+    pub iterations: u32,
+
+    /// Degree of parallelism (number of lanes).
+    pub parallelism: u32,
+}
+
+impl SecurityQuestionsAnswerStretchingArgon2id {
+    /// 19 MiB, the OWASP recommended minimum memory cost for Argon2id.
+    pub const DEFAULT_MEMORY_COST_KIB: u32 = 19 * 1024;
+
+    /// OWASP recommended minimum number of iterations for Argon2id when
+    /// using [`Self::DEFAULT_MEMORY_COST_KIB`].
+    pub const DEFAULT_ITERATIONS: u32 = 2;
+
+    /// Single lane, since we stretch one answer at a time.
+    pub const DEFAULT_PARALLELISM: u32 = 1;
+
+    /// 1 GiB, the maximum memory cost accepted, since the parameters are
+    /// read from the sealed secret, which must not be able to make opening
+    /// it exhaust the memory of the device.
+    pub const MAX_MEMORY_COST_KIB: u32 = 1024 * 1024;
+
+    /// The maximum number of iterations accepted, bounding the time it
+    /// takes to stretch an answer.
+    pub const MAX_ITERATIONS: u32 = 64;
+
+    /// The maximum degree of parallelism accepted.
+    pub const MAX_PARALLELISM: u32 = 16;
+
+    pub fn new(
+        memory_cost_kib: u32,
+        iterations: u32,
+        parallelism: u32,
+    ) -> Self {
+        Self {
+            memory_cost_kib,
+            iterations,
+            parallelism,
+        }
+    }
+
+    /// Fails with [`Error::AnswerStretchingFailed`] if any of the parameters
+    /// exceeds its maximum.
+    fn validate(&self) -> Result<()> {
+        for (name, value, max) in [
+            (
+                "memory cost",
+                self.memory_cost_kib,
+                Self::MAX_MEMORY_COST_KIB,
+            ),
+            ("iterations", self.iterations, Self::MAX_ITERATIONS),
+            ("parallelism", self.parallelism, Self::MAX_PARALLELISM),
+        ] {
+            if value > max {
+                return Err(Error::AnswerStretchingFailed {
+                    underlying: format!(
+                        "Argon2id {name} {value} exceeds the maximum {max}"
+                    ),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn argon2(&self) -> Result<Argon2<'static>> {
+        self.validate()?;
+        let params = Params::new(
+            self.memory_cost_kib,
+            self.iterations,
+            self.parallelism,
+            Some(32),
+        )
+        .map_err(|e| Error::AnswerStretchingFailed {
+            underlying: e.to_string(),
+        })?;
+        Ok(Argon2::new(Algorithm::Argon2id, Version::V0x13, params))
+    }
+
+    /// Stretches `input_key_material` - typically the trimmed answer - using
+    /// Argon2id with `salt`, returning 32 bytes of output key material.
+    pub fn stretch(
+        &self,
+        input_key_material: impl AsRef<[u8]>,
+        salt: &Exactly32Bytes,
+    ) -> Result<Exactly32Bytes> {
+        let argon2 = self.argon2()?;
+        let mut okm = [0u8; 32];
+        argon2
+            .hash_password_into(
+                input_key_material.as_ref(),
+                salt.as_ref(),
+                &mut okm,
+            )
+            .map_err(|e| Error::AnswerStretchingFailed {
+                underlying: e.to_string(),
+            })?;
+        let stretched = Exactly32Bytes::from(okm);
+        okm.zeroize();
+        Ok(stretched)
+    }
+}
+
+impl Default for SecurityQuestionsAnswerStretchingArgon2id {
+    fn default() -> Self {
+        Self::new(
+            Self::DEFAULT_MEMORY_COST_KIB,
+            Self::DEFAULT_ITERATIONS,
+            Self::DEFAULT_PARALLELISM,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Sut = SecurityQuestionsAnswerStretchingArgon2id;
+
+    /// Cheap parameters, to keep tests fast.
+    fn cheap() -> Sut {
+        Sut::new(64, 1, 1)
+    }
+
+    #[test]
+    fn stretch_is_deterministic() {
+        let sut = cheap();
+        let salt = Exactly32Bytes::sample_aced();
+        assert_eq!(
+            sut.stretch("foobar", &salt).unwrap(),
+            sut.stretch("foobar", &salt).unwrap()
+        );
+    }
+
+    #[test]
+    fn stretch_known_output() {
+        let sut = cheap();
+        let stretched = sut
+            .stretch("foobar", &Exactly32Bytes::sample_aced())
+            .unwrap();
+        assert_eq!(
+            stretched.to_hex(),
+            "bc1b0db02cab070b18f51cb2e37a5eb71a5120ffc34589aea3a2f049f6e2cf98"
+        );
+    }
+
+    #[test]
+    fn different_salt_different_output() {
+        let sut = cheap();
+        assert_ne!(
+            sut.stretch("foobar", &Exactly32Bytes::sample_aced())
+                .unwrap(),
+            sut.stretch("foobar", &Exactly32Bytes::sample_babe())
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn different_params_different_output() {
+        let salt = Exactly32Bytes::sample_aced();
+        assert_ne!(
+            cheap().stretch("foobar", &salt).unwrap(),
+            Sut::new(64, 2, 1).stretch("foobar", &salt).unwrap()
+        );
+    }
+
+    #[test]
+    fn invalid_params_is_err() {
+        let result =
+            Sut::new(0, 0, 0).stretch("foobar", &Exactly32Bytes::sample_aced());
+        assert!(matches!(result, Err(Error::AnswerStretchingFailed { .. })));
+    }
+
+    #[test]
+    fn max_params_is_ok() {
+        let sut = Sut::new(
+            Sut::MAX_MEMORY_COST_KIB,
+            Sut::MAX_ITERATIONS,
+            Sut::MAX_PARALLELISM,
+        );
+        assert!(sut.argon2().is_ok());
+    }
+
+    #[test]
+    fn params_above_max_is_err() {
+        for sut in [
+            Sut::new(Sut::MAX_MEMORY_COST_KIB + 1, 1, 1),
+            Sut::new(64, Sut::MAX_ITERATIONS + 1, 1),
+            Sut::new(64, 1, Sut::MAX_PARALLELISM + 1),
+        ] {
+            assert!(matches!(
+                sut.stretch("foobar", &Exactly32Bytes::sample_aced()),
+                Err(Error::AnswerStretchingFailed { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn out_of_range_json_is_rejected() {
+        for (json, underlying) in [
+            (
+                r#"{"memory_cost_kib":4294967295,"iterations":1,"parallelism":1}"#,
+                "Argon2id memory cost 4294967295 exceeds the maximum 1048576",
+            ),
+            (
+                r#"{"memory_cost_kib":64,"iterations":4294967295,"parallelism":1}"#,
+                "Argon2id iterations 4294967295 exceeds the maximum 64",
+            ),
+        ] {
+            let sut: Sut = serde_json::from_str(json).unwrap();
+            assert_eq!(
+                sut.stretch("foobar", &Exactly32Bytes::sample_aced()),
+                Err(Error::AnswerStretchingFailed {
+                    underlying: underlying.to_owned()
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        let sut = Sut::default();
+        let json = serde_json::to_string(&sut).unwrap();
+        assert_eq!(
+            json,
+            r#"{"memory_cost_kib":19456,"iterations":2,"parallelism":1}"#
+        );
+        let deserialized: Sut = serde_json::from_str(&json).unwrap();
+        assert_eq!(sut, deserialized);
+    }
+}
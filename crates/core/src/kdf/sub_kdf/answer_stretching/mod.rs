@@ -0,0 +1,3 @@
+mod answer_stretching_argon2id;
+
+pub use answer_stretching_argon2id::*;
@@ -40,7 +40,10 @@ impl SecurityQuestionsKeyExchangeKeysFromQandAsLowerTrimUtf8 {
         answer
     }
 
-    fn bytes_from_answer(&self, answer: impl AsRef<str>) -> Result<Vec<u8>> {
+    fn bytes_from_answer(
+        &self,
+        answer: impl AsRef<str>,
+    ) -> Result<SecretBytes> {
         let answer = answer.as_ref();
         if answer.is_empty() {
             return Err(Error::AnswersToSecurityQuestionsCannotBeEmpty);
@@ -48,7 +51,7 @@ impl SecurityQuestionsKeyExchangeKeysFromQandAsLowerTrimUtf8 {
 
         let trimmed = self.trim_answer(answer);
 
-        Ok(trimmed.as_bytes().to_owned())
+        Ok(SecretBytes::from(trimmed))
     }
 
     fn bytes_from_question(&self, question: impl AsRef<str>) -> Vec<u8> {
@@ -57,13 +60,30 @@ impl SecurityQuestionsKeyExchangeKeysFromQandAsLowerTrimUtf8 {
 }
 
 impl SecurityQuestionsKeyExchangeKeysFromQandAsLowerTrimUtf8 {
-    pub fn derive_entropies_from_question_answer_and_salt(
+    /// Input Key Material: the trimmed answer, the most secret.
+    ///
+    /// Only freeform answers are supported, structured answers result in
+    /// [`Error::AnswerDoesNotMatchQuestionKind`].
+    pub(crate) fn input_key_material(
         &self,
         question_answer_and_salt: &SecurityQuestionAnswerAndSalt,
-    ) -> Result<Exactly32Bytes> {
-        // Input Key Material: the answer, the most secret.
-        let ikm = self.bytes_from_answer(&question_answer_and_salt.answer)?;
+    ) -> Result<SecretBytes> {
+        let answer = question_answer_and_salt.answer.as_freeform().ok_or(
+            Error::AnswerDoesNotMatchQuestionKind {
+                question_id: question_answer_and_salt.question.id,
+            },
+        )?;
+        self.bytes_from_answer(answer)
+    }
 
+    /// Expands `input_key_material` into an entropy using HKDF-SHA256, with
+    /// the salt of `question_answer_and_salt` as salt and its question as
+    /// info.
+    pub(crate) fn expand(
+        &self,
+        question_answer_and_salt: &SecurityQuestionAnswerAndSalt,
+        input_key_material: impl AsRef<[u8]>,
+    ) -> Exactly32Bytes {
         // We use `question` as info so that two same answers give different
         // output for two different questions, silly example might be:
         // Q1: "Name of best childhood teddy" - A1: "Björn"
@@ -74,11 +94,21 @@ impl SecurityQuestionsKeyExchangeKeysFromQandAsLowerTrimUtf8 {
 
         let hkdf = Hkdf::<Sha256>::new(
             Some(question_answer_and_salt.salt.as_ref()),
-            &ikm,
+            input_key_material.as_ref(),
         );
         let mut okm = [0u8; 32];
         hkdf.expand(&info, &mut okm).unwrap();
-        Ok(Exactly32Bytes::from(okm))
+        let entropy = Exactly32Bytes::from(okm);
+        okm.zeroize();
+        entropy
+    }
+
+    pub fn derive_entropies_from_question_answer_and_salt(
+        &self,
+        question_answer_and_salt: &SecurityQuestionAnswerAndSalt,
+    ) -> Result<Exactly32Bytes> {
+        let ikm = self.input_key_material(question_answer_and_salt)?;
+        Ok(self.expand(question_answer_and_salt, ikm))
     }
 }
 
@@ -111,8 +141,7 @@ mod tests {
             .derive_entropies_from_question_answer_and_salt(
                 &SecurityQuestionAnswerAndSalt {
                     question: SecurityQuestion::first_concert(),
-                    answer: "Jean-Michel Jarre, Paris La Défense, 1990"
-                        .to_owned(),
+                    answer: "Jean-Michel Jarre, Paris La Défense, 1990".into(),
                     salt: Exactly32Bytes::sample_aced(),
                 },
             )
@@ -121,7 +150,7 @@ mod tests {
             .derive_entropies_from_question_answer_and_salt(
                 &SecurityQuestionAnswerAndSalt {
                     question: SecurityQuestion::first_concert(),
-                    answer: "Jean-MichelJarre,ParisLaDéfense,1990".to_owned(),
+                    answer: "Jean-MichelJarre,ParisLaDéfense,1990".into(),
                     salt: Exactly32Bytes::sample_aced(),
                 },
             )
@@ -141,4 +170,17 @@ mod tests {
             Error::AnswersToSecurityQuestionsCannotBeEmpty
         );
     }
+
+    #[test]
+    fn structured_answer_is_err() {
+        let qas = SecurityQuestionAnswerAndSalt::sample_structured();
+        let result =
+            Sut::default().derive_entropies_from_question_answer_and_salt(&qas);
+        assert_eq!(
+            result,
+            Err(Error::AnswerDoesNotMatchQuestionKind {
+                question_id: qas.question.id
+            })
+        );
+    }
 }
@@ -0,0 +1,162 @@
+use crate::prelude::*;
+
+use hkdf::Hkdf;
+use sha2::Sha256;
+
+/// A Key Derivation Scheme which lowercases, trims and utf8 encodes answers,
+/// just like [`SecurityQuestionsKeyExchangeKeysFromQandAsLowerTrimUtf8`], but
+/// which uses the `id` and `version` of the question as HKDF info instead of
+/// the question text.
+///
+/// This allows us to fix typos in, or reword, a question without breaking any
+/// secret sealed with it, as long as the meaning of the question is unchanged,
+/// if the meaning changes the `version` of the question MUST be bumped.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct SecurityQuestionsKeyExchangeKeysFromQandAsLowerTrimUtf8ByQuestionId;
+
+impl Default
+    for SecurityQuestionsKeyExchangeKeysFromQandAsLowerTrimUtf8ByQuestionId
+{
+    fn default() -> Self {
+        Self
+    }
+}
+
+impl SecurityQuestionsKeyExchangeKeysFromQandAsLowerTrimUtf8ByQuestionId {
+    /// Domain separation tag prefixing the HKDF info, ensuring that entropies
+    /// derived by this scheme are never equal to HKDF output of any other
+    /// context.
+    pub const DOMAIN_TAG: &'static [u8] =
+        b"svar/security_questions/entropy/lower_trim_utf8_by_question_id/v1";
+
+    /// The HKDF info: `DOMAIN_TAG || id (u16 big-endian) || version (u8)`.
+    pub(crate) fn info_from_question(
+        &self,
+        question: &SecurityQuestion,
+    ) -> Vec<u8> {
+        let mut info = Self::DOMAIN_TAG.to_vec();
+        info.extend_from_slice(&question.id.to_be_bytes());
+        info.push(question.version);
+        info
+    }
+
+    /// Input Key Material: the trimmed answer, the most secret.
+    pub(crate) fn input_key_material(
+        &self,
+        question_answer_and_salt: &SecurityQuestionAnswerAndSalt,
+    ) -> Result<SecretBytes> {
+        SecurityQuestionsKeyExchangeKeysFromQandAsLowerTrimUtf8
+            .input_key_material(question_answer_and_salt)
+    }
+
+    /// Expands `input_key_material` into an entropy using HKDF-SHA256, with
+    /// the salt of `question_answer_and_salt` as salt and the id and version
+    /// of its question as info.
+    pub(crate) fn expand(
+        &self,
+        question_answer_and_salt: &SecurityQuestionAnswerAndSalt,
+        input_key_material: impl AsRef<[u8]>,
+    ) -> Exactly32Bytes {
+        let info = self.info_from_question(&question_answer_and_salt.question);
+
+        let hkdf = Hkdf::<Sha256>::new(
+            Some(question_answer_and_salt.salt.as_ref()),
+            input_key_material.as_ref(),
+        );
+        let mut okm = [0u8; 32];
+        hkdf.expand(&info, &mut okm).unwrap();
+        let entropy = Exactly32Bytes::from(okm);
+        okm.zeroize();
+        entropy
+    }
+
+    pub fn derive_entropies_from_question_answer_and_salt(
+        &self,
+        question_answer_and_salt: &SecurityQuestionAnswerAndSalt,
+    ) -> Result<Exactly32Bytes> {
+        let ikm = self.input_key_material(question_answer_and_salt)?;
+        Ok(self.expand(question_answer_and_salt, ikm))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Sut =
+        SecurityQuestionsKeyExchangeKeysFromQandAsLowerTrimUtf8ByQuestionId;
+
+    fn derive(question: SecurityQuestion) -> Exactly32Bytes {
+        Sut::default()
+            .derive_entropies_from_question_answer_and_salt(
+                &SecurityQuestionAnswerAndSalt {
+                    question,
+                    answer: "Jean-Michel Jarre, Paris La Défense, 1990".into(),
+                    salt: Exactly32Bytes::sample_aced(),
+                },
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn rewording_question_does_not_change_entropy() {
+        let question = SecurityQuestion::first_concert();
+        let mut reworded = question.clone();
+        reworded.question =
+            "What was the first concert you went to?".to_owned();
+        assert_eq!(derive(question), derive(reworded));
+    }
+
+    #[test]
+    fn different_version_changes_entropy() {
+        let question = SecurityQuestion::first_concert();
+        let mut bumped = question.clone();
+        bumped.version += 1;
+        assert_ne!(derive(question), derive(bumped));
+    }
+
+    #[test]
+    fn different_id_changes_entropy() {
+        let question = SecurityQuestion::first_concert();
+        let mut other = question.clone();
+        other.id += 1;
+        assert_ne!(derive(question), derive(other));
+    }
+
+    #[test]
+    fn differs_from_question_text_as_info() {
+        let question = SecurityQuestion::first_concert();
+        let by_text = SecurityQuestionsKeyExchangeKeysFromQandAsLowerTrimUtf8
+            .derive_entropies_from_question_answer_and_salt(
+                &SecurityQuestionAnswerAndSalt {
+                    question: question.clone(),
+                    answer: "Jean-Michel Jarre, Paris La Défense, 1990".into(),
+                    salt: Exactly32Bytes::sample_aced(),
+                },
+            )
+            .unwrap();
+        assert_ne!(derive(question), by_text);
+    }
+
+    #[test]
+    fn info_from_question() {
+        let info = Sut::default()
+            .info_from_question(&SecurityQuestion::first_concert());
+        let mut expected = Sut::DOMAIN_TAG.to_vec();
+        expected.extend_from_slice(&[0x00, 0x02, 0x01]);
+        assert_eq!(info, expected);
+    }
+
+    #[test]
+    fn empty_answer_is_err() {
+        let result = Sut::default()
+            .derive_entropies_from_question_answer_and_salt(
+                &SecurityQuestionAnswerAndSalt {
+                    question: SecurityQuestion::first_concert(),
+                    answer: "".into(),
+                    salt: Exactly32Bytes::sample_aced(),
+                },
+            );
+        assert_eq!(result, Err(Error::AnswersToSecurityQuestionsCannotBeEmpty));
+    }
+}
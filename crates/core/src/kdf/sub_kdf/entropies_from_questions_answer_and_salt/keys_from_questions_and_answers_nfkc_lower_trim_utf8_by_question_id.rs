@@ -0,0 +1,424 @@
+use crate::prelude::*;
+
+use hkdf::Hkdf;
+use sha2::Sha256;
+
+/// A Key Derivation Scheme which Unicode normalizes answers using NFKC before
+/// lowercasing, trimming and utf8 encoding them, optionally folding
+/// diacritics, and which - like
+/// [`SecurityQuestionsKeyExchangeKeysFromQandAsLowerTrimUtf8ByQuestionId`] -
+/// uses the `id` and `version` of the question as HKDF info.
+///
+/// NFKC makes answers typed on different keyboards or platforms equal, e.g.
+/// "Défense" with a precomposed `é` and with an `e` followed by a combining
+/// acute accent, full-width "ＡＢＣ" and "ABC", or the ligature "ﬁ" and "fi".
+///
+/// Folding diacritics additionally makes "Björn" and "Bjorn" equal, by
+/// removing all [combining diacritical marks] after canonical decomposition.
+/// Letters which do not decompose, such as `ø`, `ł`, `æ` or `ß`, are kept
+/// as is, and so are the combining marks of scripts in which they are part
+/// of the letter, e.g. the vowel signs of Devanagari.
+///
+/// [combining diacritical marks]: https://en.wikipedia.org/wiki/Combining_Diacritical_Marks
+///
+/// # Examples
+///
+/// ```
+/// use svar_core::*;
+///
+/// let kdf = SecurityQuestionsKeyExchangeKeysFromQandAsNfkcLowerTrimUtf8ByQuestionId::default();
+/// assert!(kdf.fold_diacritics);
+/// assert_eq!(
+///     kdf.normalize_answer("Paris La De\u{301}fense"),
+///     kdf.normalize_answer("ｐａｒｉｓ ｌａ ｄｅｆｅｎｓｅ")
+/// );
+/// ```
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct SecurityQuestionsKeyExchangeKeysFromQandAsNfkcLowerTrimUtf8ByQuestionId
+{
+    /// If `true` diacritics are removed from answers, so that e.g. "Björn"
+    /// and "Bjorn" derive the same entropy.
+    pub fold_diacritics: bool,
+}
+
+impl Default
+    for SecurityQuestionsKeyExchangeKeysFromQandAsNfkcLowerTrimUtf8ByQuestionId
+{
+    fn default() -> Self {
+        Self::new(true)
+    }
+}
+
+impl SecurityQuestionsKeyExchangeKeysFromQandAsNfkcLowerTrimUtf8ByQuestionId {
+    /// Domain separation tag prefixing the HKDF info, ensuring that entropies
+    /// derived by this scheme are never equal to HKDF output of any other
+    /// context.
+    pub const DOMAIN_TAG: &'static [u8] =
+        b"svar/security_questions/entropy/nfkc_lower_trim_utf8_by_question_id/v1";
+
+    pub fn new(fold_diacritics: bool) -> Self {
+        Self { fold_diacritics }
+    }
+
+    /// Normalizes `answer` using NFKC, lowercases it, folds its diacritics
+    /// if [`fold_diacritics`](Self::fold_diacritics) is set, and removes
+    /// the characters in `SECURITY_QUESTIONS_TRIMMED_CHARS`, i.e. applies
+    /// [`AnswerNormalization::nfkc_lower_trim`].
+    ///
+    /// Intermediate normalizations of the answer are zeroized.
+    pub fn normalize_answer(&self, answer: impl AsRef<str>) -> String {
+        AnswerNormalization::nfkc_lower_trim(self.fold_diacritics)
+            .normalize(answer)
+    }
+
+    fn bytes_from_answer(
+        &self,
+        answer: impl AsRef<str>,
+    ) -> Result<SecretBytes> {
+        let answer = answer.as_ref();
+        if answer.is_empty() {
+            return Err(Error::AnswersToSecurityQuestionsCannotBeEmpty);
+        }
+
+        let normalized = self.normalize_answer(answer);
+
+        Ok(SecretBytes::from(normalized))
+    }
+
+    /// The HKDF info:
+    /// `DOMAIN_TAG || id (u16 big-endian) || version (u8) || fold (u8)`,
+    /// where `fold` is `1` if diacritics are folded, else `0`.
+    pub(crate) fn info_from_question(
+        &self,
+        question: &SecurityQuestion,
+    ) -> Vec<u8> {
+        let mut info = Self::DOMAIN_TAG.to_vec();
+        info.extend_from_slice(&question.id.to_be_bytes());
+        info.push(question.version);
+        info.push(u8::from(self.fold_diacritics));
+        info
+    }
+
+    /// Input Key Material: the normalized answer, the most secret.
+    ///
+    /// Only freeform answers are supported, structured answers result in
+    /// [`Error::AnswerDoesNotMatchQuestionKind`].
+    pub(crate) fn input_key_material(
+        &self,
+        question_answer_and_salt: &SecurityQuestionAnswerAndSalt,
+    ) -> Result<SecretBytes> {
+        let answer = question_answer_and_salt.answer.as_freeform().ok_or(
+            Error::AnswerDoesNotMatchQuestionKind {
+                question_id: question_answer_and_salt.question.id,
+            },
+        )?;
+        self.bytes_from_answer(answer)
+    }
+
+    /// Expands `input_key_material` into an entropy using HKDF-SHA256, with
+    /// the salt of `question_answer_and_salt` as salt and the id and version
+    /// of its question, and whether diacritics are folded, as info.
+    pub(crate) fn expand(
+        &self,
+        question_answer_and_salt: &SecurityQuestionAnswerAndSalt,
+        input_key_material: impl AsRef<[u8]>,
+    ) -> Exactly32Bytes {
+        let info = self.info_from_question(&question_answer_and_salt.question);
+
+        let hkdf = Hkdf::<Sha256>::new(
+            Some(question_answer_and_salt.salt.as_ref()),
+            input_key_material.as_ref(),
+        );
+        let mut okm = [0u8; 32];
+        hkdf.expand(&info, &mut okm).unwrap();
+        let entropy = Exactly32Bytes::from(okm);
+        okm.zeroize();
+        entropy
+    }
+
+    pub fn derive_entropies_from_question_answer_and_salt(
+        &self,
+        question_answer_and_salt: &SecurityQuestionAnswerAndSalt,
+    ) -> Result<Exactly32Bytes> {
+        let ikm = self.input_key_material(question_answer_and_salt)?;
+        Ok(self.expand(question_answer_and_salt, ikm))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Sut =
+        SecurityQuestionsKeyExchangeKeysFromQandAsNfkcLowerTrimUtf8ByQuestionId;
+
+    fn folding() -> Sut {
+        Sut::new(true)
+    }
+
+    fn non_folding() -> Sut {
+        Sut::new(false)
+    }
+
+    fn derive(sut: &Sut, answer: &str) -> Exactly32Bytes {
+        sut.derive_entropies_from_question_answer_and_salt(
+            &SecurityQuestionAnswerAndSalt {
+                question: SecurityQuestion::first_concert(),
+                answer: answer.into(),
+                salt: Exactly32Bytes::sample_aced(),
+            },
+        )
+        .unwrap()
+    }
+
+    /// Asserts that each pair normalizes to the same answer, both with and
+    /// without folding diacritics.
+    fn assert_same_regardless_of_folding(pairs: &[(&str, &str)]) {
+        for sut in [folding(), non_folding()] {
+            for (lhs, rhs) in pairs {
+                assert_eq!(
+                    sut.normalize_answer(lhs),
+                    sut.normalize_answer(rhs),
+                    "{lhs:?} vs {rhs:?}, fold: {}",
+                    sut.fold_diacritics
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn default_folds_diacritics() {
+        assert_eq!(Sut::default(), folding());
+    }
+
+    #[test]
+    fn composed_and_decomposed_are_equal() {
+        assert_same_regardless_of_folding(&[
+            ("D\u{E9}fense", "De\u{301}fense"),
+            ("Bj\u{F6}rn", "Bjo\u{308}rn"),
+            ("Fran\u{E7}ois", "Franc\u{327}ois"),
+            ("\u{C5}ngstr\u{F6}m", "A\u{30A}ngstro\u{308}m"),
+            // Angstrom sign and Kelvin sign are canonically equivalent to
+            // the letters they look like
+            ("\u{212B}", "\u{C5}"),
+            ("\u{212A}", "K"),
+            // Multiple combining marks in non canonical order
+            ("a\u{323}\u{302}", "a\u{302}\u{323}"),
+            ("Vi\u{1EC7}t", "Vie\u{323}\u{302}t"),
+            // Hangul syllable and its conjoining jamo
+            ("\u{D55C}", "\u{1112}\u{1161}\u{11AB}"),
+        ]);
+    }
+
+    #[test]
+    fn composed_and_decomposed_derive_same_entropy() {
+        for sut in [folding(), non_folding()] {
+            assert_eq!(
+                derive(&sut, "Jean-Michel Jarre, Paris La D\u{E9}fense, 1990"),
+                derive(
+                    &sut,
+                    "Jean-Michel Jarre, Paris La De\u{301}fense, 1990"
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn full_width_is_equal_to_half_width() {
+        assert_same_regardless_of_folding(&[
+            ("\u{FF2A}\u{FF45}\u{FF41}\u{FF4E}", "Jean"),
+            ("\u{FF11}\u{FF19}\u{FF19}\u{FF10}", "1990"),
+            // Full width comma, full stop and ideographic space are trimmed
+            ("Paris\u{FF0C}\u{3000}1990\u{FF0E}", "Paris, 1990."),
+            // Full width apostrophe
+            ("O\u{FF07}Brien", "OBrien"),
+            // Half width katakana and full width katakana
+            ("\u{FF83}\u{FF9E}\u{FF8C}\u{FF9E}", "\u{30C7}\u{30D6}"),
+        ]);
+    }
+
+    #[test]
+    fn ligatures_are_expanded() {
+        assert_same_regardless_of_folding(&[
+            ("\u{FB01}nal", "final"),
+            ("\u{FB02}ower", "flower"),
+            ("e\u{FB00}ort", "effort"),
+            ("\u{FB03}x", "ffix"),
+            ("\u{FB04}", "ffl"),
+            ("\u{FB06}", "st"),
+            ("\u{132}sselmeer", "IJsselmeer"),
+            ("\u{1C9}", "lj"),
+        ]);
+    }
+
+    #[test]
+    fn ligature_with_caron_is_folded() {
+        // DŽ (U+01C4) decomposes into D and Ž
+        assert_eq!(folding().normalize_answer("\u{1C4}"), "dz");
+        assert_eq!(non_folding().normalize_answer("\u{1C4}"), "d\u{17E}");
+    }
+
+    #[test]
+    fn compatibility_characters_are_decomposed() {
+        assert_same_regardless_of_folding(&[
+            ("x\u{B2}", "x2"),
+            ("H\u{2082}O", "H2O"),
+            ("\u{216B}", "XII"),
+            ("\u{2460}", "1"),
+            ("\u{2122}", "tm"),
+            ("\u{BD}", "1\u{2044}2"),
+            ("\u{3392}", "mhz"),
+        ]);
+    }
+
+    #[test]
+    fn folding_removes_diacritics() {
+        let sut = folding();
+        for (with, without) in [
+            ("Bj\u{F6}rn", "bjorn"),
+            ("D\u{E9}fense", "defense"),
+            ("Fran\u{E7}ois", "francois"),
+            ("Cr\u{E8}me br\u{FB}l\u{E9}e", "cremebrulee"),
+            ("Vi\u{1EC7}t Nam", "vietnam"),
+            ("\u{130}stanbul", "istanbul"),
+            (
+                "\u{1F08}\u{3B8}\u{1FC6}\u{3BD}\u{3B1}\u{3B9}",
+                "\u{3B1}\u{3B8}\u{3B7}\u{3BD}\u{3B1}\u{3B9}",
+            ),
+            ("a\u{20DD}", "a"),
+        ] {
+            assert_eq!(sut.normalize_answer(with), without, "{with:?}");
+        }
+        assert_eq!(derive(&sut, "Bj\u{F6}rn"), derive(&sut, "Bjorn"));
+    }
+
+    #[test]
+    fn non_folding_keeps_diacritics() {
+        let sut = non_folding();
+        assert_eq!(sut.normalize_answer("Bj\u{F6}rn"), "bj\u{F6}rn");
+        assert_eq!(sut.normalize_answer("Bjo\u{308}rn"), "bj\u{F6}rn");
+        assert_ne!(derive(&sut, "Bj\u{F6}rn"), derive(&sut, "Bjorn"));
+    }
+
+    #[test]
+    fn folding_keeps_letters_which_do_not_decompose() {
+        let sut = folding();
+        for answer in ["\u{F8}", "\u{142}", "\u{E6}", "\u{DF}", "\u{111}"] {
+            assert_eq!(sut.normalize_answer(answer), answer);
+        }
+    }
+
+    #[test]
+    fn folding_keeps_combining_marks_of_other_scripts() {
+        let sut = folding();
+        for answer in [
+            // Devanagari, vowel signs and virama are combining marks
+            "\u{939}\u{93F}\u{928}\u{94D}\u{926}\u{940}",
+            // Thai, with a tone mark
+            "\u{E44}\u{E17}\u{E22}",
+            "\u{E19}\u{E49}\u{E32}",
+            // Japanese, dakuten composes with the kana
+            "\u{6771}\u{4EAC}",
+            "\u{30C7}\u{30D6}",
+        ] {
+            assert_eq!(sut.normalize_answer(answer), answer);
+        }
+    }
+
+    #[test]
+    fn lowercases_and_trims() {
+        assert_same_regardless_of_folding(&[
+            (
+                "FoO\nB.a\tR ' ! FiZz ? \u{2018} B \u{2019} u\u{FF07}ZZ",
+                "foobarfizzbuzz",
+            ),
+            (
+                "\u{3A3}\u{39F}\u{3A6}\u{399}\u{391}",
+                "\u{3C3}\u{3BF}\u{3C6}\u{3B9}\u{3B1}",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn normalization_is_idempotent() {
+        for sut in [folding(), non_folding()] {
+            for answer in [
+                "Jean-Michel Jarre, Paris La D\u{E9}fense, 1990",
+                "\u{130}stanbul",
+                "\u{FB01}\u{1C4}\u{216B}",
+                "\u{FF83}\u{FF9E}\u{FF8C}\u{FF9E}",
+            ] {
+                let once = sut.normalize_answer(answer);
+                assert_eq!(sut.normalize_answer(&once), once);
+            }
+        }
+    }
+
+    #[test]
+    fn folding_changes_entropy() {
+        assert_ne!(
+            derive(&folding(), "bjorn"),
+            derive(&non_folding(), "bjorn")
+        );
+    }
+
+    #[test]
+    fn differs_from_lower_trim_utf8_by_question_id() {
+        let qas = SecurityQuestionAnswerAndSalt::sample();
+        assert_ne!(
+            Sut::default()
+                .derive_entropies_from_question_answer_and_salt(&qas)
+                .unwrap(),
+            SecurityQuestionsKeyExchangeKeysFromQandAsLowerTrimUtf8ByQuestionId
+                .derive_entropies_from_question_answer_and_salt(&qas)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn info_from_question() {
+        let question = SecurityQuestion::first_concert();
+        let mut expected = Sut::DOMAIN_TAG.to_vec();
+        expected.extend_from_slice(&question.id.to_be_bytes());
+        expected.push(question.version);
+        expected.push(1);
+        assert_eq!(folding().info_from_question(&question), expected);
+    }
+
+    #[test]
+    fn empty_answer_is_err() {
+        assert_eq!(
+            Sut::default().derive_entropies_from_question_answer_and_salt(
+                &SecurityQuestionAnswerAndSalt {
+                    question: SecurityQuestion::first_concert(),
+                    answer: "".into(),
+                    salt: Exactly32Bytes::sample_aced(),
+                },
+            ),
+            Err(Error::AnswersToSecurityQuestionsCannotBeEmpty)
+        );
+    }
+
+    #[test]
+    fn structured_answer_is_err() {
+        let qas = SecurityQuestionAnswerAndSalt::sample_structured();
+        assert_eq!(
+            Sut::default().derive_entropies_from_question_answer_and_salt(&qas),
+            Err(Error::AnswerDoesNotMatchQuestionKind {
+                question_id: qas.question.id
+            })
+        );
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        for sut in [folding(), non_folding()] {
+            let json = serde_json::to_string(&sut).unwrap();
+            assert_eq!(serde_json::from_str::<Sut>(&json).unwrap(), sut);
+        }
+        assert_eq!(
+            serde_json::to_string(&folding()).unwrap(),
+            r#"{"fold_diacritics":true}"#
+        );
+    }
+}
@@ -0,0 +1,250 @@
+use crate::prelude::*;
+
+use hkdf::Hkdf;
+use sha2::Sha256;
+
+/// A Key Derivation Scheme which normalizes answers using a configurable
+/// [`AnswerNormalization`] before utf8 encoding them, and which - like
+/// [`SecurityQuestionsKeyExchangeKeysFromQandAsLowerTrimUtf8ByQuestionId`] -
+/// uses the `id` and `version` of the question as HKDF info.
+///
+/// The normalization is serialized as part of the KDF scheme of the sealed
+/// secret, so the exact rules used when sealing are replayed when opening,
+/// e.g. a product can choose to also strip `-`, collapse whitespace or drop
+/// articles.
+///
+/// # Examples
+///
+/// ```
+/// use svar_core::*;
+///
+/// let kdf = SecurityQuestionsKeyExchangeKeysFromQandAsNormalizedByQuestionId::new(
+///     AnswerNormalization::new([
+///         AnswerNormalizationStep::CaseFold,
+///         AnswerNormalizationStep::StripChars { chars: "-,".to_owned() },
+///         AnswerNormalizationStep::sample_other(), // removes "the", "a" and "an"
+///         AnswerNormalizationStep::CollapseNumbers,
+///     ]),
+/// );
+/// assert_eq!(
+///     kdf.normalization.normalize("The Jean-Michel Jarre, 1990"),
+///     kdf.normalization.normalize("jeanmichel  jarre 01990")
+/// );
+/// ```
+#[derive(
+    Serialize, Deserialize, Clone, PartialEq, Eq, Hash, Debug, Default,
+)]
+pub struct SecurityQuestionsKeyExchangeKeysFromQandAsNormalizedByQuestionId {
+    /// The rules applied to each answer before it is used as input key
+    /// material.
+    pub normalization: AnswerNormalization,
+}
+
+impl SecurityQuestionsKeyExchangeKeysFromQandAsNormalizedByQuestionId {
+    /// Domain separation tag prefixing the HKDF info, ensuring that entropies
+    /// derived by this scheme are never equal to HKDF output of any other
+    /// context.
+    pub const DOMAIN_TAG: &'static [u8] =
+        b"svar/security_questions/entropy/normalized_by_question_id/v1";
+
+    pub fn new(normalization: AnswerNormalization) -> Self {
+        Self { normalization }
+    }
+
+    /// Normalizes `answer`, failing if either it or the normalized answer is
+    /// empty, since an answer consisting only of e.g. stripped characters
+    /// has no entropy.
+    fn bytes_from_answer(
+        &self,
+        answer: impl AsRef<str>,
+    ) -> Result<SecretBytes> {
+        let answer = answer.as_ref();
+        if answer.is_empty() {
+            return Err(Error::AnswersToSecurityQuestionsCannotBeEmpty);
+        }
+
+        let normalized =
+            SecretBytes::from(self.normalization.normalize(answer));
+        if normalized.is_empty() {
+            return Err(Error::AnswersToSecurityQuestionsCannotBeEmpty);
+        }
+
+        Ok(normalized)
+    }
+
+    /// The HKDF info: `DOMAIN_TAG || id (u16 big-endian) || version (u8)`.
+    ///
+    /// The normalization is not part of the info, it only affects the input
+    /// key material.
+    pub(crate) fn info_from_question(
+        &self,
+        question: &SecurityQuestion,
+    ) -> Vec<u8> {
+        let mut info = Self::DOMAIN_TAG.to_vec();
+        info.extend_from_slice(&question.id.to_be_bytes());
+        info.push(question.version);
+        info
+    }
+
+    /// Input Key Material: the normalized answer, the most secret.
+    ///
+    /// Only freeform answers are supported, structured answers result in
+    /// [`Error::AnswerDoesNotMatchQuestionKind`].
+    pub(crate) fn input_key_material(
+        &self,
+        question_answer_and_salt: &SecurityQuestionAnswerAndSalt,
+    ) -> Result<SecretBytes> {
+        let answer = question_answer_and_salt.answer.as_freeform().ok_or(
+            Error::AnswerDoesNotMatchQuestionKind {
+                question_id: question_answer_and_salt.question.id,
+            },
+        )?;
+        self.bytes_from_answer(answer)
+    }
+
+    /// Expands `input_key_material` into an entropy using HKDF-SHA256, with
+    /// the salt of `question_answer_and_salt` as salt and the id and version
+    /// of its question as info.
+    pub(crate) fn expand(
+        &self,
+        question_answer_and_salt: &SecurityQuestionAnswerAndSalt,
+        input_key_material: impl AsRef<[u8]>,
+    ) -> Exactly32Bytes {
+        let info = self.info_from_question(&question_answer_and_salt.question);
+
+        let hkdf = Hkdf::<Sha256>::new(
+            Some(question_answer_and_salt.salt.as_ref()),
+            input_key_material.as_ref(),
+        );
+        let mut okm = [0u8; 32];
+        hkdf.expand(&info, &mut okm).unwrap();
+        let entropy = Exactly32Bytes::from(okm);
+        okm.zeroize();
+        entropy
+    }
+
+    pub fn derive_entropies_from_question_answer_and_salt(
+        &self,
+        question_answer_and_salt: &SecurityQuestionAnswerAndSalt,
+    ) -> Result<Exactly32Bytes> {
+        let ikm = self.input_key_material(question_answer_and_salt)?;
+        Ok(self.expand(question_answer_and_salt, ikm))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Sut = SecurityQuestionsKeyExchangeKeysFromQandAsNormalizedByQuestionId;
+
+    fn strip_dash() -> Sut {
+        Sut::new(AnswerNormalization::new([
+            AnswerNormalizationStep::CaseFold,
+            AnswerNormalizationStep::StripChars {
+                chars: " -".to_owned(),
+            },
+        ]))
+    }
+
+    fn derive(sut: &Sut, answer: &str) -> Result<Exactly32Bytes> {
+        sut.derive_entropies_from_question_answer_and_salt(
+            &SecurityQuestionAnswerAndSalt {
+                question: SecurityQuestion::first_concert(),
+                answer: answer.into(),
+                salt: Exactly32Bytes::sample_aced(),
+            },
+        )
+    }
+
+    #[test]
+    fn normalization_is_applied() {
+        let sut = strip_dash();
+        assert_eq!(
+            derive(&sut, "Jean-Michel Jarre").unwrap(),
+            derive(&sut, "jean michel jarre").unwrap()
+        );
+        assert_ne!(
+            derive(&Sut::default(), "Jean-Michel Jarre").unwrap(),
+            derive(&Sut::default(), "jean michel jarre").unwrap()
+        );
+    }
+
+    #[test]
+    fn locale_case_fold_is_applied() {
+        let sut = Sut::new(AnswerNormalization::locale_lower_trim(
+            AnswerLocale::Turkish,
+        ));
+        assert_eq!(
+            derive(&sut, "Tark\u{130}n, \u{130}ZM\u{130}R").unwrap(),
+            derive(&sut, "Tarkin izmir").unwrap()
+        );
+    }
+
+    #[test]
+    fn default_has_same_ikm_as_lower_trim_utf8_by_question_id() {
+        let qas = SecurityQuestionAnswerAndSalt::sample();
+        assert_eq!(
+            Sut::default().input_key_material(&qas).unwrap(),
+            SecurityQuestionsKeyExchangeKeysFromQandAsLowerTrimUtf8ByQuestionId
+                .input_key_material(&qas)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn differs_from_lower_trim_utf8_by_question_id() {
+        let qas = SecurityQuestionAnswerAndSalt::sample();
+        assert_ne!(
+            Sut::default()
+                .derive_entropies_from_question_answer_and_salt(&qas)
+                .unwrap(),
+            SecurityQuestionsKeyExchangeKeysFromQandAsLowerTrimUtf8ByQuestionId
+                .derive_entropies_from_question_answer_and_salt(&qas)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn info_from_question() {
+        let question = SecurityQuestion::first_concert();
+        let mut expected = Sut::DOMAIN_TAG.to_vec();
+        expected.extend_from_slice(&question.id.to_be_bytes());
+        expected.push(question.version);
+        assert_eq!(strip_dash().info_from_question(&question), expected);
+    }
+
+    #[test]
+    fn empty_answer_is_err() {
+        assert_eq!(
+            derive(&strip_dash(), ""),
+            Err(Error::AnswersToSecurityQuestionsCannotBeEmpty)
+        );
+    }
+
+    #[test]
+    fn answer_empty_after_normalization_is_err() {
+        assert_eq!(
+            derive(&strip_dash(), " - -"),
+            Err(Error::AnswersToSecurityQuestionsCannotBeEmpty)
+        );
+    }
+
+    #[test]
+    fn structured_answer_is_err() {
+        let qas = SecurityQuestionAnswerAndSalt::sample_structured();
+        assert_eq!(
+            Sut::default().derive_entropies_from_question_answer_and_salt(&qas),
+            Err(Error::AnswerDoesNotMatchQuestionKind {
+                question_id: qas.question.id
+            })
+        );
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        let sut = Sut::new(AnswerNormalization::sample_other());
+        let json = serde_json::to_string(&sut).unwrap();
+        assert_eq!(serde_json::from_str::<Sut>(&json).unwrap(), sut);
+    }
+}
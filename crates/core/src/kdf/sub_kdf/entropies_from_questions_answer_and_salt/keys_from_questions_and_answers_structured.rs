@@ -0,0 +1,251 @@
+use crate::prelude::*;
+
+use hkdf::Hkdf;
+use sha2::Sha256;
+
+/// A Key Derivation Scheme for answers to
+/// [`SecurityQuestionKind::Structured`] questions, which encodes the
+/// selections - indices into the datasets referenced by the question - as
+/// big-endian `u16`s.
+///
+/// Uses the `id` and `version` of the question, and the `id` and `version` of
+/// each referenced dataset, as HKDF info, so that the same indices into
+/// different (versions of) datasets result in different entropies.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct SecurityQuestionsKeyExchangeKeysFromQandAsStructured;
+
+impl Default for SecurityQuestionsKeyExchangeKeysFromQandAsStructured {
+    fn default() -> Self {
+        Self
+    }
+}
+
+impl SecurityQuestionsKeyExchangeKeysFromQandAsStructured {
+    /// Domain separation tag prefixing the HKDF info, ensuring that entropies
+    /// derived by this scheme are never equal to HKDF output of any other
+    /// context.
+    pub const DOMAIN_TAG: &'static [u8] =
+        b"svar/security_questions/entropy/structured/v1";
+
+    /// The datasets referenced by the question of `question_answer_and_salt`
+    /// and the selections of its answer, validated to be of equal length.
+    fn datasets_and_selections<'a>(
+        &self,
+        question_answer_and_salt: &'a SecurityQuestionAnswerAndSalt,
+    ) -> Result<(&'a [AnswerDatasetReference], &'a [u16])> {
+        let question = &question_answer_and_salt.question;
+        let kind_mismatch = Error::AnswerDoesNotMatchQuestionKind {
+            question_id: question.id,
+        };
+        let SecurityQuestionKind::Structured { datasets } = &question.kind
+        else {
+            return Err(kind_mismatch);
+        };
+        let selections = question_answer_and_salt
+            .answer
+            .as_structured()
+            .ok_or(kind_mismatch)?;
+
+        if selections.is_empty() {
+            return Err(Error::AnswersToSecurityQuestionsCannotBeEmpty);
+        }
+        if selections.len() != datasets.len() {
+            return Err(Error::InvalidStructuredAnswerSelectionCount {
+                expected: datasets.len(),
+                found: selections.len(),
+            });
+        }
+        Ok((datasets, selections))
+    }
+
+    /// The HKDF info:
+    /// `DOMAIN_TAG || question id (u16 BE) || question version (u8)` followed
+    /// by `dataset id (u16 BE) || dataset version (u8)` for each dataset.
+    fn info(
+        &self,
+        question: &SecurityQuestion,
+        datasets: &[AnswerDatasetReference],
+    ) -> Vec<u8> {
+        let mut info = Self::DOMAIN_TAG.to_vec();
+        info.extend_from_slice(&question.id.to_be_bytes());
+        info.push(question.version);
+        for dataset in datasets {
+            info.extend_from_slice(&dataset.id.to_be_bytes());
+            info.push(dataset.version);
+        }
+        info
+    }
+
+    /// Input Key Material: the selections, each encoded as a big-endian
+    /// `u16`.
+    pub(crate) fn input_key_material(
+        &self,
+        question_answer_and_salt: &SecurityQuestionAnswerAndSalt,
+    ) -> Result<SecretBytes> {
+        let (_, selections) =
+            self.datasets_and_selections(question_answer_and_salt)?;
+        Ok(SecretBytes::from(
+            selections
+                .iter()
+                .flat_map(|selection| selection.to_be_bytes())
+                .collect_vec(),
+        ))
+    }
+
+    /// Expands `input_key_material` into an entropy using HKDF-SHA256, with
+    /// the salt of `question_answer_and_salt` as salt and the question and
+    /// dataset ids and versions as info.
+    pub(crate) fn expand(
+        &self,
+        question_answer_and_salt: &SecurityQuestionAnswerAndSalt,
+        input_key_material: impl AsRef<[u8]>,
+    ) -> Result<Exactly32Bytes> {
+        let (datasets, _) =
+            self.datasets_and_selections(question_answer_and_salt)?;
+        let info = self.info(&question_answer_and_salt.question, datasets);
+
+        let hkdf = Hkdf::<Sha256>::new(
+            Some(question_answer_and_salt.salt.as_ref()),
+            input_key_material.as_ref(),
+        );
+        let mut okm = [0u8; 32];
+        hkdf.expand(&info, &mut okm).unwrap();
+        let entropy = Exactly32Bytes::from(okm);
+        okm.zeroize();
+        Ok(entropy)
+    }
+
+    pub fn derive_entropies_from_question_answer_and_salt(
+        &self,
+        question_answer_and_salt: &SecurityQuestionAnswerAndSalt,
+    ) -> Result<Exactly32Bytes> {
+        let ikm = self.input_key_material(question_answer_and_salt)?;
+        self.expand(question_answer_and_salt, ikm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Sut = SecurityQuestionsKeyExchangeKeysFromQandAsStructured;
+
+    fn derive(qas: &SecurityQuestionAnswerAndSalt) -> Result<Exactly32Bytes> {
+        Sut::default().derive_entropies_from_question_answer_and_salt(qas)
+    }
+
+    #[test]
+    fn input_key_material_is_big_endian_u16s() {
+        let qas = SecurityQuestionAnswerAndSalt {
+            answer: SecurityQuestionAnswer::Structured(vec![0x0102, 0xfffe]),
+            ..SecurityQuestionAnswerAndSalt::sample_structured()
+        };
+        assert_eq!(
+            Sut::default().input_key_material(&qas).unwrap().as_ref(),
+            [0x01, 0x02, 0xff, 0xfe]
+        );
+    }
+
+    #[test]
+    fn info_binds_question_and_datasets() {
+        let question = SecurityQuestion::sample_structured();
+        let info = Sut::default().info(
+            &question,
+            &[
+                AnswerDatasetReference::sample(),
+                AnswerDatasetReference::sample_other(),
+            ],
+        );
+        let mut expected = Sut::DOMAIN_TAG.to_vec();
+        expected.extend_from_slice(&[
+            0x00, 0x64, 0x01, // question 100 v1
+            0x00, 0x01, 0x01, // dataset 1 v1
+            0x00, 0x02, 0x01, // dataset 2 v1
+        ]);
+        assert_eq!(info, expected);
+    }
+
+    #[test]
+    fn deterministic() {
+        let qas = SecurityQuestionAnswerAndSalt::sample_structured();
+        assert_eq!(derive(&qas).unwrap(), derive(&qas).unwrap());
+    }
+
+    #[test]
+    fn different_selections_different_entropy() {
+        let qas = SecurityQuestionAnswerAndSalt::sample_structured();
+        let other = SecurityQuestionAnswerAndSalt {
+            answer: SecurityQuestionAnswer::Structured(vec![42, 74]),
+            ..qas.clone()
+        };
+        assert_ne!(derive(&qas).unwrap(), derive(&other).unwrap());
+    }
+
+    #[test]
+    fn different_dataset_version_different_entropy() {
+        let qas = SecurityQuestionAnswerAndSalt::sample_structured();
+        let mut other = qas.clone();
+        other.question.kind = SecurityQuestionKind::Structured {
+            datasets: vec![
+                AnswerDatasetReference::new(1, 2),
+                AnswerDatasetReference::sample_other(),
+            ],
+        };
+        assert_ne!(derive(&qas).unwrap(), derive(&other).unwrap());
+    }
+
+    #[test]
+    fn freeform_answer_is_err() {
+        let qas = SecurityQuestionAnswerAndSalt {
+            answer: "London, 1973".into(),
+            ..SecurityQuestionAnswerAndSalt::sample_structured()
+        };
+        assert_eq!(
+            derive(&qas),
+            Err(Error::AnswerDoesNotMatchQuestionKind {
+                question_id: qas.question.id
+            })
+        );
+    }
+
+    #[test]
+    fn freeform_question_is_err() {
+        let qas = SecurityQuestionAnswerAndSalt {
+            answer: SecurityQuestionAnswer::Structured(vec![42]),
+            ..SecurityQuestionAnswerAndSalt::sample()
+        };
+        assert_eq!(
+            derive(&qas),
+            Err(Error::AnswerDoesNotMatchQuestionKind {
+                question_id: qas.question.id
+            })
+        );
+    }
+
+    #[test]
+    fn wrong_selection_count_is_err() {
+        let qas = SecurityQuestionAnswerAndSalt {
+            answer: SecurityQuestionAnswer::Structured(vec![42]),
+            ..SecurityQuestionAnswerAndSalt::sample_structured()
+        };
+        assert_eq!(
+            derive(&qas),
+            Err(Error::InvalidStructuredAnswerSelectionCount {
+                expected: 2,
+                found: 1
+            })
+        );
+    }
+
+    #[test]
+    fn empty_selections_is_err() {
+        let qas = SecurityQuestionAnswerAndSalt {
+            answer: SecurityQuestionAnswer::Structured(vec![]),
+            ..SecurityQuestionAnswerAndSalt::sample_structured()
+        };
+        assert_eq!(
+            derive(&qas),
+            Err(Error::AnswersToSecurityQuestionsCannotBeEmpty)
+        );
+    }
+}
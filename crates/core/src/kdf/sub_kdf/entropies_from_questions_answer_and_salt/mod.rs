@@ -1,6 +1,16 @@
 mod keys_from_questions_and_answers_lower_trim_utf8;
+mod keys_from_questions_and_answers_lower_trim_utf8_by_question_id;
+mod keys_from_questions_and_answers_nfkc_lower_trim_utf8_by_question_id;
+mod keys_from_questions_and_answers_normalized_by_question_id;
+mod keys_from_questions_and_answers_structured;
+mod security_questions_entropies_kdf;
 
 #[cfg(test)]
 mod test_lower_trim_utf8;
 
 pub use keys_from_questions_and_answers_lower_trim_utf8::*;
+pub use keys_from_questions_and_answers_lower_trim_utf8_by_question_id::*;
+pub use keys_from_questions_and_answers_nfkc_lower_trim_utf8_by_question_id::*;
+pub use keys_from_questions_and_answers_normalized_by_question_id::*;
+pub use keys_from_questions_and_answers_structured::*;
+pub use security_questions_entropies_kdf::*;
@@ -0,0 +1,319 @@
+use crate::prelude::*;
+
+/// The "sub-KDF" used to derive an entropy from the answer to a security
+/// question, its salt and the question itself.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum SecurityQuestionsEntropiesKdf {
+    /// Uses the question text as HKDF info, as used by
+    /// [`SecurityQuestionsKDFSchemeVersion1`].
+    LowerTrimUtf8(SecurityQuestionsKeyExchangeKeysFromQandAsLowerTrimUtf8),
+
+    /// Uses a domain tag and the id and version of the question as HKDF info,
+    /// so that questions can be reworded without breaking sealed secrets.
+    LowerTrimUtf8ByQuestionId(
+        SecurityQuestionsKeyExchangeKeysFromQandAsLowerTrimUtf8ByQuestionId,
+    ),
+
+    /// Like [`LowerTrimUtf8ByQuestionId`](Self::LowerTrimUtf8ByQuestionId)
+    /// but Unicode normalizes answers using NFKC, optionally folding
+    /// diacritics, so that e.g. "Défense" typed with a combining accent and
+    /// with a precomposed `é` derive the same entropy.
+    NfkcLowerTrimUtf8ByQuestionId(
+        SecurityQuestionsKeyExchangeKeysFromQandAsNfkcLowerTrimUtf8ByQuestionId,
+    ),
+
+    /// Like [`LowerTrimUtf8ByQuestionId`](Self::LowerTrimUtf8ByQuestionId)
+    /// but normalizes answers using a configurable [`AnswerNormalization`],
+    /// which is stored in the scheme so it is replayed when opening.
+    NormalizedByQuestionId(
+        SecurityQuestionsKeyExchangeKeysFromQandAsNormalizedByQuestionId,
+    ),
+}
+
+impl Default for SecurityQuestionsEntropiesKdf {
+    fn default() -> Self {
+        Self::LowerTrimUtf8ByQuestionId(
+            SecurityQuestionsKeyExchangeKeysFromQandAsLowerTrimUtf8ByQuestionId,
+        )
+    }
+}
+
+impl SecurityQuestionsEntropiesKdf {
+    /// The Input Key Material derived from the answer, before any stretching.
+    ///
+    /// If the question has a [`Transliteration`], freeform answers are
+    /// transliterated, and then if its expected answer format has a
+    /// [`PhoneticEncoding`] they are phonetically encoded, before being
+    /// normalized by the sub-KDF.
+    pub(crate) fn input_key_material(
+        &self,
+        question_answer_and_salt: &SecurityQuestionAnswerAndSalt,
+    ) -> Result<SecretBytes> {
+        let question = &question_answer_and_salt.question;
+        let transliteration = question.transliteration;
+        let phonetic_encoding =
+            question.expected_answer_format.phonetic_encoding;
+        let Some(answer) = question_answer_and_salt.answer.as_freeform() else {
+            return self.input_key_material_of_answer(question_answer_and_salt);
+        };
+        if transliteration.is_none() && phonetic_encoding.is_none() {
+            return self.input_key_material_of_answer(question_answer_and_salt);
+        }
+
+        let mut processed = answer.to_owned();
+        if let Some(transliteration) = transliteration {
+            let transliterated = transliteration.transliterate(&processed);
+            processed.zeroize();
+            processed = transliterated;
+        }
+        if let Some(phonetic_encoding) = phonetic_encoding {
+            let encoded = phonetic_encoding.encode(&processed);
+            processed.zeroize();
+            processed = encoded;
+        }
+
+        self.input_key_material_of_answer(&SecurityQuestionAnswerAndSalt {
+            question: question.clone(),
+            answer: SecretAnswer::from(processed).into(),
+            salt: question_answer_and_salt.salt,
+        })
+    }
+
+    fn input_key_material_of_answer(
+        &self,
+        question_answer_and_salt: &SecurityQuestionAnswerAndSalt,
+    ) -> Result<SecretBytes> {
+        match self {
+            Self::LowerTrimUtf8(kdf) => {
+                kdf.input_key_material(question_answer_and_salt)
+            }
+            Self::LowerTrimUtf8ByQuestionId(kdf) => {
+                kdf.input_key_material(question_answer_and_salt)
+            }
+            Self::NfkcLowerTrimUtf8ByQuestionId(kdf) => {
+                kdf.input_key_material(question_answer_and_salt)
+            }
+            Self::NormalizedByQuestionId(kdf) => {
+                kdf.input_key_material(question_answer_and_salt)
+            }
+        }
+    }
+
+    /// Expands the (possibly stretched) `input_key_material` into an entropy.
+    pub(crate) fn expand(
+        &self,
+        question_answer_and_salt: &SecurityQuestionAnswerAndSalt,
+        input_key_material: impl AsRef<[u8]>,
+    ) -> Exactly32Bytes {
+        match self {
+            Self::LowerTrimUtf8(kdf) => {
+                kdf.expand(question_answer_and_salt, input_key_material)
+            }
+            Self::LowerTrimUtf8ByQuestionId(kdf) => {
+                kdf.expand(question_answer_and_salt, input_key_material)
+            }
+            Self::NfkcLowerTrimUtf8ByQuestionId(kdf) => {
+                kdf.expand(question_answer_and_salt, input_key_material)
+            }
+            Self::NormalizedByQuestionId(kdf) => {
+                kdf.expand(question_answer_and_salt, input_key_material)
+            }
+        }
+    }
+
+    pub fn derive_entropies_from_question_answer_and_salt(
+        &self,
+        question_answer_and_salt: &SecurityQuestionAnswerAndSalt,
+    ) -> Result<Exactly32Bytes> {
+        let ikm = self.input_key_material(question_answer_and_salt)?;
+        Ok(self.expand(question_answer_and_salt, ikm))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Sut = SecurityQuestionsEntropiesKdf;
+
+    #[test]
+    fn default_is_by_question_id() {
+        assert!(matches!(Sut::default(), Sut::LowerTrimUtf8ByQuestionId(_)));
+    }
+
+    #[test]
+    fn delegates() {
+        let qas = SecurityQuestionAnswerAndSalt::sample();
+        assert_eq!(
+            Sut::LowerTrimUtf8(
+                SecurityQuestionsKeyExchangeKeysFromQandAsLowerTrimUtf8
+            )
+            .derive_entropies_from_question_answer_and_salt(&qas),
+            SecurityQuestionsKeyExchangeKeysFromQandAsLowerTrimUtf8
+                .derive_entropies_from_question_answer_and_salt(&qas)
+        );
+        assert_eq!(
+            Sut::default().derive_entropies_from_question_answer_and_salt(&qas),
+            SecurityQuestionsKeyExchangeKeysFromQandAsLowerTrimUtf8ByQuestionId
+                .derive_entropies_from_question_answer_and_salt(&qas)
+        );
+        let nfkc =
+            SecurityQuestionsKeyExchangeKeysFromQandAsNfkcLowerTrimUtf8ByQuestionId::default();
+        assert_eq!(
+            Sut::NfkcLowerTrimUtf8ByQuestionId(nfkc.clone())
+                .derive_entropies_from_question_answer_and_salt(&qas),
+            nfkc.derive_entropies_from_question_answer_and_salt(&qas)
+        );
+        let normalized =
+            SecurityQuestionsKeyExchangeKeysFromQandAsNormalizedByQuestionId::new(
+                AnswerNormalization::sample_other(),
+            );
+        assert_eq!(
+            Sut::NormalizedByQuestionId(normalized.clone())
+                .derive_entropies_from_question_answer_and_salt(&qas),
+            normalized.derive_entropies_from_question_answer_and_salt(&qas)
+        );
+    }
+
+    #[test]
+    fn transliteration_of_question_is_applied() {
+        let question = SecurityQuestion::first_kiss_whom()
+            .with_transliteration(Transliteration::ToLatinVersion1);
+        let derive = |question: &SecurityQuestion, answer: &str| {
+            Sut::default()
+                .derive_entropies_from_question_answer_and_salt(
+                    &SecurityQuestionAnswerAndSalt {
+                        question: question.clone(),
+                        answer: answer.into(),
+                        salt: Exactly32Bytes::sample_aced(),
+                    },
+                )
+                .unwrap()
+        };
+        assert_eq!(
+            derive(&question, "Сергей Петров"),
+            derive(&question, "Sergei Petrov")
+        );
+        let without = SecurityQuestion::first_kiss_whom();
+        assert_ne!(
+            derive(&without, "Сергей Петров"),
+            derive(&without, "Sergei Petrov")
+        );
+        assert_eq!(
+            derive(&without, "Sergei Petrov"),
+            derive(&question, "Sergei Petrov")
+        );
+    }
+
+    #[test]
+    fn phonetic_encoding_of_question_is_applied() {
+        let mut question = SecurityQuestion::first_kiss_whom();
+        question.expected_answer_format = question
+            .expected_answer_format
+            .with_phonetic_encoding(PhoneticEncoding::MetaphoneVersion1);
+        let derive = |question: &SecurityQuestion, answer: &str| {
+            Sut::default()
+                .derive_entropies_from_question_answer_and_salt(
+                    &SecurityQuestionAnswerAndSalt {
+                        question: question.clone(),
+                        answer: answer.into(),
+                        salt: Exactly32Bytes::sample_aced(),
+                    },
+                )
+                .unwrap()
+        };
+        assert_eq!(
+            derive(&question, "Catherine Smith"),
+            derive(&question, "kathryn smyth")
+        );
+        assert_ne!(
+            derive(&question, "Catherine Smith"),
+            derive(&question, "Caroline Smith")
+        );
+        let without = SecurityQuestion::first_kiss_whom();
+        assert_ne!(
+            derive(&without, "Catherine Smith"),
+            derive(&without, "kathryn smyth")
+        );
+    }
+
+    #[test]
+    fn transliteration_is_applied_before_phonetic_encoding() {
+        let mut question = SecurityQuestion::first_kiss_whom()
+            .with_transliteration(Transliteration::ToLatinVersion1);
+        question.expected_answer_format = question
+            .expected_answer_format
+            .with_phonetic_encoding(PhoneticEncoding::MetaphoneVersion1);
+        let derive = |answer: &str| {
+            Sut::default()
+                .derive_entropies_from_question_answer_and_salt(
+                    &SecurityQuestionAnswerAndSalt {
+                        question: question.clone(),
+                        answer: answer.into(),
+                        salt: Exactly32Bytes::sample_aced(),
+                    },
+                )
+                .unwrap()
+        };
+        assert_eq!(derive("Сергей"), derive("Sergey"));
+    }
+
+    #[test]
+    fn json_default() {
+        assert_eq!(
+            serde_json::to_string(&Sut::default()).unwrap(),
+            r#"{"LowerTrimUtf8ByQuestionId":null}"#
+        );
+    }
+
+    #[test]
+    fn json_nfkc() {
+        assert_eq!(
+            serde_json::to_string(&Sut::NfkcLowerTrimUtf8ByQuestionId(
+                SecurityQuestionsKeyExchangeKeysFromQandAsNfkcLowerTrimUtf8ByQuestionId::default()
+            ))
+            .unwrap(),
+            r#"{"NfkcLowerTrimUtf8ByQuestionId":{"fold_diacritics":true}}"#
+        );
+    }
+
+    #[test]
+    fn json_normalized() {
+        assert_eq!(
+            serde_json::to_string(&Sut::NormalizedByQuestionId(
+                SecurityQuestionsKeyExchangeKeysFromQandAsNormalizedByQuestionId::new(
+                    AnswerNormalization::new([
+                        AnswerNormalizationStep::CaseFold,
+                        AnswerNormalizationStep::StripChars {
+                            chars: "-".to_owned()
+                        },
+                    ])
+                )
+            ))
+            .unwrap(),
+            r#"{"NormalizedByQuestionId":{"normalization":{"steps":["CaseFold",{"StripChars":{"chars":"-"}}]}}}"#
+        );
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        for sut in [
+            Sut::default(),
+            Sut::LowerTrimUtf8(
+                SecurityQuestionsKeyExchangeKeysFromQandAsLowerTrimUtf8,
+            ),
+            Sut::NfkcLowerTrimUtf8ByQuestionId(
+                SecurityQuestionsKeyExchangeKeysFromQandAsNfkcLowerTrimUtf8ByQuestionId::new(false),
+            ),
+            Sut::NormalizedByQuestionId(
+                SecurityQuestionsKeyExchangeKeysFromQandAsNormalizedByQuestionId::new(
+                    AnswerNormalization::sample_other(),
+                ),
+            ),
+        ] {
+            let json = serde_json::to_string(&sut).unwrap();
+            let deserialized: Sut = serde_json::from_str(&json).unwrap();
+            assert_eq!(sut, deserialized);
+        }
+    }
+}
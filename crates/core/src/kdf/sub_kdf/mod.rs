@@ -1,5 +1,13 @@
+mod answer_normalization;
+mod answer_stretching;
 mod entropies_from_questions_answer_and_salt;
+mod security_questions_encryption_keys_by_hkdf_of_sorted_entropies;
 mod security_questions_encryption_keys_by_xor_entropies;
+mod security_questions_encryption_keys_kdf;
 
+pub use answer_normalization::*;
+pub use answer_stretching::*;
 pub use entropies_from_questions_answer_and_salt::*;
+pub use security_questions_encryption_keys_by_hkdf_of_sorted_entropies::*;
 pub use security_questions_encryption_keys_by_xor_entropies::*;
+pub use security_questions_encryption_keys_kdf::*;
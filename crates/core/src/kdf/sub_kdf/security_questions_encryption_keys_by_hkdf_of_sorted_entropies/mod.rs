@@ -0,0 +1,4 @@
+#[allow(clippy::module_inception)]
+mod security_questions_encryption_keys_by_hkdf_of_sorted_entropies;
+
+pub use security_questions_encryption_keys_by_hkdf_of_sorted_entropies::*;
@@ -0,0 +1,175 @@
+use crate::prelude::*;
+
+use hkdf::Hkdf;
+use sha2::Sha256;
+
+/// Derives each encryption key by running HKDF-SHA256 over the sorted
+/// entropies of a combination of answers, using a domain separation tag as
+/// salt and the sorted question ids of the combination as info.
+///
+/// Unlike [`SecurityQuestionsEncryptionKeysByXorEntropies`] keys of
+/// combinations sharing members are not linearly related, XOR-ing two keys
+/// does not cancel out the shared entropies.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct SecurityQuestionsEncryptionKeysByHkdfOfSortedEntropies;
+
+impl Default for SecurityQuestionsEncryptionKeysByHkdfOfSortedEntropies {
+    fn default() -> Self {
+        Self
+    }
+}
+
+impl SecurityQuestionsEncryptionKeysByHkdfOfSortedEntropies {
+    /// Domain separation tag used as HKDF salt, ensuring that keys derived by
+    /// this combiner are never equal to HKDF output of any other context.
+    pub const DOMAIN_TAG: &'static [u8] =
+        b"svar/security_questions/encryption_key/hkdf_of_sorted_entropies/v1";
+
+    /// Derives the encryption key of a single combination of entropies,
+    /// the order of `combination` does not matter.
+    pub(crate) fn key_from_combination(
+        &self,
+        combination: Vec<&SecurityQuestionEntropy>,
+    ) -> EncryptionKey {
+        // Input Key Material: the entropies of the combination, sorted so
+        // that the order in which the questions were answered does not matter.
+        let mut ikm = Vec::with_capacity(combination.len() * 32);
+        for entropy in combination.iter().map(|e| &e.entropy).sorted() {
+            ikm.extend_from_slice(entropy.bytes());
+        }
+        let ikm = SecretBytes::from(ikm);
+
+        // We use the sorted question ids as info, binding the key to the
+        // exact combination of questions it was derived from.
+        let info = combination
+            .iter()
+            .map(|e| e.question_id)
+            .sorted()
+            .flat_map(u16::to_be_bytes)
+            .collect_vec();
+
+        let hkdf = Hkdf::<Sha256>::new(Some(Self::DOMAIN_TAG), ikm.as_ref());
+        let mut okm = [0u8; 32];
+        hkdf.expand(&info, &mut okm).unwrap();
+        let key = EncryptionKey::from(Exactly32Bytes::from(okm));
+        okm.zeroize();
+        key
+    }
+
+    pub fn derive_encryption_keys_from<
+        const QUESTION_COUNT: usize,
+        const MIN_CORRECT_ANSWERS: usize,
+    >(
+        &self,
+        entropies: [SecurityQuestionEntropy; QUESTION_COUNT],
+    ) -> Result<EncryptionKeys<QUESTION_COUNT, MIN_CORRECT_ANSWERS>> {
+        assert!(QUESTION_COUNT >= MIN_CORRECT_ANSWERS);
+
+        let keys = entropies
+            .iter()
+            .combinations(MIN_CORRECT_ANSWERS)
+            .map(|combination| self.key_from_combination(combination))
+            .collect::<IndexSet<EncryptionKey>>();
+
+        EncryptionKeys::<QUESTION_COUNT, MIN_CORRECT_ANSWERS>::new(keys)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Sut = SecurityQuestionsEncryptionKeysByHkdfOfSortedEntropies;
+
+    fn entropies() -> [SecurityQuestionEntropy; 3] {
+        [
+            SecurityQuestionEntropy::new(1, Exactly32Bytes::sample_aced()),
+            SecurityQuestionEntropy::new(2, Exactly32Bytes::sample_babe()),
+            SecurityQuestionEntropy::new(3, Exactly32Bytes::sample_cafe()),
+        ]
+    }
+
+    #[test]
+    fn derive_encryption_keys_from_order_does_not_matter() {
+        let sut = Sut::default();
+        let entropies = entropies();
+
+        let keys1 = sut
+            .derive_encryption_keys_from::<3, 2>(entropies.clone())
+            .unwrap();
+        let keys2 = sut
+            .derive_encryption_keys_from::<3, 2>([
+                entropies[2].clone(),
+                entropies[0].clone(),
+                entropies[1].clone(),
+            ])
+            .unwrap();
+
+        assert_eq!(keys1, keys2);
+    }
+
+    #[test]
+    fn question_ids_affect_keys() {
+        let sut = Sut::default();
+        let entropies = entropies();
+        let mut other_ids = entropies.clone();
+        other_ids[0].question_id = 4;
+
+        assert_ne!(
+            sut.derive_encryption_keys_from::<3, 2>(entropies).unwrap(),
+            sut.derive_encryption_keys_from::<3, 2>(other_ids).unwrap()
+        );
+    }
+
+    #[test]
+    fn keys_are_not_linearly_related() {
+        let sut = Sut::default();
+        let entropies = entropies();
+        let keys = sut
+            .derive_encryption_keys_from::<3, 2>(entropies.clone())
+            .unwrap()
+            .into_iter()
+            .map(|k| k.0)
+            .collect_vec();
+
+        // With XOR, k(a, b) ^ k(a, c) == b ^ c.
+        assert_ne!(
+            keys[0].xor(&keys[1]),
+            entropies[1].entropy.xor(&entropies[2].entropy)
+        );
+    }
+
+    #[test]
+    fn differs_from_xor() {
+        let entropies = entropies();
+        let xor = SecurityQuestionsEncryptionKeysByXorEntropies
+            .derive_encryption_keys_from::<3, 2>(
+                entropies.each_ref().map(|e| e.entropy),
+            )
+            .unwrap();
+        let hkdf = Sut::default()
+            .derive_encryption_keys_from::<3, 2>(entropies)
+            .unwrap();
+        assert_ne!(xor, hkdf);
+    }
+
+    #[test]
+    fn known_key() {
+        let entropies = entropies();
+        let key = Sut::default()
+            .key_from_combination(vec![&entropies[1], &entropies[0]]);
+        assert_eq!(
+            key.0.to_hex(),
+            "074d9b0d355b6ff91c6b16418f7d0e2f15c0a830e41801b38d374b2aa58b1315"
+        );
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        let sut = Sut::default();
+        let json = serde_json::to_string(&sut).unwrap();
+        assert_eq!(json, "null");
+        let deserialized: Sut = serde_json::from_str(&json).unwrap();
+        assert_eq!(sut, deserialized);
+    }
+}
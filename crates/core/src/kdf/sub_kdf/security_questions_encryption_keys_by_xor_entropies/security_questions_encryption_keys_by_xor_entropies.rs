@@ -4,6 +4,22 @@ use crate::prelude::*;
 pub struct SecurityQuestionsEncryptionKeysByXorEntropies;
 
 impl SecurityQuestionsEncryptionKeysByXorEntropies {
+    /// Derives the encryption key of a single combination of entropies by
+    /// XOR-ing them, the order of `combination` does not matter.
+    pub(crate) fn key_from_combination(
+        &self,
+        combination: Vec<&Exactly32Bytes>,
+    ) -> EncryptionKey {
+        let mut bytes = combination
+            .into_iter()
+            .copied()
+            .reduce(|acc, x| acc.xor(&x))
+            .unwrap();
+        let key = EncryptionKey::from(bytes);
+        bytes.zeroize();
+        key
+    }
+
     fn encryption_keys_from_xor_between_all_combinations<
         const QUESTION_COUNT: usize,
         const MIN_CORRECT_ANSWERS: usize,
@@ -13,35 +29,28 @@ impl SecurityQuestionsEncryptionKeysByXorEntropies {
     ) -> Result<EncryptionKeys<QUESTION_COUNT, MIN_CORRECT_ANSWERS>> {
         let size = MIN_CORRECT_ANSWERS;
 
-        let key_from_combination_by_xor =
-            |combination: Vec<&Exactly32Bytes>| -> EncryptionKey {
-                let bytes = combination
-                    .into_iter()
-                    .copied()
-                    .reduce(|acc, x| acc.xor(&x))
-                    .unwrap();
-                EncryptionKey::from(bytes)
-            };
-
         let combinations = entropies.iter().combinations(size);
 
         let keys = combinations
             .into_iter()
-            .map(|combination| key_from_combination_by_xor(combination))
+            .map(|combination| self.key_from_combination(combination))
             .collect::<IndexSet<EncryptionKey>>();
 
         EncryptionKeys::<QUESTION_COUNT, MIN_CORRECT_ANSWERS>::new(keys)
     }
 
+    /// Zeroizes `entropies` after usage.
     pub fn derive_encryption_keys_from<
         const QUESTION_COUNT: usize,
         const MIN_CORRECT_ANSWERS: usize,
     >(
         &self,
-        entropies: [Exactly32Bytes; QUESTION_COUNT],
+        mut entropies: [Exactly32Bytes; QUESTION_COUNT],
     ) -> Result<EncryptionKeys<QUESTION_COUNT, MIN_CORRECT_ANSWERS>> {
         assert!(QUESTION_COUNT >= MIN_CORRECT_ANSWERS);
-        self.encryption_keys_from_xor_between_all_combinations::<QUESTION_COUNT, MIN_CORRECT_ANSWERS>(entropies)
+        let keys = self.encryption_keys_from_xor_between_all_combinations::<QUESTION_COUNT, MIN_CORRECT_ANSWERS>(entropies);
+        entropies.zeroize();
+        keys
     }
 }
 
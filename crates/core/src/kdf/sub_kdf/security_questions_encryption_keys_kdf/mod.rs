@@ -0,0 +1,6 @@
+mod security_question_entropy;
+#[allow(clippy::module_inception)]
+mod security_questions_encryption_keys_kdf;
+
+pub use security_question_entropy::*;
+pub use security_questions_encryption_keys_kdf::*;
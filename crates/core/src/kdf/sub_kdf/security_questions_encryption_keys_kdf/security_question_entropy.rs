@@ -0,0 +1,57 @@
+use zeroize::ZeroizeOnDrop;
+
+use crate::prelude::*;
+
+/// The entropy derived from the answer to a security question, together with
+/// the id of the question it was derived from, allowing the encryption keys
+/// KDF to bind each key to the questions of its combination.
+///
+/// Zeroizes the entropy when dropped, its [`Debug`] implementation only
+/// reveals the question id.
+#[derive(
+    Zeroize, ZeroizeOnDrop, Clone, PartialEq, Eq, Hash, derive_more::Debug,
+)]
+#[debug(
+    "SecurityQuestionEntropy(question_id: {question_id}, entropy: <redacted>)"
+)]
+pub struct SecurityQuestionEntropy {
+    /// The [`SecurityQuestion::id`] of the question which was answered.
+    #[zeroize(skip)]
+    pub question_id: u16,
+
+    /// The entropy derived from the answer, salt and question.
+    pub entropy: Exactly32Bytes,
+}
+
+impl SecurityQuestionEntropy {
+    pub fn new(question_id: u16, entropy: Exactly32Bytes) -> Self {
+        Self {
+            question_id,
+            entropy,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Sut = SecurityQuestionEntropy;
+
+    #[test]
+    fn debug_is_redacted() {
+        let sut = Sut::new(7, Exactly32Bytes::sample_cafe());
+        assert_eq!(
+            format!("{sut:?}"),
+            "SecurityQuestionEntropy(question_id: 7, entropy: <redacted>)"
+        );
+    }
+
+    #[test]
+    fn zeroize() {
+        let mut sut = Sut::new(7, Exactly32Bytes::sample_cafe());
+        sut.zeroize();
+        assert_eq!(sut.entropy, Exactly32Bytes::from([0u8; 32]));
+        assert_eq!(sut.question_id, 7);
+    }
+}
@@ -0,0 +1,111 @@
+use crate::prelude::*;
+
+/// The "sub-KDF" used to combine the entropies derived from the answers to
+/// security questions into encryption keys, one key per combination of
+/// `MIN_CORRECT_ANSWERS` answers.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum SecurityQuestionsEncryptionKeysKdf {
+    /// Each key is the XOR of the entropies of a combination, as used by
+    /// [`SecurityQuestionsKDFSchemeVersion1`].
+    ByXorEntropies(SecurityQuestionsEncryptionKeysByXorEntropies),
+
+    /// Each key is the HKDF of the sorted entropies of a combination, bound to
+    /// the question ids of the combination.
+    ByHkdfOfSortedEntropies(
+        SecurityQuestionsEncryptionKeysByHkdfOfSortedEntropies,
+    ),
+}
+
+impl Default for SecurityQuestionsEncryptionKeysKdf {
+    fn default() -> Self {
+        Self::ByHkdfOfSortedEntropies(
+            SecurityQuestionsEncryptionKeysByHkdfOfSortedEntropies,
+        )
+    }
+}
+
+impl SecurityQuestionsEncryptionKeysKdf {
+    pub fn derive_encryption_keys_from<
+        const QUESTION_COUNT: usize,
+        const MIN_CORRECT_ANSWERS: usize,
+    >(
+        &self,
+        entropies: [SecurityQuestionEntropy; QUESTION_COUNT],
+    ) -> Result<EncryptionKeys<QUESTION_COUNT, MIN_CORRECT_ANSWERS>> {
+        match self {
+            Self::ByXorEntropies(kdf) => {
+                kdf.derive_encryption_keys_from(entropies.map(|e| e.entropy))
+            }
+            Self::ByHkdfOfSortedEntropies(kdf) => {
+                kdf.derive_encryption_keys_from(entropies)
+            }
+        }
+    }
+
+    /// Derives the encryption key of a single combination of entropies, the
+    /// order of `combination` does not matter.
+    pub fn derive_encryption_key_from_combination(
+        &self,
+        combination: Vec<&SecurityQuestionEntropy>,
+    ) -> EncryptionKey {
+        match self {
+            Self::ByXorEntropies(kdf) => kdf.key_from_combination(
+                combination.into_iter().map(|e| &e.entropy).collect(),
+            ),
+            Self::ByHkdfOfSortedEntropies(kdf) => {
+                kdf.key_from_combination(combination)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Sut = SecurityQuestionsEncryptionKeysKdf;
+
+    #[test]
+    fn default_is_hkdf() {
+        assert!(matches!(Sut::default(), Sut::ByHkdfOfSortedEntropies(_)));
+    }
+
+    #[test]
+    fn xor_delegates() {
+        let entropies = [
+            SecurityQuestionEntropy::new(1, Exactly32Bytes::sample_aced()),
+            SecurityQuestionEntropy::new(2, Exactly32Bytes::sample_babe()),
+        ];
+        let sut =
+            Sut::ByXorEntropies(SecurityQuestionsEncryptionKeysByXorEntropies);
+        assert_eq!(
+            sut.derive_encryption_keys_from::<2, 1>(entropies.clone())
+                .unwrap(),
+            SecurityQuestionsEncryptionKeysByXorEntropies
+                .derive_encryption_keys_from::<2, 1>(
+                    entropies.map(|e| e.entropy)
+                )
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        for sut in [
+            Sut::default(),
+            Sut::ByXorEntropies(SecurityQuestionsEncryptionKeysByXorEntropies),
+        ] {
+            let json = serde_json::to_string(&sut).unwrap();
+            let deserialized: Sut = serde_json::from_str(&json).unwrap();
+            assert_eq!(sut, deserialized);
+        }
+    }
+
+    #[test]
+    fn json_default() {
+        assert_eq!(
+            serde_json::to_string(&Sut::default()).unwrap(),
+            r#"{"ByHkdfOfSortedEntropies":null}"#
+        );
+    }
+}
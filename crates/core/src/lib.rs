@@ -44,7 +44,9 @@
 //!         answer_structure: "<ARTIST>, <LOCATION>, <YEAR>".to_owned(),
 //!         example_answer: "Jean-Michel Jarre, Paris La Défense, 1990".to_owned(),
 //!         unsafe_answers: vec![],
+//!         phonetic_encoding: None,
 //!     },
+//!     transliteration: None,
 //! };
 //! let q1 = SecurityQuestion {
 //!     id: 1,
@@ -54,8 +56,10 @@
 //!     expected_answer_format: SecurityQuestionExpectedAnswerFormat {
 //!         answer_structure: "<LAST_NAME>, <FIRST_NAME>>".to_owned(),
 //!         example_answer: "Doe, Jane".to_owned(),
-//!         unsafe_answers: vec![]
+//!         unsafe_answers: vec![],
+//!         phonetic_encoding: None,
 //!     },
+//!     transliteration: None,
 //! };
 //! let q2 = SecurityQuestion {
 //!     id: 2,
@@ -72,7 +76,9 @@
 //!             "Winnie".to_owned(), // Winnie the Poh
 //!             "Rabbit".to_owned(), // Peter Rabbit
 //!         ],
+//!         phonetic_encoding: None,
 //!     },
+//!     transliteration: None,
 //! };
 //! let q3 = SecurityQuestion {
 //!     id: 3,
@@ -83,7 +89,9 @@
 //!         answer_structure: "<LAST_NAME>, <FIRST_NAME>>".to_owned(),
 //!         example_answer: "Parker, Elisabeth".to_owned(),
 //!         unsafe_answers: vec![],
+//!         phonetic_encoding: None,
 //!     },
+//!     transliteration: None,
 //! };
 //!
 //! /// The secret the user wants to protect
@@ -135,10 +143,11 @@
 //! /// Define incorrect answer for question 0 - we will use it later to
 //! /// demonstrate that we can still decrypt the secret with 3 correct answers
 //! /// and 1 incorrect answer
-//! let qas0_incorrect = SecurityQuestionAnswerAndSalt::by_answering_freeform(
-//!     q0.clone(),
-//!     |_q, _format| "Incorrect answer for Q0".to_owned()
-//! ).unwrap();
+//! /// Reuse the salt of `qas0`, the salts used to seal must be used to open
+//! let qas0_incorrect = SecurityQuestionAnswerAndSalt {
+//!     answer: "Incorrect answer for Q0".into(),
+//!     ..qas0.clone()
+//! };
 //!
 //! let qas_q0_incorrect = SecurityQuestionsAnswersAndSalts::<Q>::from([
 //!     qas0_incorrect.clone(),
@@ -154,10 +163,11 @@
 //! assert_eq!(decrypted_secret, user_secret);
 //!
 //! /// We can also provide incorrect answer for question 1...or any other question.
-//! let qas1_incorrect = SecurityQuestionAnswerAndSalt::by_answering_freeform(
-//!     q1.clone(),
-//!     |_q, _format| "Incorrect answer for Q1".to_owned()
-//! ).unwrap();
+//! /// Reuse the salt of `qas1`, the salts used to seal must be used to open
+//! let qas1_incorrect = SecurityQuestionAnswerAndSalt {
+//!     answer: "Incorrect answer for Q1".into(),
+//!     ..qas1.clone()
+//! };
 //!
 //! let qas_q1_incorrect = SecurityQuestionsAnswersAndSalts::<Q>::from([
 //!     qas0.clone(),
@@ -197,7 +207,9 @@
 //!         answer_structure: "<CITY>, <YEAR>".to_owned(),
 //!         example_answer: "London, 1963".to_owned(),
 //!         unsafe_answers: vec![],
+//!         phonetic_encoding: None,
 //!     },  
+//!     transliteration: None,
 //! };
 //!
 //! /// Provide some dummy answer to the unrelated question
@@ -235,21 +247,31 @@
 //! to seal and decrypt a secret using security questions and answers.
 
 mod encryption;
+mod is_security_questions_sealed;
 mod kdf;
 mod models;
+mod sealed_secret_header;
 mod security_questions_sealed;
+mod security_questions_sealed_dyn;
+mod security_questions_sealed_stream;
+mod shamir;
 
 pub mod prelude {
     pub use crate::encryption::*;
+    pub(crate) use crate::is_security_questions_sealed::*;
     pub use crate::kdf::*;
     pub use crate::models::*;
+    pub(crate) use crate::sealed_secret_header::*;
     pub use crate::security_questions_sealed::*;
+    pub use crate::security_questions_sealed_dyn::*;
+    pub use crate::security_questions_sealed_stream::*;
+    pub(crate) use crate::shamir::*;
 
     pub use std::str::FromStr;
 
     pub use derive_more::{AsRef, Display, From};
     pub use hex::{decode as hex_decode, encode as hex_encode};
-    pub use indexmap::IndexSet;
+    pub use indexmap::{IndexMap, IndexSet};
     pub use itertools::Itertools;
     pub use serde::{Deserialize, Serialize};
     pub use serde_with::{DeserializeFromStr, SerializeDisplay};
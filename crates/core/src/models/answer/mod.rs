@@ -1,5 +1,13 @@
+mod secret_answer;
+mod security_question_answer;
+mod security_question_answer_analysis;
 mod security_question_answer_and_salt;
 mod security_questions_answers_and_salts;
+mod security_questions_answers_report;
 
+pub use secret_answer::*;
+pub use security_question_answer::*;
+pub use security_question_answer_analysis::*;
 pub use security_question_answer_and_salt::*;
 pub use security_questions_answers_and_salts::*;
+pub use security_questions_answers_report::*;
@@ -0,0 +1,123 @@
+use zeroize::ZeroizeOnDrop;
+
+use crate::prelude::*;
+
+/// The free text answer to a [`SecurityQuestionKind::Freeform`] question.
+///
+/// Answers are as sensitive as the secrets they protect, so the text is
+/// zeroized when dropped and never printed, both [`Display`] and [`Debug`]
+/// are redacted. Use [`expose_secret`](Self::expose_secret) to read it.
+///
+/// # Examples
+///
+/// ```
+/// use svar_core::*;
+///
+/// let answer = SecretAnswer::from("Oinky piggy pig");
+/// assert_eq!(answer.expose_secret(), "Oinky piggy pig");
+/// assert_eq!(answer.to_string(), "<redacted>");
+/// assert_eq!(format!("{answer:?}"), "SecretAnswer(<redacted>)");
+/// ```
+///
+/// # Serialization
+///
+/// Serialized as a plain JSON string, since serialization is how answers are
+/// deliberately exported:
+///
+/// ```
+/// use svar_core::*;
+///
+/// let answer = SecretAnswer::from("Oxford");
+/// assert_eq!(serde_json::to_string(&answer)?, "\"Oxford\"");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(
+    Serialize,
+    Deserialize,
+    Zeroize,
+    ZeroizeOnDrop,
+    Clone,
+    PartialEq,
+    Eq,
+    Hash,
+    Display,
+    derive_more::Debug,
+)]
+#[serde(transparent)]
+#[display("<redacted>")]
+#[debug("SecretAnswer(<redacted>)")]
+pub struct SecretAnswer(String);
+
+impl SecretAnswer {
+    /// The text of the answer.
+    pub fn expose_secret(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns `true` if the answer is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Takes ownership of `value` without copying it, so that no copy of the
+/// answer is left behind.
+impl From<String> for SecretAnswer {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl From<&str> for SecretAnswer {
+    fn from(value: &str) -> Self {
+        Self(value.to_owned())
+    }
+}
+
+impl HasSampleValues for SecretAnswer {
+    fn sample() -> Self {
+        Self::from("Jean-Michel Jarre, Paris La Défense, 1990")
+    }
+
+    fn sample_other() -> Self {
+        Self::from("Oinky piggy pig")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Sut = SecretAnswer;
+
+    #[test]
+    fn equality() {
+        assert_eq!(Sut::sample(), Sut::sample());
+        assert_eq!(Sut::sample_other(), Sut::sample_other());
+        assert_ne!(Sut::sample(), Sut::sample_other());
+    }
+
+    #[test]
+    fn formatting_does_not_reveal_answer() {
+        let sut = Sut::sample_other();
+        for formatted in
+            [sut.to_string(), format!("{sut:?}"), format!("{sut:#?}")]
+        {
+            assert!(!formatted.contains("Oinky"), "{formatted}");
+        }
+    }
+
+    #[test]
+    fn zeroize() {
+        let mut sut = Sut::sample();
+        sut.zeroize();
+        assert!(sut.is_empty());
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        let sut = Sut::sample();
+        let json = serde_json::to_string(&sut).unwrap();
+        assert_eq!(serde_json::from_str::<Sut>(&json).unwrap(), sut);
+    }
+}
@@ -0,0 +1,166 @@
+use zeroize::ZeroizeOnDrop;
+
+use crate::prelude::*;
+
+/// The answer to a [`SecurityQuestion`], either free text for
+/// [`SecurityQuestionKind::Freeform`] questions or selections in datasets for
+/// [`SecurityQuestionKind::Structured`] questions.
+///
+/// # Examples
+///
+/// ```
+/// use svar_core::*;
+///
+/// let freeform: SecurityQuestionAnswer = "Oinky piggy pig".into();
+/// assert_eq!(freeform.as_freeform(), Some("Oinky piggy pig"));
+///
+/// let structured = SecurityQuestionAnswer::Structured(vec![42, 1337]);
+/// assert_eq!(structured.as_structured(), Some([42, 1337].as_slice()));
+/// ```
+///
+/// # Serialization
+///
+/// Freeform answers are serialized as a JSON string and structured answers as
+/// a JSON array of selections:
+///
+/// ```
+/// use svar_core::*;
+///
+/// let freeform: SecurityQuestionAnswer = "Oxford".into();
+/// assert_eq!(serde_json::to_string(&freeform)?, "\"Oxford\"");
+///
+/// let structured = SecurityQuestionAnswer::Structured(vec![42, 1337]);
+/// assert_eq!(serde_json::to_string(&structured)?, "[42,1337]");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Secrecy
+///
+/// Answers are zeroized when dropped and never printed, both [`Display`] and
+/// [`Debug`] are redacted:
+///
+/// ```
+/// use svar_core::*;
+///
+/// let freeform: SecurityQuestionAnswer = "Oxford".into();
+/// assert_eq!(freeform.to_string(), "<redacted>");
+/// assert!(!format!("{freeform:?}").contains("Oxford"));
+/// ```
+#[derive(
+    Serialize,
+    Deserialize,
+    Zeroize,
+    ZeroizeOnDrop,
+    Clone,
+    PartialEq,
+    Eq,
+    Hash,
+    Display,
+    derive_more::Debug,
+)]
+#[serde(untagged)]
+pub enum SecurityQuestionAnswer {
+    /// A free text answer to a [`SecurityQuestionKind::Freeform`] question.
+    #[display("<redacted>")]
+    Freeform(SecretAnswer),
+
+    /// The indices of the selected entries, one per dataset referenced by the
+    /// [`SecurityQuestionKind::Structured`] question, in the same order.
+    #[display("<redacted>")]
+    #[debug("Structured(<redacted>)")]
+    Structured(Vec<u16>),
+}
+
+impl SecurityQuestionAnswer {
+    /// Returns the free text answer, if this is a freeform answer.
+    pub fn as_freeform(&self) -> Option<&str> {
+        match self {
+            Self::Freeform(answer) => Some(answer.expose_secret()),
+            Self::Structured(_) => None,
+        }
+    }
+
+    /// Returns the selections, if this is a structured answer.
+    pub fn as_structured(&self) -> Option<&[u16]> {
+        match self {
+            Self::Freeform(_) => None,
+            Self::Structured(selections) => Some(selections),
+        }
+    }
+
+    /// Returns `true` if the answer is empty, i.e. empty free text or no
+    /// selections.
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Self::Freeform(answer) => answer.is_empty(),
+            Self::Structured(selections) => selections.is_empty(),
+        }
+    }
+}
+
+impl From<SecretAnswer> for SecurityQuestionAnswer {
+    fn from(value: SecretAnswer) -> Self {
+        Self::Freeform(value)
+    }
+}
+
+impl From<String> for SecurityQuestionAnswer {
+    fn from(value: String) -> Self {
+        Self::Freeform(value.into())
+    }
+}
+
+impl From<&str> for SecurityQuestionAnswer {
+    fn from(value: &str) -> Self {
+        Self::Freeform(value.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Sut = SecurityQuestionAnswer;
+
+    #[test]
+    fn json_roundtrip() {
+        for sut in [Sut::from("Oxford"), Sut::Structured(vec![1, 2])] {
+            let json = serde_json::to_string(&sut).unwrap();
+            let deserialized: Sut = serde_json::from_str(&json).unwrap();
+            assert_eq!(sut, deserialized);
+        }
+    }
+
+    #[test]
+    fn display_is_redacted() {
+        assert_eq!(Sut::from("Oxford").to_string(), "<redacted>");
+        assert_eq!(Sut::Structured(vec![1, 2]).to_string(), "<redacted>");
+    }
+
+    #[test]
+    fn debug_is_redacted() {
+        assert_eq!(
+            format!("{:?}", Sut::from("Oxford")),
+            "Freeform(SecretAnswer(<redacted>))"
+        );
+        assert_eq!(
+            format!("{:?}", Sut::Structured(vec![1337])),
+            "Structured(<redacted>)"
+        );
+    }
+
+    #[test]
+    fn zeroize() {
+        let mut sut = Sut::Structured(vec![42, 1337]);
+        sut.zeroize();
+        assert!(sut.is_empty());
+    }
+
+    #[test]
+    fn is_empty() {
+        assert!(Sut::from("").is_empty());
+        assert!(Sut::Structured(vec![]).is_empty());
+        assert!(!Sut::from("Oxford").is_empty());
+        assert!(!Sut::Structured(vec![0]).is_empty());
+    }
+}
@@ -0,0 +1,26 @@
+use crate::prelude::*;
+
+/// What could be concluded about the answer to a single security question
+/// after successfully opening a sealed secret, see
+/// [`SecurityQuestionsSealed::open_with_report`].
+#[derive(
+    Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug, Display,
+)]
+pub enum SecurityQuestionAnswerAnalysis {
+    /// The answer is part of a combination of answers which decrypted the
+    /// secret, so it is provably correct.
+    Correct,
+
+    /// A combination consisting of this answer and otherwise only correct
+    /// answers failed to decrypt the secret, so the answer is provably wrong.
+    ///
+    /// This is a good time to prompt the user to update the answer.
+    Incorrect,
+
+    /// Nothing could be concluded about the answer, since it was never
+    /// combined with only correct answers.
+    Ambiguous,
+
+    /// No answer was provided for the question.
+    NotProvided,
+}
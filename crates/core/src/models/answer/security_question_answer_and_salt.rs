@@ -32,7 +32,13 @@ use crate::prelude::*;
 /// )?;
 ///
 /// assert_eq!(qa_salt.question, question);
-/// assert!(qa_salt.answer.starts_with("Answer to:"));
+/// assert!(
+///     qa_salt
+///         .answer
+///         .as_freeform()
+///         .unwrap()
+///         .starts_with("Answer to:")
+/// );
 /// assert_eq!(qa_salt.salt.0.len(), 32); // Salt is always 32 bytes
 ///
 /// # Ok::<(), svar_core::Error>(())
@@ -45,12 +51,12 @@ use crate::prelude::*;
 ///
 /// let qa_salt = SecurityQuestionAnswerAndSalt {
 ///     question: SecurityQuestion::sample(),
-///     answer: "My pet's name was Fluffy".to_string(),
+///     answer: "My pet's name was Fluffy".into(),
 ///     salt: Exactly32Bytes::generate(),
 /// };
 ///
 /// println!("Question: {}", qa_salt.question.question);
-/// println!("Answer: {}", qa_salt.answer);
+/// println!("Answer: {}", qa_salt.answer); // prints "Answer: <redacted>"
 /// ```
 ///
 /// ## Using Sample Data
@@ -81,18 +87,22 @@ use crate::prelude::*;
 ///
 /// # Display Format
 ///
-/// The [`Display`] implementation shows the question and answer (but not the
-/// salt):
+/// The [`Display`] implementation shows the question but redacts the answer,
+/// and omits the salt, the [`Debug`] implementation redacts the answer too:
 ///
 /// ```
 /// use svar_core::*;
 ///
-/// let qa_salt = SecurityQuestionAnswerAndSalt::sample();
+/// let qa_salt = SecurityQuestionAnswerAndSalt {
+///     answer: "Fluffy".into(),
+///     ..SecurityQuestionAnswerAndSalt::sample()
+/// };
 /// let display = format!("{}", qa_salt);
 /// assert!(display.contains("SecurityQuestionAnswerAndSalt"));
 /// assert!(display.contains("question:"));
-/// assert!(display.contains("answer:"));
-/// // Salt is not included in display for security
+/// assert!(display.contains("answer: <redacted>"));
+/// assert!(!display.contains("Fluffy"));
+/// assert!(!format!("{:?}", qa_salt).contains("Fluffy"));
 /// ```
 #[derive(
     Serialize, Display, Deserialize, Clone, PartialEq, Eq, Hash, Debug,
@@ -109,11 +119,12 @@ pub struct SecurityQuestionAnswerAndSalt {
 
     /// The user's answer to the security question.
     ///
-    /// This is the actual response provided by the user. The answer is used
-    /// in combination with the question and salt to derive encryption keys.
-    /// Should be stored and retrieved exactly as provided for consistent
-    /// key derivation.
-    pub answer: String,
+    /// This is the actual response provided by the user, free text for
+    /// freeform questions or selections for structured questions. The answer
+    /// is used in combination with the question and salt to derive
+    /// encryption keys. Should be stored and retrieved exactly as provided
+    /// for consistent key derivation.
+    pub answer: SecurityQuestionAnswer,
 
     /// Cryptographic salt for key derivation.
     ///
@@ -161,7 +172,7 @@ impl SecurityQuestionAnswerAndSalt {
     ///     },
     /// )?;
     ///
-    /// assert_eq!(qa_salt.answer, "My answer");
+    /// assert_eq!(qa_salt.answer, "My answer".into());
     /// # Ok::<(), svar_core::Error>(())
     /// ```
     ///
@@ -187,9 +198,10 @@ impl SecurityQuestionAnswerAndSalt {
     ///
     /// # Errors
     ///
-    /// Returns an error if the question is not of kind
-    /// [`SecurityQuestionKind::Freeform`]. Currently, this method only
-    /// supports freeform questions.
+    /// Returns [`Error::AnswerDoesNotMatchQuestionKind`] if the question is
+    /// not of kind [`SecurityQuestionKind::Freeform`], use
+    /// [`by_answering_structured`](Self::by_answering_structured) for
+    /// structured questions.
     ///
     /// # Security Notes
     ///
@@ -204,7 +216,11 @@ impl SecurityQuestionAnswerAndSalt {
             SecurityQuestionExpectedAnswerFormat,
         ) -> String,
     ) -> Result<Self> {
-        assert_eq!(question.kind, SecurityQuestionKind::Freeform); // unfailable
+        if question.kind != SecurityQuestionKind::Freeform {
+            return Err(Error::AnswerDoesNotMatchQuestionKind {
+                question_id: question.id,
+            });
+        }
         let answer = provide_answer(
             question.question.clone(),
             question.expected_answer_format.clone(),
@@ -216,7 +232,87 @@ impl SecurityQuestionAnswerAndSalt {
 
         Ok(Self {
             question,
-            answer,
+            answer: SecurityQuestionAnswer::from(answer),
+            salt: Exactly32Bytes::generate(),
+        })
+    }
+
+    /// Creates a new instance by answering a structured security question,
+    /// i.e. by selecting one entry in each dataset the question refers to.
+    ///
+    /// The closure receives the question text and the referenced datasets and
+    /// returns the index of the selected entry in each dataset, in the same
+    /// order as the datasets. A cryptographic salt is automatically
+    /// generated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svar_core::*;
+    ///
+    /// let question = SecurityQuestion::with_details(
+    ///     1,
+    ///     1,
+    ///     SecurityQuestionKind::Structured {
+    ///         datasets: vec![
+    ///             AnswerDatasetReference::new(1, 1), // cities
+    ///             AnswerDatasetReference::new(2, 1), // years
+    ///         ],
+    ///     },
+    ///     "Where and when did your parents meet?",
+    ///     SecurityQuestionExpectedAnswerFormat::new(
+    ///         "<CITY>, <YEAR>",
+    ///         "London, 1973",
+    ///     ),
+    /// );
+    ///
+    /// let qa_salt = SecurityQuestionAnswerAndSalt::by_answering_structured(
+    ///     question,
+    ///     |_question_text, datasets| {
+    ///         assert_eq!(datasets.len(), 2);
+    ///         vec![42, 73]
+    ///     },
+    /// )?;
+    ///
+    /// assert_eq!(
+    ///     qa_salt.answer,
+    ///     SecurityQuestionAnswer::Structured(vec![42, 73])
+    /// );
+    /// # Ok::<(), svar_core::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - [`AnswerDoesNotMatchQuestionKind`](Error::AnswerDoesNotMatchQuestionKind):
+    ///   the question is not of kind [`SecurityQuestionKind::Structured`]
+    /// - [`InvalidStructuredAnswerSelectionCount`](Error::InvalidStructuredAnswerSelectionCount):
+    ///   not exactly one selection per dataset was provided
+    pub fn by_answering_structured(
+        question: SecurityQuestion,
+        provide_selections: impl FnOnce(
+            String,
+            Vec<AnswerDatasetReference>,
+        ) -> Vec<u16>,
+    ) -> Result<Self> {
+        let SecurityQuestionKind::Structured { datasets } = &question.kind
+        else {
+            return Err(Error::AnswerDoesNotMatchQuestionKind {
+                question_id: question.id,
+            });
+        };
+        let selections =
+            provide_selections(question.question.clone(), datasets.clone());
+
+        if selections.len() != datasets.len() {
+            return Err(Error::InvalidStructuredAnswerSelectionCount {
+                expected: datasets.len(),
+                found: selections.len(),
+            });
+        }
+
+        Ok(Self {
+            question,
+            answer: SecurityQuestionAnswer::Structured(selections),
             salt: Exactly32Bytes::generate(),
         })
     }
@@ -260,7 +356,7 @@ impl SecurityQuestionAnswerAndSalt {
     /// // Later, when user provides answer again:
     /// let reconstructed = SecurityQuestionAnswerAndSalt {
     ///     question: storable.question,
-    ///     answer: "user provided answer".to_string(),
+    ///     answer: "user provided answer".into(),
     ///     salt: storable.salt,
     /// };
     /// ```
@@ -278,11 +374,23 @@ impl SecurityQuestionAnswerAndSalt {
     }
 }
 
+#[cfg(test)]
+impl SecurityQuestionAnswerAndSalt {
+    /// A sample answer to [`SecurityQuestion::sample_structured`].
+    pub(crate) fn sample_structured() -> Self {
+        Self {
+            question: SecurityQuestion::sample_structured(),
+            answer: SecurityQuestionAnswer::Structured(vec![42, 73]),
+            salt: Exactly32Bytes::sample_cafe(),
+        }
+    }
+}
+
 impl HasSampleValues for SecurityQuestionAnswerAndSalt {
     fn sample() -> Self {
         Self {
             question: SecurityQuestion::first_concert(),
-            answer: "Jean-Michel Jarre, Paris La Défense, 1990".to_owned(),
+            answer: "Jean-Michel Jarre, Paris La Défense, 1990".into(),
             salt: Exactly32Bytes::sample_aced(),
         }
     }
@@ -290,7 +398,7 @@ impl HasSampleValues for SecurityQuestionAnswerAndSalt {
     fn sample_other() -> Self {
         Self {
             question: SecurityQuestion::stuffed_animal(),
-            answer: "Oinky piggy pig".to_owned(),
+            answer: "Oinky piggy pig".into(),
             salt: Exactly32Bytes::sample_babe(),
         }
     }
@@ -324,7 +432,7 @@ mod tests {
         )
         .expect("Should have been able to answer freeform question");
         assert_eq!(qa.question, question);
-        assert_eq!(qa.answer, answer);
+        assert_eq!(qa.answer, answer.clone().into());
 
         let second = SecurityQuestionAnswerAndSalt::by_answering_freeform(
             question.clone(),
@@ -336,4 +444,101 @@ mod tests {
         assert_eq!(qa.answer, second.answer);
         assert_ne!(qa.salt, second.salt);
     }
+
+    #[test]
+    fn by_answering_freeform_fails_for_structured_question() {
+        let question = SecurityQuestion::sample_structured();
+        let result = Sut::by_answering_freeform(question.clone(), |_, _| {
+            "London, 1973".to_owned()
+        });
+        assert_eq!(
+            result,
+            Err(Error::AnswerDoesNotMatchQuestionKind {
+                question_id: question.id
+            })
+        );
+    }
+
+    #[test]
+    fn test_by_answering_structured() {
+        let question = SecurityQuestion::sample_structured();
+        let qa =
+            Sut::by_answering_structured(question.clone(), |_, datasets| {
+                assert_eq!(
+                    datasets,
+                    vec![
+                        AnswerDatasetReference::sample(),
+                        AnswerDatasetReference::sample_other()
+                    ]
+                );
+                vec![42, 73]
+            })
+            .unwrap();
+        assert_eq!(qa.question, question);
+        assert_eq!(qa.answer, SecurityQuestionAnswer::Structured(vec![42, 73]));
+    }
+
+    #[test]
+    fn by_answering_structured_fails_for_freeform_question() {
+        let question = SecurityQuestion::sample();
+        let result =
+            Sut::by_answering_structured(question.clone(), |_, _| vec![42]);
+        assert_eq!(
+            result,
+            Err(Error::AnswerDoesNotMatchQuestionKind {
+                question_id: question.id
+            })
+        );
+    }
+
+    #[test]
+    fn by_answering_structured_fails_for_wrong_selection_count() {
+        let result = Sut::by_answering_structured(
+            SecurityQuestion::sample_structured(),
+            |_, _| vec![42],
+        );
+        assert_eq!(
+            result,
+            Err(Error::InvalidStructuredAnswerSelectionCount {
+                expected: 2,
+                found: 1
+            })
+        );
+    }
+
+    #[test]
+    fn formatting_does_not_reveal_answer() {
+        // The sample answers are the example answers of their questions, so
+        // use answers which appear nowhere else
+        for (sut, answer) in [
+            (
+                Sut {
+                    answer: "Zyzzyva".into(),
+                    ..Sut::sample()
+                },
+                "Zyzzyva",
+            ),
+            (
+                Sut {
+                    answer: SecurityQuestionAnswer::Structured(vec![4242]),
+                    ..Sut::sample_structured()
+                },
+                "4242",
+            ),
+        ] {
+            for formatted in
+                [sut.to_string(), format!("{sut:?}"), format!("{sut:#?}")]
+            {
+                assert!(!formatted.contains(answer), "{formatted}");
+            }
+        }
+    }
+
+    #[test]
+    fn json_roundtrip_structured() {
+        let sut = Sut::sample_structured();
+        let json = serde_json::to_string(&sut).unwrap();
+        let deserialized: Sut = serde_json::from_str(&json).unwrap();
+        assert_eq!(sut, deserialized);
+    }
 }
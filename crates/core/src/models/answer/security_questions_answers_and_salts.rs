@@ -32,7 +32,7 @@ use crate::prelude::*;
 ///         SecurityQuestionAnswerAndSalt::sample_other(),
 ///         SecurityQuestionAnswerAndSalt {
 ///             question: SecurityQuestion::sample(),
-///             answer: "My custom answer".to_string(),
+///             answer: "My custom answer".into(),
 ///             salt: Exactly32Bytes::sample(),
 ///         },
 ///     ])?;
@@ -65,9 +65,10 @@ use crate::prelude::*;
 /// // Access via indexing (implements Deref)
 /// let first_qa = &qa_set[0];
 /// println!("Question: {}", first_qa.question.question);
-/// println!("Answer: {}", first_qa.answer);
+/// assert!(first_qa.answer.as_freeform().is_some());
 ///
-/// // Iterate over all questions and answers
+/// // Iterate over all questions and answers, answers are redacted when
+/// // formatted
 /// for qa in qa_set.iter() {
 ///     println!("Q: {} A: {}", qa.question.question, qa.answer);
 /// }
@@ -243,32 +244,32 @@ impl SecurityQuestionsAnswersAndSalts<6> {
         Self::try_from_iter([
             QA {
                 question: Q::failed_exam(),
-                answer: "Wrong answer".to_owned(),
+                answer: "Wrong answer".into(),
                 salt: Exactly32Bytes::sample_aced(),
             },
             QA {
                 question: Q::parents_met(),
-                answer: "Wrong answer".to_owned(),
+                answer: "Wrong answer".into(),
                 salt: Exactly32Bytes::sample_babe(),
             },
             QA {
                 question: Q::first_concert(),
-                answer: "Wrong answer".to_owned(),
+                answer: "Wrong answer".into(),
                 salt: Exactly32Bytes::sample_cafe(),
             },
             QA {
                 question: Q::first_kiss_whom(),
-                answer: "Wrong answer".to_owned(),
+                answer: "Wrong answer".into(),
                 salt: Exactly32Bytes::sample_dead(),
             },
             QA {
                 question: Q::first_kiss_location(),
-                answer: "Wrong answer".to_owned(),
+                answer: "Wrong answer".into(),
                 salt: Exactly32Bytes::sample_ecad(),
             },
             QA {
                 question: Q::spouse_met(),
-                answer: "Wrong answer".to_owned(),
+                answer: "Wrong answer".into(),
                 salt: Exactly32Bytes::sample_fade(),
             },
         ])
@@ -283,32 +284,32 @@ impl HasSampleValues for SecurityQuestionsAnswersAndSalts<6> {
         Self::try_from_iter([
             QA {
                 question: Q::failed_exam(),
-                answer: "MIT, year 4, Python".to_owned(),
+                answer: "MIT, year 4, Python".into(),
                 salt: Exactly32Bytes::sample_aced(),
             },
             QA {
                 question: Q::parents_met(),
-                answer: "London, 1973".to_owned(),
+                answer: "London, 1973".into(),
                 salt: Exactly32Bytes::sample_babe(),
             },
             QA {
                 question: Q::first_concert(),
-                answer: "Jean-Michel Jarre, Paris La Défense, 1990".to_owned(),
+                answer: "Jean-Michel Jarre, Paris La Défense, 1990".into(),
                 salt: Exactly32Bytes::sample_cafe(),
             },
             QA {
                 question: Q::first_kiss_whom(),
-                answer: "John Doe".to_owned(),
+                answer: "John Doe".into(),
                 salt: Exactly32Bytes::sample_dead(),
             },
             QA {
                 question: Q::first_kiss_location(),
-                answer: "Behind the shed in the oak tree forrest.".to_owned(),
+                answer: "Behind the shed in the oak tree forrest.".into(),
                 salt: Exactly32Bytes::sample_ecad(),
             },
             QA {
                 question: Q::spouse_met(),
-                answer: "Tokyo, 1989".to_owned(),
+                answer: "Tokyo, 1989".into(),
                 salt: Exactly32Bytes::sample_fade(),
             },
         ])
@@ -321,32 +322,32 @@ impl HasSampleValues for SecurityQuestionsAnswersAndSalts<6> {
         Self::try_from_iter([
             QA {
                 question: Q::child_middle_name(),
-                answer: "Joe".to_owned(),
+                answer: "Joe".into(),
                 salt: Exactly32Bytes::sample_aced(),
             },
             QA {
                 question: Q::stuffed_animal(),
-                answer: "Bobby".to_owned(),
+                answer: "Bobby".into(),
                 salt: Exactly32Bytes::sample_babe(),
             },
             QA {
                 question: Q::oldest_cousin(),
-                answer: "Roxanne".to_owned(),
+                answer: "Roxanne".into(),
                 salt: Exactly32Bytes::sample_cafe(),
             },
             QA {
                 question: Q::teacher_grade3(),
-                answer: "Ali".to_owned(),
+                answer: "Ali".into(),
                 salt: Exactly32Bytes::sample_dead(),
             },
             QA {
                 question: Q::applied_uni_no_attend(),
-                answer: "Oxford".to_owned(),
+                answer: "Oxford".into(),
                 salt: Exactly32Bytes::sample_ecad(),
             },
             QA {
                 question: Q::first_school(),
-                answer: "Hogwartz".to_owned(),
+                answer: "Hogwartz".into(),
                 salt: Exactly32Bytes::sample_fade(),
             },
         ])
@@ -377,7 +378,20 @@ mod tests {
         let wrong = Sut::sample_wrong_answers();
         assert_eq!(wrong.0.len(), 6);
         for qa in &wrong.0 {
-            assert_eq!(qa.answer, "Wrong answer");
+            assert_eq!(qa.answer, "Wrong answer".into());
+        }
+    }
+
+    #[test]
+    fn formatting_does_not_reveal_answers() {
+        let mut sut = Sut::sample();
+        for (i, qa) in sut.iter_mut().enumerate() {
+            qa.answer = format!("Zyzzyva {i}").into();
+        }
+        for formatted in
+            [sut.to_string(), format!("{sut:?}"), format!("{sut:#?}")]
+        {
+            assert!(!formatted.contains("Zyzzyva"), "{formatted}");
         }
     }
 
@@ -0,0 +1,154 @@
+use crate::prelude::*;
+
+/// A report of which answers to the security questions of a sealed secret
+/// were correct, returned by [`SecurityQuestionsSealed::open_with_report`].
+///
+/// The analysis is derived from which combinations of answers decrypted the
+/// secret and which did not: every answer of a combination which decrypted
+/// the secret is correct, and an answer in a combination which failed even
+/// though all other answers of it are correct is incorrect.
+///
+/// # Examples
+///
+/// ```
+/// use svar_core::*;
+///
+/// let sealed = SecurityQuestionsSealed::<String, 6, 4>::sample();
+/// let mut answers = SecurityQuestionsAnswersAndSalts::sample();
+/// answers[1].answer = "forgotten".into();
+///
+/// let (_, report) = sealed.open_with_report(answers.iter().cloned())?;
+/// assert_eq!(
+///     report.incorrect_question_ids(),
+///     vec![answers[1].question.id]
+/// );
+/// assert_eq!(report.correct_question_ids().len(), 5);
+/// # Ok::<(), svar_core::Error>(())
+/// ```
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct SecurityQuestionsAnswersReport {
+    /// The analysis of the answer to each question, keyed by question id, in
+    /// the order the questions were sealed in.
+    pub analysis_by_question_id: IndexMap<u16, SecurityQuestionAnswerAnalysis>,
+}
+
+impl SecurityQuestionsAnswersReport {
+    /// Analyses the answers to the questions with ids `question_ids`, given
+    /// the combinations of answered question ids which decrypted the secret
+    /// (`successful`) and those which did not (`failed`).
+    ///
+    /// Questions which are part of neither a successful nor a failed
+    /// combination are
+    /// [`NotProvided`](SecurityQuestionAnswerAnalysis::NotProvided).
+    pub fn new<'a>(
+        question_ids: impl IntoIterator<Item = u16>,
+        successful: impl IntoIterator<Item = &'a Vec<u16>>,
+        failed: impl IntoIterator<Item = &'a Vec<u16>>,
+    ) -> Self {
+        let correct = successful.into_iter().flatten().collect::<IndexSet<_>>();
+        let failed = failed.into_iter().collect_vec();
+
+        let analysis_by_question_id = question_ids
+            .into_iter()
+            .map(|question_id| {
+                let analysis = if correct.contains(&question_id) {
+                    SecurityQuestionAnswerAnalysis::Correct
+                } else if failed.iter().any(|combination| {
+                    combination.contains(&question_id)
+                        && combination
+                            .iter()
+                            .filter(|id| **id != question_id)
+                            .all(|id| correct.contains(id))
+                }) {
+                    SecurityQuestionAnswerAnalysis::Incorrect
+                } else if failed
+                    .iter()
+                    .any(|combination| combination.contains(&question_id))
+                {
+                    SecurityQuestionAnswerAnalysis::Ambiguous
+                } else {
+                    SecurityQuestionAnswerAnalysis::NotProvided
+                };
+                (question_id, analysis)
+            })
+            .collect();
+
+        Self {
+            analysis_by_question_id,
+        }
+    }
+
+    fn question_ids_with(
+        &self,
+        analysis: SecurityQuestionAnswerAnalysis,
+    ) -> Vec<u16> {
+        self.analysis_by_question_id
+            .iter()
+            .filter(|(_, a)| **a == analysis)
+            .map(|(id, _)| *id)
+            .collect()
+    }
+
+    /// The ids of the questions which were provably answered correctly.
+    pub fn correct_question_ids(&self) -> Vec<u16> {
+        self.question_ids_with(SecurityQuestionAnswerAnalysis::Correct)
+    }
+
+    /// The ids of the questions which were provably answered incorrectly.
+    pub fn incorrect_question_ids(&self) -> Vec<u16> {
+        self.question_ids_with(SecurityQuestionAnswerAnalysis::Incorrect)
+    }
+
+    /// The ids of the questions for which the answer could neither be proven
+    /// correct nor incorrect.
+    pub fn ambiguous_question_ids(&self) -> Vec<u16> {
+        self.question_ids_with(SecurityQuestionAnswerAnalysis::Ambiguous)
+    }
+
+    /// The ids of the questions which were not answered.
+    pub fn not_provided_question_ids(&self) -> Vec<u16> {
+        self.question_ids_with(SecurityQuestionAnswerAnalysis::NotProvided)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Sut = SecurityQuestionsAnswersReport;
+
+    #[test]
+    fn all_correct() {
+        let sut =
+            Sut::new([1, 2, 3], &[vec![1, 2], vec![1, 3], vec![2, 3]], &[]);
+        assert_eq!(sut.correct_question_ids(), vec![1, 2, 3]);
+        assert!(sut.incorrect_question_ids().is_empty());
+    }
+
+    #[test]
+    fn one_incorrect() {
+        let sut = Sut::new([1, 2, 3], &[vec![1, 3]], &[vec![1, 2], vec![2, 3]]);
+        assert_eq!(sut.correct_question_ids(), vec![1, 3]);
+        assert_eq!(sut.incorrect_question_ids(), vec![2]);
+    }
+
+    #[test]
+    fn not_provided() {
+        let sut = Sut::new([1, 2, 3, 4], &[vec![1, 3]], &[]);
+        assert_eq!(sut.not_provided_question_ids(), vec![2, 4]);
+    }
+
+    #[test]
+    fn ambiguous_when_never_combined_with_only_correct_answers() {
+        let sut = Sut::new([1, 2, 3, 4], &[], &[vec![1, 2], vec![3, 4]]);
+        assert_eq!(sut.ambiguous_question_ids(), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        let sut = Sut::new([1, 2, 3], &[vec![1, 3]], &[vec![1, 2]]);
+        let json = serde_json::to_string(&sut).unwrap();
+        let deserialized: Sut = serde_json::from_str(&json).unwrap();
+        assert_eq!(sut, deserialized);
+    }
+}
@@ -0,0 +1,403 @@
+use crate::prelude::*;
+
+use rand::{RngCore, rngs::OsRng};
+use sha2::{Digest, Sha256};
+use std::sync::LazyLock;
+use zeroize::ZeroizeOnDrop;
+
+/// The BIP39 English wordlist, one word per line in alphabetical order.
+const ENGLISH_WORDLIST: &str = include_str!("english.txt");
+
+/// The 2048 words of [`ENGLISH_WORDLIST`], indexed by their 11 bits value.
+static ENGLISH_WORDS: LazyLock<Vec<&'static str>> =
+    LazyLock::new(|| ENGLISH_WORDLIST.lines().collect());
+
+/// A BIP39 mnemonic using the English wordlist, the typical secret to seal,
+/// e.g. the seed phrase of a cryptocurrency wallet.
+///
+/// Only the entropy is stored, from which the words are derived, so that it
+/// can be sealed as 17 to 33 bytes instead of the 50 to 200 bytes of the
+/// phrase: the entropy followed by one checksum byte, which is validated
+/// when the mnemonic is restored from bytes.
+///
+/// The entropy is zeroized when dropped, and [`Debug`] only shows the word
+/// count.
+///
+/// # Examples
+///
+/// ```
+/// use svar_core::*;
+///
+/// let mnemonic: Bip39Mnemonic =
+///     "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo wrong".parse()?;
+/// assert_eq!(mnemonic.word_count(), Bip39WordCount::Twelve);
+/// assert_eq!(mnemonic.entropy(), [0xff; 16]);
+///
+/// let questions = SecurityQuestionsAnswersAndSalts::sample();
+/// let sealed = SecurityQuestionsSealed::<Bip39Mnemonic, 6, 4>::seal(
+///     mnemonic.clone(),
+///     questions.clone(),
+/// )?;
+/// assert_eq!(sealed.open(questions)?, mnemonic);
+/// # Ok::<(), svar_core::Error>(())
+/// ```
+#[derive(Zeroize, ZeroizeOnDrop, Clone, PartialEq, Eq, Hash)]
+pub struct Bip39Mnemonic {
+    /// 16, 20, 24, 28 or 32 bytes.
+    entropy: Vec<u8>,
+}
+
+impl Bip39Mnemonic {
+    /// The SHA-256 digest of the entropy, the first bits of which are the
+    /// checksum.
+    fn checksum(entropy: &[u8]) -> u8 {
+        Sha256::digest(entropy)[0]
+    }
+
+    fn invalid(underlying: impl Into<String>) -> Error {
+        Error::InvalidMnemonicPhrase {
+            underlying: underlying.into(),
+        }
+    }
+
+    /// Creates a mnemonic from 16, 20, 24, 28 or 32 bytes of `entropy`.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`Error::InvalidMnemonicPhrase`] if `entropy` has any other
+    /// length.
+    pub fn from_entropy(entropy: impl AsRef<[u8]>) -> Result<Self> {
+        let entropy = entropy.as_ref();
+        Bip39WordCount::from_entropy_byte_count(entropy.len())?;
+        Ok(Self {
+            entropy: entropy.to_vec(),
+        })
+    }
+
+    /// Generates a new mnemonic of `word_count` words using a CSPRNG.
+    pub fn generate(word_count: Bip39WordCount) -> Self {
+        let mut entropy = vec![0u8; word_count.entropy_byte_count()];
+        OsRng.fill_bytes(&mut entropy);
+        Self { entropy }
+    }
+
+    /// Parses a mnemonic from its words separated by whitespace, ignoring
+    /// case.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`Error::InvalidMnemonicPhrase`] if the number of words is
+    /// not 12, 15, 18, 21 or 24, if a word is not in the English wordlist or
+    /// if the checksum is invalid.
+    pub fn from_phrase(phrase: impl AsRef<str>) -> Result<Self> {
+        let words = phrase.as_ref().split_whitespace().collect_vec();
+        let word_count = Bip39WordCount::from_word_count(words.len())?;
+
+        // Big endian bits of the entropy followed by the checksum
+        let mut bits = Vec::<bool>::with_capacity(words.len() * 11);
+        for (index, word) in words.iter().enumerate() {
+            let word = word.to_lowercase();
+            let value = ENGLISH_WORDS
+                .binary_search(&word.as_str())
+                .map_err(|_| {
+                    Self::invalid(format!(
+                        "Word at index {index} is not in the BIP39 English wordlist"
+                    ))
+                })?;
+            bits.extend((0..11).rev().map(|bit| (value >> bit) & 1 == 1));
+        }
+
+        let (entropy_bits, checksum_bits) =
+            bits.split_at(word_count.entropy_byte_count() * 8);
+        let entropy = entropy_bits
+            .chunks(8)
+            .map(|byte| byte.iter().fold(0u8, |acc, bit| acc << 1 | *bit as u8))
+            .collect_vec();
+        let mnemonic = Self { entropy };
+        let is_checksum_valid = mnemonic.checksum_bits() == checksum_bits;
+        bits.zeroize();
+
+        if !is_checksum_valid {
+            return Err(Self::invalid("Invalid checksum"));
+        }
+        Ok(mnemonic)
+    }
+
+    /// The checksum bits following the entropy bits in the words.
+    fn checksum_bits(&self) -> Vec<bool> {
+        let checksum = Self::checksum(&self.entropy);
+        (0..self.word_count().checksum_bit_count())
+            .map(|bit| (checksum >> (7 - bit)) & 1 == 1)
+            .collect()
+    }
+
+    /// The entropy the words encode.
+    pub fn entropy(&self) -> &[u8] {
+        &self.entropy
+    }
+
+    /// The number of words.
+    pub fn word_count(&self) -> Bip39WordCount {
+        Bip39WordCount::from_entropy_byte_count(self.entropy.len())
+            .expect("Entropy length is validated on creation")
+    }
+
+    /// The words of the mnemonic.
+    pub fn words(&self) -> Vec<&'static str> {
+        let mut bits = self
+            .entropy
+            .iter()
+            .flat_map(|byte| {
+                (0..8).rev().map(move |bit| (byte >> bit) & 1 == 1)
+            })
+            .collect_vec();
+        bits.extend(self.checksum_bits());
+
+        let words = bits
+            .chunks(11)
+            .map(|word_bits| {
+                let value = word_bits
+                    .iter()
+                    .fold(0usize, |acc, bit| acc << 1 | *bit as usize);
+                ENGLISH_WORDS[value]
+            })
+            .collect();
+        bits.zeroize();
+        words
+    }
+
+    /// The words of the mnemonic separated by a single space.
+    pub fn phrase(&self) -> String {
+        self.words().join(" ")
+    }
+}
+
+impl FromStr for Bip39Mnemonic {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::from_phrase(s)
+    }
+}
+
+impl std::fmt::Debug for Bip39Mnemonic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Bip39Mnemonic({})", self.word_count())
+    }
+}
+
+/// Sealed as its entropy followed by the first byte of the SHA-256 digest of
+/// the entropy, e.g. 33 bytes for 24 words.
+impl IsSecret for Bip39Mnemonic {
+    /// Fails with [`Error::InvalidMnemonicPhrase`] if the length of the
+    /// entropy is invalid or if the checksum byte does not match it.
+    fn from_bytes(
+        bytes: SecretBytes,
+    ) -> std::result::Result<Self, Box<dyn std::error::Error>> {
+        let Some((checksum, entropy)) = bytes.as_ref().split_last() else {
+            return Err(Self::invalid("Expected entropy and checksum").into());
+        };
+        let mnemonic = Self::from_entropy(entropy)?;
+        if Self::checksum(mnemonic.entropy()) != *checksum {
+            return Err(Self::invalid("Invalid checksum").into());
+        }
+        Ok(mnemonic)
+    }
+
+    fn to_bytes(
+        &self,
+    ) -> std::result::Result<SecretBytes, Box<dyn std::error::Error>> {
+        // Allocate room for the checksum up front, so that pushing it does
+        // not reallocate and leave a copy of the entropy behind
+        let mut bytes = Vec::with_capacity(self.entropy.len() + 1);
+        bytes.extend_from_slice(&self.entropy);
+        bytes.push(Self::checksum(&self.entropy));
+        Ok(SecretBytes::from(bytes))
+    }
+}
+
+impl HasSampleValues for Bip39Mnemonic {
+    fn sample() -> Self {
+        Self::from_phrase(
+            "hamster diagram private dutch cause delay private meat slide toddler razor book happy fancy gospel tennis maple dilemma loan word shrug inflict delay length",
+        )
+        .unwrap()
+    }
+
+    fn sample_other() -> Self {
+        Self::from_phrase(
+            "legal winner thank year wave sausage worth useful legal winner thank yellow",
+        )
+        .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Sut = Bip39Mnemonic;
+
+    #[test]
+    fn equality() {
+        assert_eq!(Sut::sample(), Sut::sample());
+        assert_eq!(Sut::sample_other(), Sut::sample_other());
+        assert_ne!(Sut::sample(), Sut::sample_other());
+    }
+
+    #[test]
+    fn wordlist() {
+        assert_eq!(ENGLISH_WORDS.len(), 2048);
+        assert!(ENGLISH_WORDS.is_sorted());
+        assert_eq!(ENGLISH_WORDS.first(), Some(&"abandon"));
+        assert_eq!(ENGLISH_WORDS.last(), Some(&"zoo"));
+    }
+
+    /// Test vectors from the BIP39 reference implementation.
+    #[test]
+    fn test_vectors() {
+        let vectors = [
+            (
+                "00000000000000000000000000000000",
+                "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            ),
+            (
+                "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+                "legal winner thank year wave sausage worth useful legal winner thank yellow",
+            ),
+            (
+                "80808080808080808080808080808080",
+                "letter advice cage absurd amount doctor acoustic avoid letter advice cage above",
+            ),
+            (
+                "ffffffffffffffffffffffffffffffff",
+                "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo wrong",
+            ),
+            (
+                "9e885d952ad362caeb4efe34a8e91bd2",
+                "ozone drill grab fiber curtain grace pudding thank cruise elder eight picnic",
+            ),
+            (
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art",
+            ),
+            (
+                "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+                "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo vote",
+            ),
+            (
+                "68a79eaca2324873eacc50cb9c6eca8cc68ea5d936f98787c60c7ebc74e6ce7c",
+                "hamster diagram private dutch cause delay private meat slide toddler razor book happy fancy gospel tennis maple dilemma loan word shrug inflict delay length",
+            ),
+        ];
+        for (entropy, phrase) in vectors {
+            let sut = Sut::from_entropy(hex_decode(entropy).unwrap()).unwrap();
+            assert_eq!(sut.phrase(), phrase);
+            assert_eq!(Sut::from_phrase(phrase).unwrap(), sut);
+        }
+    }
+
+    #[test]
+    fn from_phrase_ignores_case_and_whitespace() {
+        let sut = Sut::from_phrase(
+            "  Legal WINNER thank year wave sausage\nworth useful legal winner thank yellow ",
+        )
+        .unwrap();
+        assert_eq!(sut, Sut::sample_other());
+    }
+
+    #[test]
+    fn from_phrase_invalid_checksum_is_err() {
+        assert_eq!(
+            Sut::from_phrase("zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo"),
+            Err(Error::InvalidMnemonicPhrase {
+                underlying: "Invalid checksum".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn from_phrase_unknown_word_is_err() {
+        assert_eq!(
+            Sut::from_phrase(
+                "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo wrongg"
+            ),
+            Err(Error::InvalidMnemonicPhrase {
+                underlying:
+                    "Word at index 11 is not in the BIP39 English wordlist"
+                        .to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn from_phrase_invalid_word_count_is_err() {
+        assert!(matches!(
+            Sut::from_phrase("zoo zoo zoo"),
+            Err(Error::InvalidMnemonicPhrase { .. })
+        ));
+    }
+
+    #[test]
+    fn from_entropy_invalid_length_is_err() {
+        assert!(matches!(
+            Sut::from_entropy([0u8; 17]),
+            Err(Error::InvalidMnemonicPhrase { .. })
+        ));
+    }
+
+    #[test]
+    fn generate() {
+        for word_count in Bip39WordCount::ALL {
+            let sut = Sut::generate(word_count);
+            assert_eq!(sut.word_count(), word_count);
+            assert_eq!(sut.words().len(), word_count.word_count());
+            assert_eq!(Sut::from_phrase(sut.phrase()).unwrap(), sut);
+        }
+        assert_ne!(
+            Sut::generate(Bip39WordCount::TwentyFour),
+            Sut::generate(Bip39WordCount::TwentyFour)
+        );
+    }
+
+    #[test]
+    fn secret_bytes_roundtrip() {
+        let bytes = Sut::sample().to_bytes().unwrap();
+        assert_eq!(bytes.len(), 33);
+        assert_eq!(Sut::from_bytes(bytes).unwrap(), Sut::sample());
+    }
+
+    #[test]
+    fn from_bytes_invalid_checksum_is_err() {
+        let mut bytes = Sut::sample().to_bytes().unwrap().to_vec();
+        bytes[0] ^= 1;
+        assert_eq!(
+            Sut::from_bytes(bytes.into()).unwrap_err().to_string(),
+            "Invalid mnemonic phrase: Invalid checksum"
+        );
+    }
+
+    #[test]
+    fn from_bytes_invalid_length_is_err() {
+        assert!(Sut::from_bytes(SecretBytes::from(vec![0u8; 16])).is_err());
+        assert!(Sut::from_bytes(SecretBytes::default()).is_err());
+    }
+
+    #[test]
+    fn debug_does_not_reveal_words() {
+        assert_eq!(format!("{:?}", Sut::sample()), "Bip39Mnemonic(24 words)");
+    }
+
+    #[test]
+    fn seal_open_roundtrip() {
+        let questions = SecurityQuestionsAnswersAndSalts::sample();
+        let sealed = SecurityQuestionsSealed::<Sut, 6, 4>::with_schemes(
+            Sut::sample(),
+            questions.clone(),
+            SecurityQuestionsKdfScheme::version1(),
+            EncryptionScheme::default(),
+        )
+        .unwrap();
+        assert_eq!(sealed.open(questions).unwrap(), Sut::sample());
+    }
+}
@@ -0,0 +1,145 @@
+use crate::prelude::*;
+
+/// The number of words of a [`Bip39Mnemonic`], each word encoding 11 bits of
+/// entropy and checksum.
+#[repr(u8)]
+#[derive(
+    Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Display,
+)]
+pub enum Bip39WordCount {
+    /// 12 words, 128 bits of entropy.
+    #[display("12 words")]
+    Twelve = 12,
+
+    /// 15 words, 160 bits of entropy.
+    #[display("15 words")]
+    Fifteen = 15,
+
+    /// 18 words, 192 bits of entropy.
+    #[display("18 words")]
+    Eighteen = 18,
+
+    /// 21 words, 224 bits of entropy.
+    #[display("21 words")]
+    TwentyOne = 21,
+
+    /// 24 words, 256 bits of entropy.
+    #[default]
+    #[display("24 words")]
+    TwentyFour = 24,
+}
+
+impl Bip39WordCount {
+    /// All word counts, from fewest to most words.
+    pub const ALL: [Self; 5] = [
+        Self::Twelve,
+        Self::Fifteen,
+        Self::Eighteen,
+        Self::TwentyOne,
+        Self::TwentyFour,
+    ];
+
+    /// The number of words.
+    pub fn word_count(&self) -> usize {
+        *self as usize
+    }
+
+    /// The byte count of the entropy encoded by this many words.
+    pub fn entropy_byte_count(&self) -> usize {
+        self.word_count() * 4 / 3
+    }
+
+    /// The number of bits of checksum encoded by this many words, one per 32
+    /// bits of entropy.
+    pub fn checksum_bit_count(&self) -> usize {
+        self.word_count() / 3
+    }
+
+    /// The word count of a mnemonic of `word_count` words.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`Error::InvalidMnemonicPhrase`] if `word_count` is not
+    /// one of 12, 15, 18, 21 or 24.
+    pub fn from_word_count(word_count: usize) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|c| c.word_count() == word_count)
+            .ok_or_else(|| Error::InvalidMnemonicPhrase {
+                underlying: format!(
+                    "Expected 12, 15, 18, 21 or 24 words, found {word_count}"
+                ),
+            })
+    }
+
+    /// The word count of a mnemonic with `entropy_byte_count` bytes of
+    /// entropy.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`Error::InvalidMnemonicPhrase`] if `entropy_byte_count`
+    /// is not one of 16, 20, 24, 28 or 32.
+    pub fn from_entropy_byte_count(entropy_byte_count: usize) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|c| c.entropy_byte_count() == entropy_byte_count)
+            .ok_or_else(|| Error::InvalidMnemonicPhrase {
+                underlying: format!(
+                    "Expected 16, 20, 24, 28 or 32 bytes of entropy, found {entropy_byte_count}"
+                ),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Sut = Bip39WordCount;
+
+    #[test]
+    fn default_is_24_words() {
+        assert_eq!(Sut::default().word_count(), 24);
+    }
+
+    #[test]
+    fn entropy_and_checksum() {
+        assert_eq!(
+            Sut::ALL
+                .iter()
+                .map(|c| (c.entropy_byte_count(), c.checksum_bit_count()))
+                .collect_vec(),
+            vec![(16, 4), (20, 5), (24, 6), (28, 7), (32, 8)]
+        );
+        for sut in Sut::ALL {
+            assert_eq!(
+                sut.word_count() * 11,
+                sut.entropy_byte_count() * 8 + sut.checksum_bit_count()
+            );
+        }
+    }
+
+    #[test]
+    fn from_word_count() {
+        for sut in Sut::ALL {
+            assert_eq!(Sut::from_word_count(sut.word_count()), Ok(sut));
+        }
+        assert!(Sut::from_word_count(13).is_err());
+    }
+
+    #[test]
+    fn from_entropy_byte_count() {
+        for sut in Sut::ALL {
+            assert_eq!(
+                Sut::from_entropy_byte_count(sut.entropy_byte_count()),
+                Ok(sut)
+            );
+        }
+        assert!(Sut::from_entropy_byte_count(17).is_err());
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Sut::Twelve.to_string(), "12 words");
+    }
+}
@@ -0,0 +1,5 @@
+mod bip39_mnemonic;
+mod bip39_word_count;
+
+pub use bip39_mnemonic::*;
+pub use bip39_word_count::*;
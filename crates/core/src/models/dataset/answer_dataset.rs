@@ -0,0 +1,387 @@
+use crate::prelude::*;
+
+use std::path::Path;
+
+/// A versioned dataset of possible answers to a
+/// [`SecurityQuestionKind::Structured`] question, e.g. a list of video game
+/// consoles.
+///
+/// Datasets can depend on a parent dataset, forming a hierarchy, e.g.
+/// manufacturer → console → game, where every entry of the dependent dataset
+/// is a child of an entry in the parent dataset. The choices for the console
+/// are then the children of the selected manufacturer, see
+/// [`children`](Self::children).
+///
+/// The answer to a structured question is the index of the selected entry in
+/// each dataset, so the entries of a dataset MUST never change for a given
+/// `id` and `version`.
+///
+/// # Examples
+///
+/// ```
+/// use svar_core::*;
+///
+/// let consoles = AnswerDataset::from_json_str(
+///     r#"{
+///         "id": 2,
+///         "version": 1,
+///         "name": "Video game consoles",
+///         "parent": { "id": 1, "version": 1 },
+///         "entries": [
+///             { "value": "NES", "parent": 0 },
+///             { "value": "Mega Drive", "parent": 1 }
+///         ]
+///     }"#,
+/// )?;
+///
+/// let by_sega = consoles.children(Some(1)).collect::<Vec<_>>();
+/// assert_eq!(by_sega.len(), 1);
+/// assert_eq!(by_sega[0].0, 1);
+/// assert_eq!(by_sega[0].1.value, "Mega Drive");
+/// # Ok::<(), svar_core::Error>(())
+/// ```
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Hash, Debug)]
+#[serde(try_from = "UnvalidatedAnswerDataset")]
+pub struct AnswerDataset {
+    /// Unique identifier of the dataset.
+    pub id: u16,
+
+    /// Version of the dataset, bumped on any change of the entries.
+    pub version: u8,
+
+    /// Human readable name of the dataset, e.g. "Video game consoles".
+    pub name: String,
+
+    /// The dataset this dataset depends on, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent: Option<AnswerDatasetReference>,
+
+    /// The entries of the dataset, selections are indices into this list.
+    entries: Vec<AnswerDatasetEntry>,
+}
+
+#[derive(Deserialize)]
+struct UnvalidatedAnswerDataset {
+    id: u16,
+    version: u8,
+    name: String,
+    #[serde(default)]
+    parent: Option<AnswerDatasetReference>,
+    entries: Vec<AnswerDatasetEntry>,
+}
+
+impl TryFrom<UnvalidatedAnswerDataset> for AnswerDataset {
+    type Error = Error;
+
+    fn try_from(value: UnvalidatedAnswerDataset) -> Result<Self> {
+        Self::new(
+            value.id,
+            value.version,
+            value.name,
+            value.parent,
+            value.entries,
+        )
+    }
+}
+
+fn invalid(underlying: impl Into<String>) -> Error {
+    Error::InvalidAnswerDataset {
+        underlying: underlying.into(),
+    }
+}
+
+impl AnswerDataset {
+    /// Creates a new dataset, validating that it is non empty, that its
+    /// selections fit in a `u16`, that every entry has a parent if and only
+    /// if the dataset has a parent, and that no two siblings have the same
+    /// (case insensitive) value, which would make answers ambiguous.
+    pub fn new(
+        id: u16,
+        version: u8,
+        name: impl AsRef<str>,
+        parent: Option<AnswerDatasetReference>,
+        entries: impl IntoIterator<Item = AnswerDatasetEntry>,
+    ) -> Result<Self> {
+        let entries = entries.into_iter().collect_vec();
+        let reference = AnswerDatasetReference::new(id, version);
+
+        if entries.is_empty() {
+            return Err(invalid(format!("{reference} has no entries")));
+        }
+        if entries.len() > u16::MAX as usize + 1 {
+            return Err(invalid(format!(
+                "{reference} has too many entries: {}",
+                entries.len()
+            )));
+        }
+        if parent == Some(reference) {
+            return Err(invalid(format!("{reference} is its own parent")));
+        }
+        if let Some(entry) = entries
+            .iter()
+            .find(|entry| entry.parent.is_some() != parent.is_some())
+        {
+            return Err(invalid(format!(
+                "Entry '{entry}' of {reference} must have a parent if and only if the dataset has a parent"
+            )));
+        }
+        let mut siblings = IndexSet::new();
+        if let Some(entry) = entries.iter().find(|entry| {
+            !siblings.insert((entry.parent, entry.value.to_lowercase()))
+        }) {
+            return Err(invalid(format!(
+                "Entry '{entry}' of {reference} is ambiguous"
+            )));
+        }
+
+        Ok(Self {
+            id,
+            version,
+            name: name.as_ref().to_owned(),
+            parent,
+            entries,
+        })
+    }
+
+    /// Parses and validates a dataset from JSON.
+    pub fn from_json_str(json: impl AsRef<str>) -> Result<Self> {
+        serde_json::from_str(json.as_ref()).map_err(|e| invalid(e.to_string()))
+    }
+
+    /// Reads, parses and validates a dataset from a JSON file at `path`.
+    pub fn from_json_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path).map_err(|e| {
+            invalid(format!("Failed to read {}: {e}", path.display()))
+        })?;
+        Self::from_json_str(json)
+    }
+
+    /// A reference to this dataset, as used by structured questions.
+    pub fn reference(&self) -> AnswerDatasetReference {
+        AnswerDatasetReference::new(self.id, self.version)
+    }
+
+    /// All entries of the dataset, selections are indices into this slice.
+    pub fn entries(&self) -> &[AnswerDatasetEntry] {
+        &self.entries
+    }
+
+    /// The entry at index `selection`, if any.
+    pub fn entry(&self, selection: u16) -> Option<&AnswerDatasetEntry> {
+        self.entries.get(selection as usize)
+    }
+
+    /// The entries which are valid choices given the selection made in the
+    /// parent dataset, together with their selection index.
+    ///
+    /// Pass `None` for datasets without a parent, which returns all entries.
+    pub fn children(
+        &self,
+        parent_selection: Option<u16>,
+    ) -> impl Iterator<Item = (u16, &AnswerDatasetEntry)> {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter(move |(_, entry)| entry.parent == parent_selection)
+            .map(|(index, entry)| (index as u16, entry))
+    }
+
+    /// Like [`children`](Self::children) but only entries whose value
+    /// contains `query`, ignoring case. An empty query matches all children.
+    ///
+    /// Returns an iterator so that UIs can cheaply `take` the first matches
+    /// of large datasets.
+    pub fn search(
+        &self,
+        query: impl AsRef<str>,
+        parent_selection: Option<u16>,
+    ) -> impl Iterator<Item = (u16, &AnswerDatasetEntry)> {
+        let query = query.as_ref().to_lowercase();
+        self.children(parent_selection).filter(move |(_, entry)| {
+            entry.value.to_lowercase().contains(&query)
+        })
+    }
+}
+
+impl HasSampleValues for AnswerDataset {
+    /// Video game console manufacturers.
+    fn sample() -> Self {
+        Self::from_json_str(include_str!(
+            "fixtures/video_game_manufacturers.json"
+        ))
+        .expect("Valid sample")
+    }
+
+    /// Video game consoles, depending on [`AnswerDataset::sample`].
+    fn sample_other() -> Self {
+        Self::from_json_str(include_str!("fixtures/video_game_consoles.json"))
+            .expect("Valid sample")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Sut = AnswerDataset;
+
+    fn entry(value: &str) -> AnswerDatasetEntry {
+        AnswerDatasetEntry::new(value, None)
+    }
+
+    #[test]
+    fn equality() {
+        assert_eq!(Sut::sample(), Sut::sample());
+        assert_eq!(Sut::sample_other(), Sut::sample_other());
+    }
+
+    #[test]
+    fn inequality() {
+        assert_ne!(Sut::sample(), Sut::sample_other());
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        let sut = Sut::sample_other();
+        let json = serde_json::to_string(&sut).unwrap();
+        assert_eq!(Sut::from_json_str(json).unwrap(), sut);
+    }
+
+    #[test]
+    fn from_json_file() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/models/dataset/fixtures/video_games.json"
+        );
+        let sut = Sut::from_json_file(path).unwrap();
+        assert_eq!(sut.reference(), AnswerDatasetReference::new(3, 1));
+        assert_eq!(sut.parent, Some(AnswerDatasetReference::new(2, 1)));
+    }
+
+    #[test]
+    fn from_json_file_missing_is_err() {
+        let result = Sut::from_json_file("does/not/exist.json");
+        assert!(matches!(result, Err(Error::InvalidAnswerDataset { .. })));
+    }
+
+    #[test]
+    fn empty_is_err() {
+        let result = Sut::new(1, 1, "Empty", None, []);
+        assert!(matches!(result, Err(Error::InvalidAnswerDataset { .. })));
+    }
+
+    #[test]
+    fn own_parent_is_err() {
+        let result = Sut::new(
+            1,
+            1,
+            "Self",
+            Some(AnswerDatasetReference::new(1, 1)),
+            [AnswerDatasetEntry::new("a", 0)],
+        );
+        assert!(matches!(result, Err(Error::InvalidAnswerDataset { .. })));
+    }
+
+    #[test]
+    fn entry_without_parent_in_dependent_dataset_is_err() {
+        let result = Sut::new(
+            2,
+            1,
+            "Consoles",
+            Some(AnswerDatasetReference::new(1, 1)),
+            [AnswerDatasetEntry::new("NES", 0), entry("Mega Drive")],
+        );
+        assert!(matches!(result, Err(Error::InvalidAnswerDataset { .. })));
+    }
+
+    #[test]
+    fn entry_with_parent_in_root_dataset_is_err() {
+        let result = Sut::new(
+            1,
+            1,
+            "Manufacturers",
+            None,
+            [AnswerDatasetEntry::new("Nintendo", 0)],
+        );
+        assert!(matches!(result, Err(Error::InvalidAnswerDataset { .. })));
+    }
+
+    #[test]
+    fn ambiguous_siblings_is_err() {
+        let result = Sut::new(
+            1,
+            1,
+            "Manufacturers",
+            None,
+            [entry("Sega"), entry("SEGA")],
+        );
+        assert!(matches!(result, Err(Error::InvalidAnswerDataset { .. })));
+    }
+
+    #[test]
+    fn same_value_different_parents_is_ok() {
+        let result = Sut::new(
+            2,
+            1,
+            "Consoles",
+            Some(AnswerDatasetReference::new(1, 1)),
+            [
+                AnswerDatasetEntry::new("Classic", 0),
+                AnswerDatasetEntry::new("Classic", 1),
+            ],
+        );
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn too_many_entries_is_err() {
+        let result = Sut::new(
+            1,
+            1,
+            "Numbers",
+            None,
+            (0..=u16::MAX as usize + 1).map(|n| entry(&n.to_string())),
+        );
+        assert!(matches!(result, Err(Error::InvalidAnswerDataset { .. })));
+    }
+
+    #[test]
+    fn children_of_parent_selection() {
+        let sut = Sut::sample_other();
+        let nintendo = sut
+            .children(Some(0))
+            .map(|(index, entry)| (index, entry.value.as_str()))
+            .collect_vec();
+        assert_eq!(nintendo, vec![(0, "NES"), (1, "SNES"), (2, "Game Boy")]);
+        assert_eq!(sut.children(None).count(), 0);
+    }
+
+    #[test]
+    fn children_of_root_dataset() {
+        assert_eq!(Sut::sample().children(None).count(), 3);
+    }
+
+    #[test]
+    fn search_is_case_insensitive() {
+        let sut = Sut::sample_other();
+        let result = sut
+            .search("game", Some(0))
+            .map(|(index, _)| index)
+            .collect_vec();
+        assert_eq!(result, vec![2]);
+    }
+
+    #[test]
+    fn search_respects_parent_selection() {
+        let sut = Sut::sample_other();
+        assert_eq!(sut.search("m", Some(1)).count(), 2); // Master System, Mega Drive
+        assert_eq!(sut.search("mega", Some(0)).count(), 0);
+    }
+
+    #[test]
+    fn search_empty_query_matches_all_children() {
+        let sut = Sut::sample_other();
+        assert_eq!(sut.search("", Some(2)).count(), 1);
+    }
+}
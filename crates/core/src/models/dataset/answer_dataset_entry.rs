@@ -0,0 +1,52 @@
+use crate::prelude::*;
+
+/// An entry in an [`AnswerDataset`], e.g. "Nintendo" in a dataset of video
+/// game console manufacturers.
+#[derive(
+    Serialize, Deserialize, Clone, PartialEq, Eq, Hash, Debug, Display,
+)]
+#[display("{value}")]
+pub struct AnswerDatasetEntry {
+    /// The human readable value of this entry, presented to the user.
+    pub value: String,
+
+    /// The index of the entry in the parent dataset which this entry is a
+    /// child of, e.g. the index of "Nintendo" for the entry "NES". Present if
+    /// and only if the dataset has a parent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<u16>,
+}
+
+impl AnswerDatasetEntry {
+    pub fn new(value: impl AsRef<str>, parent: impl Into<Option<u16>>) -> Self {
+        Self {
+            value: value.as_ref().to_owned(),
+            parent: parent.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Sut = AnswerDatasetEntry;
+
+    #[test]
+    fn json_without_parent() {
+        let sut = Sut::new("Nintendo", None);
+        assert_eq!(
+            serde_json::to_string(&sut).unwrap(),
+            r#"{"value":"Nintendo"}"#
+        );
+    }
+
+    #[test]
+    fn json_roundtrip_with_parent() {
+        let sut = Sut::new("NES", 0);
+        let json = serde_json::to_string(&sut).unwrap();
+        assert_eq!(json, r#"{"value":"NES","parent":0}"#);
+        let deserialized: Sut = serde_json::from_str(&json).unwrap();
+        assert_eq!(sut, deserialized);
+    }
+}
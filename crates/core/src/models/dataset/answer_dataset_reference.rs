@@ -0,0 +1,54 @@
+use crate::prelude::*;
+
+/// A reference to a specific version of a dataset of possible answers, e.g.
+/// a list of countries, used by [`SecurityQuestionKind::Structured`]
+/// questions.
+///
+/// The answer to a structured question is a set of indices into the
+/// referenced datasets, so a dataset MUST never change for a given `id` and
+/// `version`, any change requires a new `version`.
+#[derive(
+    Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug, Display,
+)]
+#[display("AnswerDatasetReference(id: {id}, version: {version})")]
+pub struct AnswerDatasetReference {
+    /// Unique identifier of the dataset.
+    pub id: u16,
+
+    /// Version of the dataset identified by `id`.
+    pub version: u8,
+}
+
+impl AnswerDatasetReference {
+    pub fn new(id: u16, version: u8) -> Self {
+        Self { id, version }
+    }
+}
+
+impl HasSampleValues for AnswerDatasetReference {
+    fn sample() -> Self {
+        Self::new(1, 1)
+    }
+
+    fn sample_other() -> Self {
+        Self::new(2, 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Sut = AnswerDatasetReference;
+
+    #[test]
+    fn equality() {
+        assert_eq!(Sut::sample(), Sut::sample());
+        assert_eq!(Sut::sample_other(), Sut::sample_other());
+    }
+
+    #[test]
+    fn inequality() {
+        assert_ne!(Sut::sample(), Sut::sample_other());
+    }
+}
@@ -0,0 +1,541 @@
+use crate::prelude::*;
+
+use std::path::Path;
+
+/// A collection of [`AnswerDataset`]s, used to present the choices of
+/// [`SecurityQuestionKind::Structured`] questions and to validate structured
+/// answers before they are fed into a [`SecurityQuestionsKdfScheme`].
+///
+/// # Examples
+///
+/// ```
+/// use svar_core::*;
+///
+/// let datasets = AnswerDatasets::sample(); // manufacturer → console → game
+/// let question = SecurityQuestion::with_details(
+///     200,
+///     1,
+///     SecurityQuestionKind::Structured {
+///         datasets: datasets.references(),
+///     },
+///     "What was the first video game you owned?",
+///     SecurityQuestionExpectedAnswerFormat::new(
+///         "<MANUFACTURER>, <CONSOLE>, <GAME>",
+///         "Nintendo, NES, Super Mario Bros.",
+///     ),
+/// );
+///
+/// // The user picks "Sega" among all manufacturers...
+/// let manufacturers = datasets.choices(&question, &[])?;
+/// let (sega, _) = manufacturers.iter().find(|(_, e)| e.value == "Sega").unwrap();
+///
+/// // ... then searches for "mega" among the consoles made by Sega...
+/// let consoles = datasets.search_choices(&question, &[*sega], "mega")?;
+/// let (mega_drive, _) = consoles[0];
+///
+/// // ... and finally picks the only game made for the Mega Drive.
+/// let games = datasets.choices(&question, &[*sega, mega_drive])?;
+/// assert_eq!(games.len(), 1);
+/// let (sonic, _) = games[0];
+///
+/// let answer = SecurityQuestionAnswerAndSalt::by_answering_structured(
+///     question,
+///     |_, _| vec![*sega, mega_drive, sonic],
+/// )?;
+/// datasets.validate_answer(&answer)?;
+/// # Ok::<(), svar_core::Error>(())
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct AnswerDatasets(IndexMap<AnswerDatasetReference, AnswerDataset>);
+
+impl AnswerDatasets {
+    /// Creates a new collection, validating that datasets are unique and that
+    /// every parent dataset is present and every entry of a dependent dataset
+    /// refers to an existing entry of its parent.
+    pub fn new(
+        datasets: impl IntoIterator<Item = AnswerDataset>,
+    ) -> Result<Self> {
+        let mut map = IndexMap::new();
+        for dataset in datasets {
+            let reference = dataset.reference();
+            if map.insert(reference, dataset).is_some() {
+                return Err(Error::InvalidAnswerDataset {
+                    underlying: format!("Duplicate {reference}"),
+                });
+            }
+        }
+
+        for dataset in map.values() {
+            let Some(parent) = dataset.parent else {
+                continue;
+            };
+            let parent =
+                map.get(&parent).ok_or(Error::UnknownAnswerDataset {
+                    id: parent.id,
+                    version: parent.version,
+                })?;
+            if let Some(entry) = dataset.entries().iter().find(|entry| {
+                entry
+                    .parent
+                    .is_none_or(|selection| parent.entry(selection).is_none())
+            }) {
+                return Err(Error::InvalidAnswerDataset {
+                    underlying: format!(
+                        "Entry '{entry}' of {} refers to a non existing entry of its parent",
+                        dataset.reference()
+                    ),
+                });
+            }
+        }
+
+        Ok(Self(map))
+    }
+
+    /// Reads, parses and validates datasets from JSON files.
+    pub fn from_json_files(
+        paths: impl IntoIterator<Item = impl AsRef<Path>>,
+    ) -> Result<Self> {
+        paths
+            .into_iter()
+            .map(AnswerDataset::from_json_file)
+            .collect::<Result<Vec<_>>>()
+            .and_then(Self::new)
+    }
+
+    /// The references of all datasets, in insertion order.
+    pub fn references(&self) -> Vec<AnswerDatasetReference> {
+        self.0.keys().copied().collect()
+    }
+
+    /// The dataset referenced by `reference`.
+    pub fn get(
+        &self,
+        reference: &AnswerDatasetReference,
+    ) -> Result<&AnswerDataset> {
+        self.0.get(reference).ok_or(Error::UnknownAnswerDataset {
+            id: reference.id,
+            version: reference.version,
+        })
+    }
+
+    /// Validates a (possibly partial) path of `selections` in `datasets`,
+    /// returning the dataset following the last selection, if any.
+    fn validate_path<'a>(
+        &'a self,
+        datasets: &[AnswerDatasetReference],
+        selections: &[u16],
+    ) -> Result<Option<&'a AnswerDataset>> {
+        if selections.len() > datasets.len() {
+            return Err(Error::InvalidStructuredAnswerSelectionCount {
+                expected: datasets.len(),
+                found: selections.len(),
+            });
+        }
+
+        let mut previous: Option<(&AnswerDatasetReference, u16)> = None;
+        for (index, reference) in datasets.iter().enumerate() {
+            let dataset = self.get(reference)?;
+
+            let parent_selection = match (dataset.parent, previous) {
+                (None, _) => None,
+                (Some(parent), Some((previous_reference, selection)))
+                    if parent == *previous_reference =>
+                {
+                    Some(selection)
+                }
+                (Some(parent), _) => {
+                    return Err(Error::InvalidAnswerDataset {
+                        underlying: format!(
+                            "{reference} depends on {parent} which must directly precede it"
+                        ),
+                    });
+                }
+            };
+
+            let Some(&selection) = selections.get(index) else {
+                return Ok(Some(dataset));
+            };
+
+            let is_valid = dataset
+                .entry(selection)
+                .is_some_and(|entry| entry.parent == parent_selection);
+            if !is_valid {
+                return Err(Error::InvalidAnswerDatasetSelection {
+                    dataset_id: dataset.id,
+                    selection,
+                });
+            }
+            previous = Some((reference, selection));
+        }
+        Ok(None)
+    }
+
+    /// Validates a full path of `selections`, one per dataset in `datasets`,
+    /// checking that every selection exists and is a child of the previous
+    /// selection, if its dataset depends on the previous dataset.
+    pub fn validate_selections(
+        &self,
+        datasets: &[AnswerDatasetReference],
+        selections: &[u16],
+    ) -> Result<()> {
+        if selections.len() != datasets.len() {
+            return Err(Error::InvalidStructuredAnswerSelectionCount {
+                expected: datasets.len(),
+                found: selections.len(),
+            });
+        }
+        self.validate_path(datasets, selections).map(|_| ())
+    }
+
+    /// Validates the answer to a structured question, see
+    /// [`validate_selections`](Self::validate_selections). Freeform answers
+    /// to freeform questions are always valid.
+    pub fn validate_answer(
+        &self,
+        question_answer_and_salt: &SecurityQuestionAnswerAndSalt,
+    ) -> Result<()> {
+        let question = &question_answer_and_salt.question;
+        match (&question.kind, &question_answer_and_salt.answer) {
+            (
+                SecurityQuestionKind::Freeform,
+                SecurityQuestionAnswer::Freeform(_),
+            ) => Ok(()),
+            (
+                SecurityQuestionKind::Structured { datasets },
+                SecurityQuestionAnswer::Structured(selections),
+            ) => self.validate_selections(datasets, selections),
+            _ => Err(Error::AnswerDoesNotMatchQuestionKind {
+                question_id: question.id,
+            }),
+        }
+    }
+
+    /// The datasets referenced by a structured `question`.
+    fn datasets_of<'q>(
+        &self,
+        question: &'q SecurityQuestion,
+    ) -> Result<&'q [AnswerDatasetReference]> {
+        match &question.kind {
+            SecurityQuestionKind::Structured { datasets } => Ok(datasets),
+            SecurityQuestionKind::Freeform => {
+                Err(Error::AnswerDoesNotMatchQuestionKind {
+                    question_id: question.id,
+                })
+            }
+        }
+    }
+
+    /// The valid choices for the next part of the answer to a structured
+    /// `question`, given the selections made so far.
+    pub fn choices(
+        &self,
+        question: &SecurityQuestion,
+        selected: &[u16],
+    ) -> Result<Vec<(u16, &AnswerDatasetEntry)>> {
+        self.search_choices(question, selected, "")
+    }
+
+    /// Like [`choices`](Self::choices) but only entries whose value contains
+    /// `query`, ignoring case.
+    pub fn search_choices(
+        &self,
+        question: &SecurityQuestion,
+        selected: &[u16],
+        query: impl AsRef<str>,
+    ) -> Result<Vec<(u16, &AnswerDatasetEntry)>> {
+        let datasets = self.datasets_of(question)?;
+        let next = self.validate_path(datasets, selected)?.ok_or(
+            Error::InvalidStructuredAnswerSelectionCount {
+                expected: datasets.len(),
+                found: selected.len() + 1,
+            },
+        )?;
+        let parent_selection =
+            next.parent.and_then(|_| selected.last().copied());
+        Ok(next.search(query, parent_selection).collect())
+    }
+}
+
+impl HasSampleValues for AnswerDatasets {
+    /// Video game manufacturer → console → game.
+    fn sample() -> Self {
+        Self::new([
+            AnswerDataset::sample(),
+            AnswerDataset::sample_other(),
+            AnswerDataset::from_json_str(include_str!(
+                "fixtures/video_games.json"
+            ))
+            .expect("Valid sample"),
+        ])
+        .expect("Valid sample")
+    }
+
+    /// Only video game console manufacturers.
+    fn sample_other() -> Self {
+        Self::new([AnswerDataset::sample()]).expect("Valid sample")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Sut = AnswerDatasets;
+
+    fn manufacturers() -> AnswerDatasetReference {
+        AnswerDatasetReference::new(1, 1)
+    }
+    fn consoles() -> AnswerDatasetReference {
+        AnswerDatasetReference::new(2, 1)
+    }
+    fn games() -> AnswerDatasetReference {
+        AnswerDatasetReference::new(3, 1)
+    }
+
+    fn question() -> SecurityQuestion {
+        SecurityQuestion::with_details(
+            200,
+            1,
+            SecurityQuestionKind::Structured {
+                datasets: vec![manufacturers(), consoles(), games()],
+            },
+            "What was the first video game you owned?",
+            SecurityQuestionExpectedAnswerFormat::new(
+                "<MANUFACTURER>, <CONSOLE>, <GAME>",
+                "Nintendo, NES, Super Mario Bros.",
+            ),
+        )
+    }
+
+    fn values(choices: Vec<(u16, &AnswerDatasetEntry)>) -> Vec<&str> {
+        choices
+            .into_iter()
+            .map(|(_, entry)| entry.value.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn equality() {
+        assert_eq!(Sut::sample(), Sut::sample());
+        assert_eq!(Sut::sample_other(), Sut::sample_other());
+    }
+
+    #[test]
+    fn inequality() {
+        assert_ne!(Sut::sample(), Sut::sample_other());
+    }
+
+    #[test]
+    fn from_json_files() {
+        let dir =
+            concat!(env!("CARGO_MANIFEST_DIR"), "/src/models/dataset/fixtures");
+        let sut = Sut::from_json_files(
+            [
+                "video_game_manufacturers.json",
+                "video_game_consoles.json",
+                "video_games.json",
+            ]
+            .map(|file| format!("{dir}/{file}")),
+        )
+        .unwrap();
+        assert_eq!(sut, Sut::sample());
+    }
+
+    #[test]
+    fn duplicate_is_err() {
+        let result =
+            Sut::new([AnswerDataset::sample(), AnswerDataset::sample()]);
+        assert!(matches!(result, Err(Error::InvalidAnswerDataset { .. })));
+    }
+
+    #[test]
+    fn missing_parent_is_err() {
+        let result = Sut::new([AnswerDataset::sample_other()]);
+        assert_eq!(
+            result,
+            Err(Error::UnknownAnswerDataset { id: 1, version: 1 })
+        );
+    }
+
+    #[test]
+    fn entry_referring_to_non_existing_parent_entry_is_err() {
+        let consoles = AnswerDataset::new(
+            2,
+            1,
+            "Consoles",
+            Some(manufacturers()),
+            [AnswerDatasetEntry::new("Atari 2600", 3)],
+        )
+        .unwrap();
+        let result = Sut::new([AnswerDataset::sample(), consoles]);
+        assert!(matches!(result, Err(Error::InvalidAnswerDataset { .. })));
+    }
+
+    #[test]
+    fn choices_resolves_children_of_parent_selection() {
+        let sut = Sut::sample();
+        let question = question();
+        assert_eq!(
+            values(sut.choices(&question, &[]).unwrap()),
+            vec!["Nintendo", "Sega", "Sony"]
+        );
+        assert_eq!(
+            values(sut.choices(&question, &[2]).unwrap()),
+            vec!["PlayStation"]
+        );
+        assert_eq!(
+            values(sut.choices(&question, &[2, 5]).unwrap()),
+            vec!["Crash Bandicoot", "Final Fantasy VII"]
+        );
+    }
+
+    #[test]
+    fn choices_when_path_is_complete_is_err() {
+        let sut = Sut::sample();
+        let result = sut.choices(&question(), &[2, 5, 7]);
+        assert_eq!(
+            result,
+            Err(Error::InvalidStructuredAnswerSelectionCount {
+                expected: 3,
+                found: 4
+            })
+        );
+    }
+
+    #[test]
+    fn choices_with_invalid_path_is_err() {
+        let sut = Sut::sample();
+        let result = sut.choices(&question(), &[2, 0]);
+        assert_eq!(
+            result,
+            Err(Error::InvalidAnswerDatasetSelection {
+                dataset_id: 2,
+                selection: 0
+            })
+        );
+    }
+
+    #[test]
+    fn choices_of_freeform_question_is_err() {
+        let question = SecurityQuestion::sample();
+        let sut = Sut::sample();
+        let result = sut.choices(&question, &[]);
+        assert_eq!(
+            result,
+            Err(Error::AnswerDoesNotMatchQuestionKind {
+                question_id: question.id
+            })
+        );
+    }
+
+    #[test]
+    fn search_choices() {
+        let sut = Sut::sample();
+        let question = question();
+        assert_eq!(
+            values(sut.search_choices(&question, &[0], "GAME").unwrap()),
+            vec!["Game Boy"]
+        );
+        assert_eq!(
+            values(sut.search_choices(&question, &[0, 2], "o").unwrap()),
+            vec!["Pokémon Red"]
+        );
+    }
+
+    #[test]
+    fn validate_selections_ok() {
+        let sut = Sut::sample();
+        assert!(
+            sut.validate_selections(
+                &[manufacturers(), consoles(), games()],
+                &[0, 2, 4]
+            )
+            .is_ok()
+        );
+    }
+
+    #[test]
+    fn validate_selections_out_of_bounds_is_err() {
+        let result = Sut::sample()
+            .validate_selections(&[manufacturers(), consoles()], &[0, 6]);
+        assert_eq!(
+            result,
+            Err(Error::InvalidAnswerDatasetSelection {
+                dataset_id: 2,
+                selection: 6
+            })
+        );
+    }
+
+    #[test]
+    fn validate_selections_partial_path_is_err() {
+        let result = Sut::sample()
+            .validate_selections(&[manufacturers(), consoles()], &[0]);
+        assert_eq!(
+            result,
+            Err(Error::InvalidStructuredAnswerSelectionCount {
+                expected: 2,
+                found: 1
+            })
+        );
+    }
+
+    #[test]
+    fn validate_selections_parent_not_preceding_is_err() {
+        let result =
+            Sut::sample().validate_selections(&[consoles(), games()], &[0, 0]);
+        assert!(matches!(result, Err(Error::InvalidAnswerDataset { .. })));
+    }
+
+    #[test]
+    fn validate_selections_unknown_dataset_is_err() {
+        let result = Sut::sample()
+            .validate_selections(&[AnswerDatasetReference::new(9, 1)], &[0]);
+        assert_eq!(
+            result,
+            Err(Error::UnknownAnswerDataset { id: 9, version: 1 })
+        );
+    }
+
+    #[test]
+    fn validate_selections_of_independent_datasets() {
+        // Two root datasets, e.g. manufacturer of first and of second console.
+        let sut = Sut::sample();
+        assert!(
+            sut.validate_selections(
+                &[manufacturers(), manufacturers()],
+                &[0, 2]
+            )
+            .is_ok()
+        );
+    }
+
+    #[test]
+    fn validate_answer() {
+        let sut = Sut::sample();
+        let question = question();
+        let valid = SecurityQuestionAnswerAndSalt::by_answering_structured(
+            question.clone(),
+            |_, _| vec![1, 4, 6],
+        )
+        .unwrap();
+        assert!(sut.validate_answer(&valid).is_ok());
+
+        let invalid = SecurityQuestionAnswerAndSalt::by_answering_structured(
+            question,
+            |_, _| vec![1, 4, 7],
+        )
+        .unwrap();
+        assert_eq!(
+            sut.validate_answer(&invalid),
+            Err(Error::InvalidAnswerDatasetSelection {
+                dataset_id: 3,
+                selection: 7
+            })
+        );
+        assert!(
+            sut.validate_answer(&SecurityQuestionAnswerAndSalt::sample())
+                .is_ok()
+        );
+    }
+}
@@ -0,0 +1,9 @@
+mod answer_dataset;
+mod answer_dataset_entry;
+mod answer_dataset_reference;
+mod answer_datasets;
+
+pub use answer_dataset::*;
+pub use answer_dataset_entry::*;
+pub use answer_dataset_reference::*;
+pub use answer_datasets::*;
@@ -18,16 +18,35 @@ pub struct EncryptionKeys<
 /// Returns the number of combinations or an error if the inputs are invalid:
 /// if `answers` is greater than `questions`.
 fn n_choose_m<const N: usize, const M: usize>() -> Result<usize> {
-    let questions = N;
-    let answers = M;
+    combinations_count(N, M)
+}
+
+/// Runtime counterpart of [`n_choose_m`], the number of combinations of
+/// `answers` out of `questions` questions.
+///
+/// Since the counts may come from an untrusted sealed secret, the
+/// calculation is checked, failing with [`Error::InvalidSealedSecret`] if
+/// the number of combinations does not fit in a `usize`.
+pub(crate) fn combinations_count(
+    questions: usize,
+    answers: usize,
+) -> Result<usize> {
     if answers > questions {
-        Err(Error::QuestionsMustBeGreaterThanOrEqualAnswers {
+        return Err(Error::QuestionsMustBeGreaterThanOrEqualAnswers {
             questions,
             answers,
-        })
-    } else {
-        Ok((0..M).fold(1, |acc, i| acc * (N - i) / (i + 1)))
+        });
     }
+    // `acc` is `questions` choose `i`, so the division is exact
+    (0..answers)
+        .try_fold(1usize, |acc, i| {
+            acc.checked_mul(questions - i)?.checked_div(i + 1)
+        })
+        .ok_or_else(|| Error::InvalidSealedSecret {
+            underlying: format!(
+                "Number of combinations of {answers} out of {questions} questions overflows"
+            ),
+        })
 }
 
 impl<const QUESTION_COUNT: usize, const MIN_CORRECT_ANSWERS: usize>
@@ -77,6 +96,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn combinations_count_values() {
+        assert_eq!(combinations_count(6, 3), Ok(20));
+        assert_eq!(combinations_count(6, 0), Ok(1));
+        assert_eq!(combinations_count(6, 6), Ok(1));
+        assert_eq!(combinations_count(30, 15), Ok(155117520));
+    }
+
+    #[test]
+    fn combinations_count_overflow_is_err() {
+        assert!(matches!(
+            combinations_count(70, 35),
+            Err(Error::InvalidSealedSecret { .. })
+        ));
+    }
+
     #[test]
     fn new_too_short() {
         let result = Sut::new(vec![
@@ -207,6 +207,15 @@ pub enum Error {
     ///
     /// This error occurs when trying to parse or use a mnemonic phrase
     /// that doesn't conform to the expected format or contains invalid words.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svar_core::*;
+    ///
+    /// let result = Bip39Mnemonic::from_phrase("zoo zoo zoo");
+    /// assert!(matches!(result, Err(Error::InvalidMnemonicPhrase { .. })));
+    /// ```
     #[error("Invalid mnemonic phrase: {underlying}")]
     InvalidMnemonicPhrase { underlying: String },
 
@@ -224,11 +233,12 @@ pub enum Error {
     /// impl IsSecret for FailingSecret {
     ///     fn to_bytes(
     ///         &self,
-    ///     ) -> std::result::Result<Vec<u8>, Box<dyn std::error::Error>> {
+    ///     ) -> std::result::Result<SecretBytes, Box<dyn std::error::Error>>
+    ///     {
     ///         Err("conversion failed".into())
     ///     }
     ///     fn from_bytes(
-    ///         _: Vec<u8>,
+    ///         _: SecretBytes,
     ///     ) -> std::result::Result<Self, Box<dyn std::error::Error>> {
     ///         unreachable!()
     ///     }
@@ -318,6 +328,54 @@ pub enum Error {
         found: usize,
     },
 
+    /// XChaCha20-Poly1305 decryption operation failed.
+    ///
+    /// This error occurs when the XChaCha20-Poly1305 decryption algorithm
+    /// fails, typically due to incorrect keys or corrupted ciphertext.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svar_core::*;
+    ///
+    /// let scheme = EncryptionScheme::version2();
+    /// let encrypted = scheme.encrypt(b"secret", EncryptionKey::sample());
+    ///
+    /// let result = scheme.decrypt(&encrypted, EncryptionKey::sample_other());
+    /// assert!(matches!(
+    ///     result,
+    ///     Err(Error::XChaCha20Poly1305DecryptionFailed { .. })
+    /// ));
+    /// ```
+    #[error("XChaCha20-Poly1305 Decryption failed: {underlying}")]
+    XChaCha20Poly1305DecryptionFailed { underlying: String },
+
+    /// XChaCha20-Poly1305 ciphertext is too short to be valid.
+    ///
+    /// This error occurs when the provided ciphertext doesn't contain enough
+    /// bytes to include the required XChaCha20-Poly1305 components (nonce,
+    /// tag, etc.).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svar_core::*;
+    ///
+    /// let scheme = EncryptionScheme::version2();
+    /// let result = scheme.decrypt(vec![1, 2], EncryptionKey::sample());
+    /// assert!(matches!(
+    ///     result,
+    ///     Err(Error::InvalidXChaCha20Poly1305BytesTooShort { .. })
+    /// ));
+    /// ```
+    #[error(
+        "Invalid XChaCha20-Poly1305 bytes too short: expected at least {expected_at_least}, found {found}"
+    )]
+    InvalidXChaCha20Poly1305BytesTooShort {
+        expected_at_least: usize,
+        found: usize,
+    },
+
     /// Answers to security questions cannot be empty.
     ///
     /// This error occurs when trying to provide an empty answer to a security
@@ -338,4 +396,425 @@ pub enum Error {
     /// ```
     #[error("Answers to security questions cannot be empty")]
     AnswersToSecurityQuestionsCannotBeEmpty,
+
+    /// Stretching an answer using a memory-hard function failed.
+    ///
+    /// This error occurs when the parameters of the answer stretching, e.g.
+    /// of [`SecurityQuestionsAnswerStretchingArgon2id`](crate::SecurityQuestionsAnswerStretchingArgon2id),
+    /// are invalid, such as a memory cost which is too low.
+    #[error("Failed to stretch answer: {underlying}")]
+    AnswerStretchingFailed { underlying: String },
+
+    /// The kind of the answer does not match the kind of the question.
+    ///
+    /// This error occurs when a freeform question is answered with
+    /// selections, when a structured question is answered with free text, or
+    /// when a structured answer is given to a KDF scheme which does not
+    /// support structured answers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svar_core::*;
+    ///
+    /// let question = SecurityQuestion::sample(); // freeform
+    /// let result = SecurityQuestionAnswerAndSalt::by_answering_structured(
+    ///     question.clone(),
+    ///     |_, _| vec![1],
+    /// );
+    /// assert_eq!(
+    ///     result,
+    ///     Err(Error::AnswerDoesNotMatchQuestionKind {
+    ///         question_id: question.id
+    ///     })
+    /// );
+    /// ```
+    #[error(
+        "Answer does not match the kind of question with id: {question_id}"
+    )]
+    AnswerDoesNotMatchQuestionKind { question_id: u16 },
+
+    /// The number of selections of a structured answer does not match the
+    /// number of datasets referenced by the structured question.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svar_core::*;
+    ///
+    /// let question = SecurityQuestion::with_details(
+    ///     1,
+    ///     1,
+    ///     SecurityQuestionKind::Structured {
+    ///         datasets: vec![AnswerDatasetReference::new(1, 1)],
+    ///     },
+    ///     "In which country were you born?",
+    ///     SecurityQuestionExpectedAnswerFormat::new("<COUNTRY>", "Sweden"),
+    /// );
+    /// let result = SecurityQuestionAnswerAndSalt::by_answering_structured(
+    ///     question,
+    ///     |_, _| vec![1, 2],
+    /// );
+    /// assert_eq!(
+    ///     result,
+    ///     Err(Error::InvalidStructuredAnswerSelectionCount {
+    ///         expected: 1,
+    ///         found: 2
+    ///     })
+    /// );
+    /// ```
+    #[error(
+        "Invalid structured answer selection count: expected {expected}, found {found}"
+    )]
+    InvalidStructuredAnswerSelectionCount { expected: usize, found: usize },
+
+    /// An answer dataset is invalid, could not be loaded or parsed, or is
+    /// inconsistent with the datasets it depends on.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svar_core::*;
+    ///
+    /// let result = AnswerDataset::from_json_str(
+    ///     r#"{"id": 1, "version": 1, "name": "Empty", "entries": []}"#,
+    /// );
+    /// assert!(matches!(result, Err(Error::InvalidAnswerDataset { .. })));
+    /// ```
+    #[error("Invalid answer dataset: {underlying}")]
+    InvalidAnswerDataset { underlying: String },
+
+    /// A structured question refers to an answer dataset which is not known.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svar_core::*;
+    ///
+    /// let datasets = AnswerDatasets::sample();
+    /// let result = datasets.get(&AnswerDatasetReference::new(1, 2));
+    /// assert_eq!(
+    ///     result.unwrap_err(),
+    ///     Error::UnknownAnswerDataset { id: 1, version: 2 }
+    /// );
+    /// ```
+    #[error("Unknown answer dataset with id: {id}, version: {version}")]
+    UnknownAnswerDataset { id: u16, version: u8 },
+
+    /// A selection in a structured answer is not a valid entry of its
+    /// dataset, either because it is out of bounds or because it is not a
+    /// child of the selection made in the parent dataset.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svar_core::*;
+    ///
+    /// let datasets = AnswerDatasets::sample();
+    /// let manufacturers = AnswerDatasetReference::new(1, 1);
+    /// let consoles = AnswerDatasetReference::new(2, 1);
+    ///
+    /// // "Sega" (1) did not make the "NES" (0)
+    /// let result =
+    ///     datasets.validate_selections(&[manufacturers, consoles], &[1, 0]);
+    /// assert_eq!(
+    ///     result,
+    ///     Err(Error::InvalidAnswerDatasetSelection {
+    ///         dataset_id: 2,
+    ///         selection: 0
+    ///     })
+    /// );
+    /// ```
+    #[error(
+        "Invalid selection {selection} in answer dataset with id: {dataset_id}"
+    )]
+    InvalidAnswerDatasetSelection { dataset_id: u16, selection: u16 },
+
+    /// More than one answer was provided for the same question.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svar_core::*;
+    ///
+    /// let sealed = SecurityQuestionsSealed::<String>::sample();
+    /// let answers = SecurityQuestionsAnswersAndSalts::sample();
+    /// let result = sealed
+    ///     .open_with_subset([0, 0, 1, 2].map(|index| answers[index].clone()));
+    /// assert_eq!(
+    ///     result,
+    ///     Err(Error::DuplicateAnswersToQuestion {
+    ///         question_id: answers[0].question.id
+    ///     })
+    /// );
+    /// ```
+    #[error("Duplicate answers to question with id: {question_id}")]
+    DuplicateAnswersToQuestion { question_id: u16 },
+
+    /// Fewer answers were provided than the minimum number of correct
+    /// answers required to open a sealed secret.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svar_core::*;
+    ///
+    /// let sealed = SecurityQuestionsSealed::<String, 6, 4>::sample();
+    /// let answers = SecurityQuestionsAnswersAndSalts::sample();
+    /// let result = sealed.open_with_subset(answers.iter().take(3).cloned());
+    /// assert_eq!(
+    ///     result,
+    ///     Err(Error::TooFewAnswers {
+    ///         expected_at_least: 4,
+    ///         found: 3
+    ///     })
+    /// );
+    /// ```
+    #[error(
+        "Too few answers: expected at least {expected_at_least}, found {found}"
+    )]
+    TooFewAnswers {
+        expected_at_least: usize,
+        found: usize,
+    },
+
+    /// An answer was provided for a question id which is not part of the
+    /// security questions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svar_core::*;
+    ///
+    /// let sealed = SecurityQuestionsSealed::<String>::sample();
+    /// let result = sealed.open_with_answers([(1337, "Oxford")]);
+    /// assert_eq!(result, Err(Error::UnknownQuestionId { question_id: 1337 }));
+    /// ```
+    #[error("Unknown question id: {question_id}")]
+    UnknownQuestionId { question_id: u16 },
+
+    /// Failed to decrypt a sealed secret, and the answer to a question was
+    /// provided with a different salt than the one used to seal the secret.
+    ///
+    /// This error typically occurs when answers are created using
+    /// [`SecurityQuestionAnswerAndSalt::by_answering_freeform`](crate::SecurityQuestionAnswerAndSalt::by_answering_freeform),
+    /// which generates a new random salt, instead of reusing the salts stored
+    /// in the sealed secret, see
+    /// [`SecurityQuestionsSealed::open_with_answers`](crate::SecurityQuestionsSealed::open_with_answers).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svar_core::*;
+    ///
+    /// let sealed = SecurityQuestionsSealed::<String>::sample();
+    /// let mut answers = SecurityQuestionsAnswersAndSalts::sample();
+    /// for answer_and_salt in answers.iter_mut() {
+    ///     answer_and_salt.salt = Exactly32Bytes::generate();
+    /// }
+    ///
+    /// let result = sealed.decrypt(answers.clone());
+    /// assert_eq!(
+    ///     result,
+    ///     Err(Error::SaltMismatch {
+    ///         question_id: answers[0].question.id
+    ///     })
+    /// );
+    /// ```
+    #[error(
+        "Salt of answer to question with id: {question_id} does not match the salt used to seal the secret"
+    )]
+    SaltMismatch { question_id: u16 },
+
+    /// The minimum number of correct answers is zero or greater than the
+    /// number of questions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svar_core::*;
+    ///
+    /// let answers = SecurityQuestionsAnswersAndSalts::sample();
+    /// let result = SecurityQuestionsSealedDyn::<String>::seal(
+    ///     "secret".to_owned(),
+    ///     answers.iter().cloned(),
+    ///     7,
+    /// );
+    /// assert_eq!(
+    ///     result,
+    ///     Err(Error::InvalidMinCorrectAnswers {
+    ///         min_correct_answers: 7,
+    ///         question_count: 6
+    ///     })
+    /// );
+    /// ```
+    #[error(
+        "Invalid min correct answers: {min_correct_answers}, must be at least 1 and at most the question count: {question_count}"
+    )]
+    InvalidMinCorrectAnswers {
+        min_correct_answers: usize,
+        question_count: usize,
+    },
+
+    /// A sealed secret is invalid, e.g. the number of encryptions does not
+    /// match its question count and minimum number of correct answers, or
+    /// its sizes do not match those of the type it is converted to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svar_core::*;
+    ///
+    /// let sealed =
+    ///     SecurityQuestionsSealedDyn::from(
+    ///         SecurityQuestionsSealed::<String, 6, 4>::sample(),
+    ///     );
+    /// let result = SecurityQuestionsSealed::<String, 6, 3>::try_from(sealed);
+    /// assert!(matches!(result, Err(Error::InvalidSealedSecret { .. })));
+    /// ```
+    #[error("Invalid sealed secret: {underlying}")]
+    InvalidSealedSecret { underlying: String },
+
+    /// More questions were provided than shares can be created for when
+    /// sealing with [`SealingMode::Shamir`](crate::SealingMode::Shamir).
+    #[error(
+        "Too many questions for Shamir sealing mode: {found}, at most {max} supported"
+    )]
+    TooManyQuestionsForShamir { max: usize, found: usize },
+
+    /// The answers derived the key of an encryption of the sealed secret,
+    /// but the encryption does not authenticate the questions, salts or
+    /// schemes stored next to it, or the encryption itself was modified.
+    ///
+    /// Only detected for sealed secrets of
+    /// [`SealedSecretVersion::Version2`](crate::SealedSecretVersion::Version2)
+    /// or later.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svar_core::*;
+    ///
+    /// let mut sealed = SecurityQuestionsSealed::<String, 6, 4>::sample();
+    /// sealed.encryption_scheme = EncryptionScheme::version3();
+    /// let result = sealed.open(SecurityQuestionsAnswersAndSalts::sample());
+    /// assert_eq!(result, Err(Error::SealedSecretTampered));
+    /// ```
+    #[error("Sealed secret has been tampered with")]
+    SealedSecretTampered,
+
+    /// Failed to read from the stream of the plaintext or the sealed secret
+    /// when sealing or opening a
+    /// [`SecurityQuestionsSealedStream`](crate::SecurityQuestionsSealedStream).
+    #[error("Failed to read stream: {underlying}")]
+    FailedToReadStream { underlying: String },
+
+    /// Failed to write to the stream of the sealed secret or the plaintext
+    /// when sealing or opening a
+    /// [`SecurityQuestionsSealedStream`](crate::SecurityQuestionsSealedStream).
+    #[error("Failed to write stream: {underlying}")]
+    FailedToWriteStream { underlying: String },
+
+    /// The stream is not a sealed secret stream, or it has been truncated or
+    /// extended.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svar_core::*;
+    ///
+    /// let result =
+    ///     SecurityQuestionsSealedStream::read_header(&mut &b"not sealed"[..]);
+    /// assert!(matches!(result, Err(Error::InvalidSealedStream { .. })));
+    /// ```
+    #[error("Invalid sealed stream: {underlying}")]
+    InvalidSealedStream { underlying: String },
+
+    /// The secret is too long to be padded according to the
+    /// [`PaddingPolicy::Fixed`](crate::PaddingPolicy::Fixed) of the
+    /// encryption scheme.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroU32;
+    /// use svar_core::*;
+    ///
+    /// let encryption_scheme =
+    ///     EncryptionScheme::default().with_padding(PaddingPolicy::Fixed {
+    ///         len: NonZeroU32::new(8).unwrap(),
+    ///     });
+    /// let result = SecurityQuestionsSealed::<String, 6, 4>::with_schemes(
+    ///     "too long secret".to_owned(),
+    ///     SecurityQuestionsAnswersAndSalts::sample(),
+    ///     SecurityQuestionsKdfScheme::default(),
+    ///     encryption_scheme,
+    /// );
+    /// assert_eq!(
+    ///     result,
+    ///     Err(Error::SecretTooLongForPadding { max: 7, found: 15 })
+    /// );
+    /// ```
+    #[error(
+        "Secret of {found} bytes is too long for padding, max is {max} bytes"
+    )]
+    SecretTooLongForPadding { max: usize, found: usize },
+
+    /// The padding of a decrypted plaintext is not valid, which given that
+    /// it was authenticated means it was not padded when encrypted.
+    #[error("Invalid padding of decrypted plaintext")]
+    InvalidPadding,
+
+    /// The language of a locale tag is not supported by
+    /// [`AnswerLocale`](crate::AnswerLocale).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svar_core::*;
+    ///
+    /// let result = "sv-SE".parse::<AnswerLocale>();
+    /// assert_eq!(
+    ///     result,
+    ///     Err(Error::UnsupportedAnswerLocale {
+    ///         tag: "sv-SE".to_owned()
+    ///     })
+    /// );
+    /// ```
+    #[error("Unsupported answer locale: {tag}")]
+    UnsupportedAnswerLocale { tag: String },
+
+    /// A question opts into a [`Transliteration`](crate::Transliteration) or
+    /// [`PhoneticEncoding`](crate::PhoneticEncoding) of its answers, which
+    /// the KDF scheme used to seal the secret does not apply.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svar_core::*;
+    ///
+    /// let mut answers = SecurityQuestionsAnswersAndSalts::sample().to_vec();
+    /// answers[0].question = answers[0]
+    ///     .question
+    ///     .clone()
+    ///     .with_transliteration(Transliteration::ToLatinVersion1);
+    ///
+    /// let result = SecurityQuestionsSealedDyn::<String>::with_schemes(
+    ///     "secret".to_owned(),
+    ///     answers.clone(),
+    ///     4,
+    ///     SecurityQuestionsKdfScheme::version1(),
+    ///     EncryptionScheme::default(),
+    /// );
+    /// assert_eq!(
+    ///     result,
+    ///     Err(Error::AnswerProcessingNotSupportedByKdfScheme {
+    ///         question_id: answers[0].question.id
+    ///     })
+    /// );
+    /// ```
+    #[error(
+        "Transliteration or phonetic encoding of answers to question with id: {question_id} is not supported by the KDF scheme"
+    )]
+    AnswerProcessingNotSupportedByKdfScheme { question_id: u16 },
 }
@@ -1,6 +1,7 @@
 use crate::prelude::*;
 
 pub type Exactly32Bytes = ExactlyNBytes<32>;
+pub type Exactly24Bytes = ExactlyNBytes<24>;
 pub type Exactly16Bytes = ExactlyNBytes<16>;
 pub type Exactly12Bytes = ExactlyNBytes<12>;
 
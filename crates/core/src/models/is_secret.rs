@@ -1,3 +1,5 @@
+use crate::prelude::*;
+
 /// A trait for types that can be treated as secrets in the svar encryption
 /// system.
 ///
@@ -5,22 +7,31 @@
 /// questions. Types implementing this trait must be able to convert themselves
 /// to and from bytes, as the encryption process operates on byte arrays.
 ///
+/// The bytes are passed around as [`SecretBytes`], which are zeroized when
+/// dropped.
+///
 /// # Security Considerations
 ///
 /// When implementing this trait:
 /// - Ensure the byte representation preserves all necessary information
 /// - For sensitive data like mnemonics, use entropy bytes rather than the
-///   human-readable form
+///   human-readable form, like [`Bip39Mnemonic`](crate::Bip39Mnemonic) does
 /// - Consider implementing [`Zeroize`] for the type to clear sensitive data
 ///   from memory
+/// - Avoid intermediate copies of the bytes which are not zeroized, e.g. build
+///   [`SecretBytes`] from an owned `Vec<u8>` or `String` rather than from a
+///   borrowed copy
 ///
 /// # Examples
 ///
 /// ## Basic Implementation for Custom Type
 ///
+/// Serde types need not implement this trait by hand, they can be wrapped in
+/// a [`SerdeSecret`](crate::SerdeSecret) instead.
+///
 /// ```
 /// use serde::{Deserialize, Serialize};
-/// use svar_core::IsSecret;
+/// use svar_core::{IsSecret, SecretBytes};
 ///
 /// #[derive(Serialize, Deserialize, Clone)]
 /// struct MySecret {
@@ -31,16 +42,16 @@
 /// impl IsSecret for MySecret {
 ///     fn to_bytes(
 ///         &self,
-///     ) -> std::result::Result<Vec<u8>, Box<dyn std::error::Error>> {
+///     ) -> std::result::Result<SecretBytes, Box<dyn std::error::Error>>
+///     {
 ///         let json = serde_json::to_string(self)?;
-///         Ok(json.into_bytes())
+///         Ok(SecretBytes::from(json))
 ///     }
 ///
 ///     fn from_bytes(
-///         bytes: Vec<u8>,
+///         bytes: SecretBytes,
 ///     ) -> std::result::Result<Self, Box<dyn std::error::Error>> {
-///         let json = String::from_utf8(bytes)?;
-///         let secret: MySecret = serde_json::from_str(&json)?;
+///         let secret: MySecret = serde_json::from_slice(bytes.as_ref())?;
 ///         Ok(secret)
 ///     }
 /// }
@@ -116,7 +127,7 @@ pub trait IsSecret: Sized {
     /// assert_eq!(original, reconstructed);
     /// ```
     fn from_bytes(
-        bytes: Vec<u8>,
+        bytes: SecretBytes,
     ) -> std::result::Result<Self, Box<dyn std::error::Error>>;
 
     /// Convert the secret to its byte representation.
@@ -126,7 +137,7 @@ pub trait IsSecret: Sized {
     ///
     /// # Returns
     ///
-    /// - `Ok(Vec<u8>)`: Successfully converted to bytes
+    /// - `Ok(SecretBytes)`: Successfully converted to bytes
     /// - `Err(Box<dyn std::error::Error>)`: Conversion failed
     ///
     /// # Examples
@@ -136,11 +147,11 @@ pub trait IsSecret: Sized {
     ///
     /// let secret = "hello world".to_string();
     /// let bytes = secret.to_bytes().unwrap();
-    /// assert_eq!(bytes, b"hello world");
+    /// assert_eq!(bytes.as_ref(), b"hello world");
     /// ```
     fn to_bytes(
         &self,
-    ) -> std::result::Result<Vec<u8>, Box<dyn std::error::Error>>;
+    ) -> std::result::Result<SecretBytes, Box<dyn std::error::Error>>;
 }
 
 /// Implementation of [`IsSecret`] for [`String`].
@@ -175,15 +186,18 @@ pub trait IsSecret: Sized {
 /// - [`to_bytes`](IsSecret::to_bytes): Never fails for valid strings
 impl IsSecret for String {
     fn from_bytes(
-        bytes: Vec<u8>,
+        bytes: SecretBytes,
     ) -> std::result::Result<Self, Box<dyn std::error::Error>> {
-        String::from_utf8(bytes).map_err(|e| e.into())
+        // Unlike `String::from_utf8`, the error does not own the bytes
+        std::str::from_utf8(bytes.as_ref())
+            .map(str::to_owned)
+            .map_err(|e| e.into())
     }
 
     fn to_bytes(
         &self,
-    ) -> std::result::Result<Vec<u8>, Box<dyn std::error::Error>> {
-        Ok(self.as_bytes().to_vec())
+    ) -> std::result::Result<SecretBytes, Box<dyn std::error::Error>> {
+        Ok(SecretBytes::from(self.as_bytes()))
     }
 }
 
@@ -219,15 +233,15 @@ impl IsSecret for String {
 /// - [`to_bytes`](IsSecret::to_bytes): Never fails
 impl IsSecret for Vec<u8> {
     fn from_bytes(
-        bytes: Vec<u8>,
+        bytes: SecretBytes,
     ) -> std::result::Result<Self, Box<dyn std::error::Error>> {
-        Ok(bytes)
+        Ok(bytes.to_vec())
     }
 
     fn to_bytes(
         &self,
-    ) -> std::result::Result<Vec<u8>, Box<dyn std::error::Error>> {
-        Ok(self.clone())
+    ) -> std::result::Result<SecretBytes, Box<dyn std::error::Error>> {
+        Ok(SecretBytes::from(self.as_slice()))
     }
 }
 
@@ -244,4 +258,9 @@ mod tests {
             Vec::<u8>::from_bytes(bytes).expect("from_bytes failed");
         assert_eq!(secret, secret_from_bytes);
     }
+
+    #[test]
+    fn string_from_invalid_utf8_is_err() {
+        assert!(String::from_bytes(SecretBytes::from(vec![0xff])).is_err());
+    }
 }
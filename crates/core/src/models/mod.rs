@@ -1,4 +1,6 @@
 mod answer;
+mod bip39;
+mod dataset;
 mod encryption_keys;
 mod error;
 mod exactly_n_bytes;
@@ -6,9 +8,16 @@ mod has_sample_values;
 mod hex_bytes;
 mod is_secret;
 mod question;
+mod sealed_secret_version;
+mod sealing_mode;
+mod secret_bytes;
+mod secret_codec;
 mod secure_random_bytes;
+mod serde_secret;
 
 pub use answer::*;
+pub use bip39::*;
+pub use dataset::*;
 pub use encryption_keys::*;
 pub use error::*;
 pub use exactly_n_bytes::*;
@@ -16,4 +25,9 @@ pub use has_sample_values::*;
 pub use hex_bytes::*;
 pub use is_secret::*;
 pub use question::*;
+pub use sealed_secret_version::*;
+pub use sealing_mode::*;
+pub use secret_bytes::*;
+pub use secret_codec::*;
 pub use secure_random_bytes::*;
+pub use serde_secret::*;
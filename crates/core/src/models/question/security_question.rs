@@ -125,6 +125,14 @@ pub struct SecurityQuestion {
     /// format, numeric) to ensure consistency in answer collection and
     /// validation.
     pub expected_answer_format: SecurityQuestionExpectedAnswerFormat,
+
+    /// Optional transliteration of freeform answers to this question.
+    ///
+    /// Applied before answers are normalized, so that e.g. the answer to a
+    /// question about a name matches whether the name is written in Cyrillic
+    /// or in Latin letters.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transliteration: Option<Transliteration>,
 }
 
 /// Provides access to the question text as a string reference.
@@ -246,9 +254,33 @@ impl SecurityQuestion {
             kind,
             question: question.as_ref().to_owned(),
             expected_answer_format,
+            transliteration: None,
         }
     }
 
+    /// Returns this question with answers transliterated using
+    /// `transliteration` before they are normalized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svar_core::*;
+    ///
+    /// let question = SecurityQuestion::first_kiss_whom()
+    ///     .with_transliteration(Transliteration::ToLatinVersion1);
+    /// assert_eq!(
+    ///     question.transliteration,
+    ///     Some(Transliteration::ToLatinVersion1)
+    /// );
+    /// ```
+    pub fn with_transliteration(
+        mut self,
+        transliteration: Transliteration,
+    ) -> Self {
+        self.transliteration = Some(transliteration);
+        self
+    }
+
     /// Creates a freeform security question with the specified ID.
     ///
     /// This is a convenience constructor for creating freeform questions
@@ -649,6 +681,29 @@ impl SecurityQuestion {
     }
 }
 
+#[cfg(test)]
+impl SecurityQuestion {
+    /// A sample structured question, answered by selecting a manufacturer in
+    /// the sample dataset and a console in the other sample dataset.
+    pub(crate) fn sample_structured() -> Self {
+        Self::with_details(
+            100,
+            1,
+            SecurityQuestionKind::Structured {
+                datasets: vec![
+                    AnswerDatasetReference::sample(),
+                    AnswerDatasetReference::sample_other(),
+                ],
+            },
+            "What was your first video game console?",
+            SecurityQuestionExpectedAnswerFormat::new(
+                "<MANUFACTURER>, <CONSOLE>",
+                "Nintendo, NES",
+            ),
+        )
+    }
+}
+
 impl HasSampleValues for SecurityQuestion {
     /// A sample used to facilitate unit tests.
     fn sample() -> Self {
@@ -686,6 +741,22 @@ mod tests {
         assert_eq!(set.len(), 17);
     }
 
+    #[test]
+    fn json_omits_transliteration_if_none() {
+        let json = serde_json::to_value(Sut::sample()).unwrap();
+        assert!(json.get("transliteration").is_none());
+        assert_eq!(serde_json::from_value::<Sut>(json).unwrap(), Sut::sample());
+    }
+
+    #[test]
+    fn json_roundtrip_with_transliteration() {
+        let sut = Sut::sample()
+            .with_transliteration(Transliteration::ToLatinVersion1);
+        let json = serde_json::to_value(&sut).unwrap();
+        assert_eq!(json["transliteration"], "ToLatinVersion1");
+        assert_eq!(serde_json::from_value::<Sut>(json).unwrap(), sut);
+    }
+
     #[test]
     fn freeform_samples() {
         assert!(
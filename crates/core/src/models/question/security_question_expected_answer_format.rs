@@ -19,6 +19,13 @@ pub struct SecurityQuestionExpectedAnswerFormat {
     /// Then we can discourage the user from selecting that question
     /// if the answer is in `["Teddy", "Peter Rabbit", "Winnie (the Poh)"]`
     pub unsafe_answers: Vec<String>,
+
+    /// Opt-in phonetic encoding of answers which are names, whose spelling
+    /// users often misremember, so that e.g. `"Catherine"` and `"Kathryn"`
+    /// are equal, at the cost of some entropy, see
+    /// [`phonetic_entropy_loss`](Self::phonetic_entropy_loss).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub phonetic_encoding: Option<PhoneticEncoding>,
 }
 
 impl SecurityQuestionExpectedAnswerFormat {
@@ -34,9 +41,45 @@ impl SecurityQuestionExpectedAnswerFormat {
                 .into_iter()
                 .map(|x| x.to_owned())
                 .collect_vec(),
+            phonetic_encoding: None,
         }
     }
 
+    /// Returns this format with answers encoded using `phonetic_encoding`
+    /// before they are normalized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svar_core::*;
+    ///
+    /// let mut question = SecurityQuestion::first_kiss_whom();
+    /// question.expected_answer_format = question
+    ///     .expected_answer_format
+    ///     .with_phonetic_encoding(PhoneticEncoding::MetaphoneVersion1);
+    /// assert!(
+    ///     question
+    ///         .expected_answer_format
+    ///         .phonetic_entropy_loss()
+    ///         .is_some()
+    /// );
+    /// ```
+    pub fn with_phonetic_encoding(
+        mut self,
+        phonetic_encoding: PhoneticEncoding,
+    ) -> Self {
+        self.phonetic_encoding = Some(phonetic_encoding);
+        self
+    }
+
+    /// The estimated entropy lost by the
+    /// [`phonetic_encoding`](Self::phonetic_encoding) of answers, `None` if
+    /// answers are not phonetically encoded.
+    pub fn phonetic_entropy_loss(&self) -> Option<PhoneticEntropyLoss> {
+        self.phonetic_encoding
+            .map(|encoding| encoding.entropy_loss())
+    }
+
     pub fn new(structure: impl AsRef<str>, example: impl AsRef<str>) -> Self {
         Self::with_details(structure, example, [])
     }
@@ -85,4 +128,28 @@ mod tests {
     fn inequality() {
         assert_ne!(Sut::sample(), Sut::sample_other());
     }
+
+    #[test]
+    fn phonetic_entropy_loss() {
+        assert_eq!(Sut::name().phonetic_entropy_loss(), None);
+        assert_eq!(
+            Sut::name()
+                .with_phonetic_encoding(PhoneticEncoding::MetaphoneVersion1)
+                .phonetic_entropy_loss(),
+            Some(PhoneticEncoding::MetaphoneVersion1.entropy_loss())
+        );
+    }
+
+    #[test]
+    fn json_omits_phonetic_encoding_if_none() {
+        let json = serde_json::to_value(Sut::name()).unwrap();
+        assert!(json.get("phonetic_encoding").is_none());
+        assert_eq!(serde_json::from_value::<Sut>(json).unwrap(), Sut::name());
+
+        let sut = Sut::name()
+            .with_phonetic_encoding(PhoneticEncoding::MetaphoneVersion1);
+        let json = serde_json::to_value(&sut).unwrap();
+        assert_eq!(json["phonetic_encoding"], "MetaphoneVersion1");
+        assert_eq!(serde_json::from_value::<Sut>(json).unwrap(), sut);
+    }
 }
@@ -4,5 +4,59 @@ use crate::prelude::*;
     Serialize, Deserialize, Clone, PartialEq, Eq, Hash, Debug, Display,
 )]
 pub enum SecurityQuestionKind {
+    /// A question which is answered with free text.
     Freeform,
+
+    /// A question which is answered by selecting one entry in each of the
+    /// referenced datasets, e.g. a country and a year, rather than typing
+    /// free text, making the answers unambiguous.
+    #[display("Structured({})", datasets.iter().join(", "))]
+    Structured {
+        /// The versioned datasets the answer selects from, in order, the
+        /// answer contains one selection per dataset.
+        datasets: Vec<AnswerDatasetReference>,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Sut = SecurityQuestionKind;
+
+    #[test]
+    fn json_freeform() {
+        assert_eq!(
+            serde_json::to_string(&Sut::Freeform).unwrap(),
+            "\"Freeform\""
+        );
+    }
+
+    #[test]
+    fn json_structured_roundtrip() {
+        let sut = Sut::Structured {
+            datasets: vec![
+                AnswerDatasetReference::sample(),
+                AnswerDatasetReference::sample_other(),
+            ],
+        };
+        let json = serde_json::to_string(&sut).unwrap();
+        assert_eq!(
+            json,
+            r#"{"Structured":{"datasets":[{"id":1,"version":1},{"id":2,"version":1}]}}"#
+        );
+        let deserialized: Sut = serde_json::from_str(&json).unwrap();
+        assert_eq!(sut, deserialized);
+    }
+
+    #[test]
+    fn display_structured() {
+        let sut = Sut::Structured {
+            datasets: vec![AnswerDatasetReference::sample()],
+        };
+        assert_eq!(
+            sut.to_string(),
+            "Structured(AnswerDatasetReference(id: 1, version: 1))"
+        );
+    }
 }
@@ -0,0 +1,63 @@
+use serde_repr::{Deserialize_repr, Serialize_repr};
+
+/// The version of the format of a sealed secret, which determines what each
+/// of its encryptions authenticates.
+#[repr(u32)]
+#[derive(
+    Serialize_repr,
+    Deserialize_repr,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    derive_more::Display,
+    derive_more::Debug,
+)]
+pub enum SealedSecretVersion {
+    /// The encryptions authenticate nothing but the secret, so the questions,
+    /// salts and schemes stored next to them can be modified without being
+    /// noticed. Kept so that old sealed secrets can still be opened.
+    Version1 = 1,
+
+    /// Each encryption authenticates the questions and salts, the versions
+    /// of the schemes, the sealing mode, the question count and the minimum
+    /// number of correct answers as associated data, and is prefixed with a
+    /// commitment to its key, so that opening a modified sealed secret with
+    /// correct answers fails with
+    /// [`Error::SealedSecretTampered`](crate::Error::SealedSecretTampered).
+    #[default]
+    Version2 = 2,
+}
+
+impl SealedSecretVersion {
+    /// The version of sealed secrets serialized before it was recorded.
+    pub fn version1() -> Self {
+        Self::Version1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Sut = SealedSecretVersion;
+
+    #[test]
+    fn default_is_version2() {
+        assert_eq!(Sut::default(), Sut::Version2);
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        let json = serde_json::to_string(&Sut::Version2).unwrap();
+        assert_eq!(json, "2");
+        assert_eq!(serde_json::from_str::<Sut>(&json).unwrap(), Sut::Version2);
+    }
+
+    #[test]
+    fn deserialize_unknown_version_is_err() {
+        assert!(serde_json::from_str::<Sut>("3").is_err());
+    }
+}
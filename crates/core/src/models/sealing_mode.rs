@@ -0,0 +1,75 @@
+use crate::prelude::*;
+
+/// How a secret is sealed such that any `min_correct_answers` of the answers
+/// to the security questions can open it.
+#[derive(
+    Serialize,
+    Deserialize,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    Debug,
+    Display,
+)]
+pub enum SealingMode {
+    /// The secret is encrypted once per combination of `min_correct_answers`
+    /// answers, with a key derived from the answers of the combination.
+    ///
+    /// The number of encryptions is `question_count` choose
+    /// `min_correct_answers`, which grows combinatorially, e.g. 12 of 20
+    /// questions results in 125,970 encryptions.
+    #[default]
+    Combinations,
+
+    /// The secret is encrypted once with a random data key, which is split
+    /// using Shamir's Secret Sharing over GF(256) into one share per question,
+    /// any `min_correct_answers` of which recover the data key.
+    ///
+    /// Each share is stored masked with a key derived from the answer to its
+    /// question, so the sealed secret grows linearly with the number of
+    /// questions. The masking is not authenticated, so just like with
+    /// [`Combinations`](Self::Combinations) the answers can only be verified
+    /// `min_correct_answers` at a time. Limited to 255 questions.
+    Shamir,
+
+    /// The secret is encrypted once with a random data key, which is
+    /// encrypted (wrapped) once per combination of `min_correct_answers`
+    /// answers, with a key derived from the answers of the combination.
+    ///
+    /// The number of wrapped keys grows just like the number of encryptions
+    /// with [`Combinations`](Self::Combinations), but each is about a hundred
+    /// bytes regardless of the size of the secret, so the size of the secret
+    /// is only stored once.
+    Envelope,
+}
+
+impl SealingMode {
+    /// Returns `true` if `self` is [`SealingMode::Combinations`].
+    pub fn is_combinations(&self) -> bool {
+        *self == Self::Combinations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Sut = SealingMode;
+
+    #[test]
+    fn default_is_combinations() {
+        assert!(Sut::default().is_combinations());
+        assert!(!Sut::Shamir.is_combinations());
+        assert!(!Sut::Envelope.is_combinations());
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        let json = serde_json::to_string(&Sut::Shamir).unwrap();
+        assert_eq!(json, "\"Shamir\"");
+        assert_eq!(serde_json::from_str::<Sut>(&json).unwrap(), Sut::Shamir);
+    }
+}
@@ -0,0 +1,112 @@
+use zeroize::ZeroizeOnDrop;
+
+use crate::prelude::*;
+
+/// The bytes of a secret, or of an intermediate value derived from one, e.g.
+/// the trimmed answer to a security question used as input key material.
+///
+/// Zeroizes its contents when dropped and never prints them, its [`Debug`]
+/// implementation is redacted.
+///
+/// # Examples
+///
+/// ```
+/// use svar_core::*;
+///
+/// let bytes = SecretBytes::from(vec![0xde, 0xad, 0xbe, 0xef]);
+/// assert_eq!(bytes.as_ref(), &[0xde, 0xad, 0xbe, 0xef]);
+/// assert_eq!(format!("{bytes:?}"), "SecretBytes(<redacted>)");
+/// ```
+#[derive(
+    Zeroize, ZeroizeOnDrop, Clone, Default, PartialEq, Eq, derive_more::Debug,
+)]
+#[debug("SecretBytes(<redacted>)")]
+pub struct SecretBytes(Vec<u8>);
+
+impl SecretBytes {
+    /// The number of bytes.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if there are no bytes.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Copies the bytes into a `Vec<u8>` which is **not** zeroized when
+    /// dropped, prefer [`as_ref`](AsRef::as_ref) where possible.
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.0.clone()
+    }
+}
+
+impl AsRef<[u8]> for SecretBytes {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// Takes ownership of `value` without copying it, so that no copy of the
+/// bytes is left behind.
+impl From<Vec<u8>> for SecretBytes {
+    fn from(value: Vec<u8>) -> Self {
+        Self(value)
+    }
+}
+
+impl From<&[u8]> for SecretBytes {
+    fn from(value: &[u8]) -> Self {
+        Self(value.to_vec())
+    }
+}
+
+/// Takes ownership of the UTF-8 bytes of `value` without copying them.
+impl From<String> for SecretBytes {
+    fn from(value: String) -> Self {
+        Self(value.into_bytes())
+    }
+}
+
+impl HasSampleValues for SecretBytes {
+    fn sample() -> Self {
+        Self::from(vec![0xde, 0xad, 0xbe, 0xef])
+    }
+
+    fn sample_other() -> Self {
+        Self::from(vec![0xca, 0xfe, 0xba, 0xbe])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Sut = SecretBytes;
+
+    #[test]
+    fn equality() {
+        assert_eq!(Sut::sample(), Sut::sample());
+        assert_eq!(Sut::sample_other(), Sut::sample_other());
+        assert_ne!(Sut::sample(), Sut::sample_other());
+    }
+
+    #[test]
+    fn debug_is_redacted() {
+        let debug = format!("{:?}", Sut::sample());
+        assert_eq!(debug, "SecretBytes(<redacted>)");
+        assert!(!debug.to_lowercase().contains("dead"));
+    }
+
+    #[test]
+    fn zeroize() {
+        let mut sut = Sut::sample();
+        sut.zeroize();
+        assert!(sut.is_empty());
+    }
+
+    #[test]
+    fn from_string_keeps_utf8_bytes() {
+        assert_eq!(Sut::from("Oxford".to_owned()).as_ref(), b"Oxford");
+    }
+}
@@ -0,0 +1,141 @@
+use crate::prelude::*;
+
+use serde::de::DeserializeOwned;
+
+/// Encodes values into the bytes of a secret and decodes them back, used by
+/// [`SerdeSecret`] to implement [`IsSecret`] for any serde type.
+///
+/// Errors are returned as is, sealing maps them into
+/// [`Error::FailedToConvertSecretToBytes`] and opening into
+/// [`Error::FailedToConvertBytesToSecret`].
+pub trait SecretCodec {
+    /// Encodes `value` into bytes.
+    fn encode<T: Serialize>(
+        value: &T,
+    ) -> std::result::Result<Vec<u8>, Box<dyn std::error::Error>>;
+
+    /// Decodes a value from `bytes` produced by [`encode`](Self::encode).
+    fn decode<T: DeserializeOwned>(
+        bytes: &[u8],
+    ) -> std::result::Result<T, Box<dyn std::error::Error>>;
+}
+
+/// Encodes secrets as JSON, human readable and forgiving of added optional
+/// fields, at the cost of size.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, Debug)]
+pub struct JsonCodec;
+
+impl SecretCodec for JsonCodec {
+    fn encode<T: Serialize>(
+        value: &T,
+    ) -> std::result::Result<Vec<u8>, Box<dyn std::error::Error>> {
+        serde_json::to_vec(value).map_err(|e| e.into())
+    }
+
+    fn decode<T: DeserializeOwned>(
+        bytes: &[u8],
+    ) -> std::result::Result<T, Box<dyn std::error::Error>> {
+        serde_json::from_slice(bytes).map_err(|e| e.into())
+    }
+}
+
+/// Encodes secrets using the compact binary [postcard] format, which does not
+/// store field names, so the layout of the type must not change between
+/// sealing and opening.
+///
+/// [postcard]: https://docs.rs/postcard
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, Debug)]
+pub struct PostcardCodec;
+
+impl SecretCodec for PostcardCodec {
+    fn encode<T: Serialize>(
+        value: &T,
+    ) -> std::result::Result<Vec<u8>, Box<dyn std::error::Error>> {
+        postcard::to_stdvec(value).map_err(|e| e.into())
+    }
+
+    /// Fails if `bytes` contains more than the encoding of a value.
+    fn decode<T: DeserializeOwned>(
+        bytes: &[u8],
+    ) -> std::result::Result<T, Box<dyn std::error::Error>> {
+        let (value, remaining) = postcard::take_from_bytes(bytes)?;
+        if !remaining.is_empty() {
+            return Err(format!(
+                "Unexpected {} trailing bytes after value",
+                remaining.len()
+            )
+            .into());
+        }
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Wallet {
+        name: String,
+        key: [u8; 4],
+        index: u32,
+    }
+
+    fn wallet() -> Wallet {
+        Wallet {
+            name: "savings".to_owned(),
+            key: [0xde, 0xad, 0xbe, 0xef],
+            index: 7,
+        }
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        let bytes = JsonCodec::encode(&wallet()).unwrap();
+        assert_eq!(
+            String::from_utf8(bytes.clone()).unwrap(),
+            r#"{"name":"savings","key":[222,173,190,239],"index":7}"#
+        );
+        assert_eq!(JsonCodec::decode::<Wallet>(&bytes).unwrap(), wallet());
+    }
+
+    #[test]
+    fn postcard_roundtrip() {
+        let bytes = PostcardCodec::encode(&wallet()).unwrap();
+        assert_eq!(hex_encode(&bytes), "07736176696e6773deadbeef07");
+        assert_eq!(PostcardCodec::decode::<Wallet>(&bytes).unwrap(), wallet());
+    }
+
+    #[test]
+    fn postcard_is_more_compact_than_json() {
+        assert!(
+            PostcardCodec::encode(&wallet()).unwrap().len() * 3
+                < JsonCodec::encode(&wallet()).unwrap().len()
+        );
+    }
+
+    #[test]
+    fn json_decode_invalid_is_err() {
+        assert!(JsonCodec::decode::<Wallet>(b"{}").is_err());
+    }
+
+    #[test]
+    fn postcard_decode_truncated_is_err() {
+        let bytes = PostcardCodec::encode(&wallet()).unwrap();
+        assert!(
+            PostcardCodec::decode::<Wallet>(&bytes[..bytes.len() - 1]).is_err()
+        );
+    }
+
+    #[test]
+    fn postcard_decode_trailing_bytes_is_err() {
+        let mut bytes = PostcardCodec::encode(&wallet()).unwrap();
+        bytes.push(0);
+        assert_eq!(
+            PostcardCodec::decode::<Wallet>(&bytes)
+                .unwrap_err()
+                .to_string(),
+            "Unexpected 1 trailing bytes after value"
+        );
+    }
+}
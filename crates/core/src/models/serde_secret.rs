@@ -0,0 +1,210 @@
+use crate::prelude::*;
+
+use serde::de::DeserializeOwned;
+
+/// Makes any serde type a secret, encoded into bytes using `Codec`, either
+/// [`JsonCodec`] (the default) or the compact binary [`PostcardCodec`].
+///
+/// Never prints the wrapped value, its [`Debug`] implementation is redacted.
+///
+/// # Examples
+///
+/// ```
+/// use svar_core::*;
+///
+/// #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+/// struct Wallet {
+///     name: String,
+///     private_key: [u8; 32],
+/// }
+///
+/// let wallet = Wallet {
+///     name: "savings".to_owned(),
+///     private_key: [0xab; 32],
+/// };
+///
+/// let questions = SecurityQuestionsAnswersAndSalts::sample();
+/// let sealed = SecurityQuestionsSealed::<
+///     SerdeSecret<Wallet, PostcardCodec>,
+///     6,
+///     4,
+/// >::seal(
+///     SerdeSecret::new(wallet.clone()), questions.clone()
+/// )?;
+///
+/// let opened = sealed.open(questions)?;
+/// assert_eq!(opened.into_inner(), wallet);
+/// # Ok::<(), svar_core::Error>(())
+/// ```
+#[derive(
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    derive_more::Debug,
+    derive_more::Deref,
+    derive_more::DerefMut,
+)]
+#[debug("SerdeSecret(<redacted>)")]
+pub struct SerdeSecret<T, Codec = JsonCodec> {
+    #[deref]
+    #[deref_mut]
+    value: T,
+    #[debug(skip)]
+    phantom: std::marker::PhantomData<Codec>,
+}
+
+impl<T, Codec> SerdeSecret<T, Codec> {
+    /// Wraps `value` to be sealed using `Codec`.
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            phantom: std::marker::PhantomData,
+        }
+    }
+
+    /// Returns the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T, Codec> From<T> for SerdeSecret<T, Codec> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T, Codec> IsSecret for SerdeSecret<T, Codec>
+where
+    T: Serialize + DeserializeOwned,
+    Codec: SecretCodec,
+{
+    fn from_bytes(
+        bytes: SecretBytes,
+    ) -> std::result::Result<Self, Box<dyn std::error::Error>> {
+        Codec::decode(bytes.as_ref()).map(Self::new)
+    }
+
+    fn to_bytes(
+        &self,
+    ) -> std::result::Result<SecretBytes, Box<dyn std::error::Error>> {
+        Codec::encode(&self.value).map(SecretBytes::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+    struct Wallet {
+        name: String,
+        private_key: Exactly32Bytes,
+        account_indices: Vec<u32>,
+    }
+
+    fn wallet() -> Wallet {
+        Wallet {
+            name: "savings".to_owned(),
+            private_key: Exactly32Bytes::sample(),
+            account_indices: vec![0, 1, 5],
+        }
+    }
+
+    fn seal_open<Codec: SecretCodec>() {
+        let questions = SecurityQuestionsAnswersAndSalts::sample();
+        let sealed = SecurityQuestionsSealed::<
+            SerdeSecret<Wallet, Codec>,
+            6,
+            4,
+        >::with_schemes(
+            SerdeSecret::new(wallet()),
+            questions.clone(),
+            SecurityQuestionsKdfScheme::version1(),
+            EncryptionScheme::default(),
+        )
+        .unwrap();
+        assert_eq!(sealed.open(questions).unwrap().into_inner(), wallet());
+    }
+
+    #[test]
+    fn seal_open_json() {
+        seal_open::<JsonCodec>();
+    }
+
+    #[test]
+    fn seal_open_postcard() {
+        seal_open::<PostcardCodec>();
+    }
+
+    #[test]
+    fn default_codec_is_json() {
+        let sut = SerdeSecret::<Wallet>::new(wallet());
+        assert_eq!(
+            sut.to_bytes().unwrap().as_ref(),
+            JsonCodec::encode(&wallet()).unwrap()
+        );
+    }
+
+    #[test]
+    fn debug_is_redacted() {
+        let debug = format!("{:?}", SerdeSecret::<Wallet>::new(wallet()));
+        assert_eq!(debug, "SerdeSecret(<redacted>)");
+        assert!(!debug.contains("savings"));
+        assert!(!debug.contains(&format!("{:?}", wallet().private_key)));
+    }
+
+    #[test]
+    fn deref() {
+        let sut = SerdeSecret::<Wallet>::from(wallet());
+        assert_eq!(sut.name, "savings");
+    }
+
+    #[test]
+    fn seal_unserializable_is_failed_to_convert_secret_to_bytes() {
+        // JSON only supports maps keyed by strings
+        let secret = SerdeSecret::<HashMap<Vec<u8>, u8>, JsonCodec>::new(
+            HashMap::from([(vec![1], 2)]),
+        );
+        let result = SecurityQuestionsSealed::<_, 6, 4>::with_schemes(
+            secret,
+            SecurityQuestionsAnswersAndSalts::sample(),
+            SecurityQuestionsKdfScheme::version1(),
+            EncryptionScheme::default(),
+        );
+        assert!(matches!(
+            result,
+            Err(Error::FailedToConvertSecretToBytes { .. })
+        ));
+    }
+
+    #[test]
+    fn open_as_other_type_is_failed_to_convert_bytes_to_secret() {
+        let questions = SecurityQuestionsAnswersAndSalts::sample();
+        let sealed = SecurityQuestionsSealed::<
+            SerdeSecret<String, PostcardCodec>,
+            6,
+            4,
+        >::with_schemes(
+            SerdeSecret::new("not a wallet".to_owned()),
+            questions.clone(),
+            SecurityQuestionsKdfScheme::version1(),
+            EncryptionScheme::default(),
+        )
+        .unwrap();
+        let json = serde_json::to_string(&sealed).unwrap();
+        let sealed: SecurityQuestionsSealed<
+            SerdeSecret<Wallet, PostcardCodec>,
+            6,
+            4,
+        > = serde_json::from_str(&json).unwrap();
+        assert!(matches!(
+            sealed.open(questions),
+            Err(Error::FailedToConvertBytesToSecret { .. })
+        ));
+    }
+}
@@ -0,0 +1,561 @@
+use crate::prelude::*;
+
+use hkdf::Hkdf;
+use sha2::Sha256;
+
+/// The metadata stored next to the encryptions of a sealed secret, which
+/// each encryption of a [`SealedSecretVersion::Version2`] sealed secret
+/// authenticates as associated data.
+pub(crate) struct SealedSecretHeader<'a> {
+    pub(crate) security_questions_and_salts: &'a [SecurityQuestionAndSalt],
+    pub(crate) min_correct_answers: usize,
+    pub(crate) kdf_scheme: &'a SecurityQuestionsKdfScheme,
+    pub(crate) encryption_scheme: &'a EncryptionScheme,
+    pub(crate) sealing_mode: SealingMode,
+}
+
+impl SealedSecretHeader<'_> {
+    /// Prefix of the associated data, separating it from other uses of the
+    /// same keys.
+    const DOMAIN_TAG: &'static [u8] = b"svar/sealed_secret_header/v2";
+
+    /// HKDF salt used to derive the commitment to a key.
+    const KEY_COMMITMENT_DOMAIN_TAG: &'static [u8] =
+        b"svar/sealed_secret_key_commitment/v2";
+
+    /// Byte count of the commitment to the key prefixing each encryption.
+    const KEY_COMMITMENT_LEN: usize = 32;
+
+    /// The canonical encoding of the header, integers are big endian and
+    /// strings and lists are prefixed with their length as `u32`.
+    ///
+    /// The whole KDF scheme is encoded, not just its version, so that none
+    /// of its parameters can be modified without the encryptions failing to
+    /// authenticate. Note that most such modifications also change the
+    /// derived keys, which cannot be told apart from incorrect answers.
+    ///
+    /// The padding policy of the encryption scheme is appended only if the
+    /// plaintext is padded, keeping the encoding of headers sealed before
+    /// padding was introduced unchanged.
+    pub(crate) fn associated_data(&self) -> Vec<u8> {
+        let mut data = Self::DOMAIN_TAG.to_vec();
+
+        put_kdf_scheme(&mut data, self.kdf_scheme);
+        data.extend((self.encryption_scheme.version() as u32).to_be_bytes());
+        data.push(match self.sealing_mode {
+            SealingMode::Combinations => 0,
+            SealingMode::Shamir => 1,
+            SealingMode::Envelope => 2,
+        });
+        put_len(&mut data, self.security_questions_and_salts.len());
+        put_len(&mut data, self.min_correct_answers);
+
+        for SecurityQuestionAndSalt { question, salt } in
+            self.security_questions_and_salts
+        {
+            data.extend(question.id.to_be_bytes());
+            data.push(question.version);
+            match &question.kind {
+                SecurityQuestionKind::Freeform => data.push(0),
+                SecurityQuestionKind::Structured { datasets } => {
+                    data.push(1);
+                    put_len(&mut data, datasets.len());
+                    for dataset in datasets {
+                        data.extend(dataset.id.to_be_bytes());
+                        data.push(dataset.version);
+                    }
+                }
+            }
+            put_str(&mut data, &question.question);
+            data.push(match question.transliteration {
+                None => 0,
+                Some(Transliteration::ToLatinVersion1) => 1,
+            });
+
+            let format = &question.expected_answer_format;
+            put_str(&mut data, &format.answer_structure);
+            put_str(&mut data, &format.example_answer);
+            put_len(&mut data, format.unsafe_answers.len());
+            for unsafe_answer in &format.unsafe_answers {
+                put_str(&mut data, unsafe_answer);
+            }
+            data.push(match format.phonetic_encoding {
+                None => 0,
+                Some(PhoneticEncoding::MetaphoneVersion1) => 1,
+            });
+
+            data.extend(salt.bytes());
+        }
+
+        match self.encryption_scheme.padding() {
+            PaddingPolicy::None => {}
+            PaddingPolicy::Bucket { bucket_size } => {
+                data.push(1);
+                data.extend(bucket_size.get().to_be_bytes());
+            }
+            PaddingPolicy::Fixed { len } => {
+                data.push(2);
+                data.extend(len.get().to_be_bytes());
+            }
+        }
+
+        data
+    }
+
+    /// A commitment to `key`, which unlike the authentication tag of the
+    /// encryption does not depend on the header, allowing a tampered header
+    /// to be told apart from an incorrect key.
+    fn key_commitment(
+        key: &EncryptionKey,
+    ) -> [u8; SealedSecretHeader::KEY_COMMITMENT_LEN] {
+        let hkdf = Hkdf::<Sha256>::new(
+            Some(Self::KEY_COMMITMENT_DOMAIN_TAG),
+            key.0.bytes(),
+        );
+        let mut okm = [0u8; Self::KEY_COMMITMENT_LEN];
+        hkdf.expand(&[], &mut okm)
+            .expect("32 bytes is a valid HKDF-SHA256 output length");
+        okm
+    }
+
+    /// Encrypts `plaintext` authenticating the header, prefixed with a
+    /// commitment to `encryption_key`.
+    pub(crate) fn encrypt(
+        &self,
+        plaintext: impl AsRef<[u8]>,
+        encryption_key: EncryptionKey,
+    ) -> Vec<u8> {
+        let mut encrypted = Self::key_commitment(&encryption_key).to_vec();
+        encrypted.extend(self.encryption_scheme.encrypt_with_associated_data(
+            plaintext,
+            encryption_key,
+            self.associated_data(),
+        ));
+        encrypted
+    }
+
+    /// Decrypts `encrypted` produced by [`encrypt`](Self::encrypt).
+    ///
+    /// Fails with [`Error::FailedToDecryptSealedSecret`] if `decryption_key`
+    /// is not the key committed to, with [`Error::SealedSecretTampered`] if
+    /// it is but the header or the encryption has been modified and with
+    /// [`Error::InvalidPadding`] if the decrypted plaintext was not padded.
+    pub(crate) fn decrypt(
+        &self,
+        encrypted: impl AsRef<[u8]>,
+        decryption_key: EncryptionKey,
+    ) -> Result<Vec<u8>> {
+        let encrypted = encrypted.as_ref();
+        if encrypted.len() < Self::KEY_COMMITMENT_LEN {
+            return Err(Error::InvalidSealedSecret {
+                underlying: format!(
+                    "Expected encryption of at least {} bytes, found {}",
+                    Self::KEY_COMMITMENT_LEN,
+                    encrypted.len()
+                ),
+            });
+        }
+        let (commitment, cipher_text) =
+            encrypted.split_at(Self::KEY_COMMITMENT_LEN);
+        if commitment != Self::key_commitment(&decryption_key) {
+            return Err(Error::FailedToDecryptSealedSecret);
+        }
+        self.encryption_scheme
+            .decrypt_with_associated_data(
+                cipher_text,
+                decryption_key,
+                self.associated_data(),
+            )
+            .map_err(|e| match e {
+                Error::InvalidPadding => e,
+                _ => Error::SealedSecretTampered,
+            })
+    }
+}
+
+fn put_len(data: &mut Vec<u8>, len: usize) {
+    data.extend((len as u32).to_be_bytes());
+}
+
+fn put_str(data: &mut Vec<u8>, s: &str) {
+    put_len(data, s.len());
+    data.extend(s.as_bytes());
+}
+
+/// Appends the canonical encoding of `kdf_scheme`: its version followed by
+/// a tag and the parameters of each of its sub-KDFs.
+fn put_kdf_scheme(data: &mut Vec<u8>, kdf_scheme: &SecurityQuestionsKdfScheme) {
+    match kdf_scheme {
+        SecurityQuestionsKdfScheme::Version1(_) => {
+            data.extend(1u32.to_be_bytes());
+        }
+        SecurityQuestionsKdfScheme::Version2(kdf) => {
+            data.extend(2u32.to_be_bytes());
+            put_entropies_kdf(
+                data,
+                &kdf.entropies_from_questions_answer_and_salt,
+            );
+
+            let stretching = &kdf.answer_stretching;
+            data.extend(stretching.memory_cost_kib.to_be_bytes());
+            data.extend(stretching.iterations.to_be_bytes());
+            data.extend(stretching.parallelism.to_be_bytes());
+
+            data.push(match kdf.kdf_encryption_keys_from_key_exchange_keys {
+                SecurityQuestionsEncryptionKeysKdf::ByXorEntropies(_) => 0,
+                SecurityQuestionsEncryptionKeysKdf::ByHkdfOfSortedEntropies(
+                    _,
+                ) => 1,
+            });
+        }
+    }
+}
+
+fn put_entropies_kdf(data: &mut Vec<u8>, kdf: &SecurityQuestionsEntropiesKdf) {
+    match kdf {
+        SecurityQuestionsEntropiesKdf::LowerTrimUtf8(_) => data.push(0),
+        SecurityQuestionsEntropiesKdf::LowerTrimUtf8ByQuestionId(_) => {
+            data.push(1)
+        }
+        SecurityQuestionsEntropiesKdf::NfkcLowerTrimUtf8ByQuestionId(kdf) => {
+            data.push(2);
+            data.push(kdf.fold_diacritics as u8);
+        }
+        SecurityQuestionsEntropiesKdf::NormalizedByQuestionId(kdf) => {
+            data.push(3);
+            put_normalization(data, &kdf.normalization);
+        }
+    }
+}
+
+fn put_normalization(data: &mut Vec<u8>, normalization: &AnswerNormalization) {
+    let steps = normalization.steps();
+    put_len(data, steps.len());
+    for step in steps {
+        match step {
+            AnswerNormalizationStep::Nfkc => data.push(0),
+            AnswerNormalizationStep::FoldDiacritics => data.push(1),
+            AnswerNormalizationStep::CaseFold => data.push(2),
+            AnswerNormalizationStep::LocaleCaseFold { locale } => {
+                data.push(3);
+                data.push(match locale {
+                    AnswerLocale::Turkish => 0,
+                    AnswerLocale::Azerbaijani => 1,
+                    AnswerLocale::German => 2,
+                    AnswerLocale::Greek => 3,
+                });
+            }
+            AnswerNormalizationStep::StripChars { chars } => {
+                data.push(4);
+                put_str(data, chars);
+            }
+            AnswerNormalizationStep::CollapseWhitespace => data.push(5),
+            AnswerNormalizationStep::RemoveArticles { articles } => {
+                data.push(6);
+                put_len(data, articles.len());
+                for article in articles {
+                    put_str(data, article);
+                }
+            }
+            AnswerNormalizationStep::CollapseNumbers => data.push(7),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_header<R>(
+        modify: impl FnOnce(&mut Vec<SecurityQuestionAndSalt>),
+        f: impl FnOnce(SealedSecretHeader<'_>) -> R,
+    ) -> R {
+        let mut security_questions_and_salts = vec![
+            SecurityQuestionAndSalt::sample(),
+            SecurityQuestionAndSalt::sample_other(),
+        ];
+        modify(&mut security_questions_and_salts);
+        let kdf_scheme = SecurityQuestionsKdfScheme::default();
+        let encryption_scheme = EncryptionScheme::default();
+        f(SealedSecretHeader {
+            security_questions_and_salts: &security_questions_and_salts,
+            min_correct_answers: 1,
+            kdf_scheme: &kdf_scheme,
+            encryption_scheme: &encryption_scheme,
+            sealing_mode: SealingMode::Combinations,
+        })
+    }
+
+    fn associated_data(
+        modify: impl FnOnce(&mut Vec<SecurityQuestionAndSalt>),
+    ) -> Vec<u8> {
+        with_header(modify, |header| header.associated_data())
+    }
+
+    #[test]
+    fn associated_data_is_deterministic() {
+        assert_eq!(associated_data(|_| {}), associated_data(|_| {}));
+    }
+
+    #[test]
+    fn associated_data_depends_on_questions_and_salts() {
+        let unmodified = associated_data(|_| {});
+        assert_ne!(
+            associated_data(|qas| qas[0].question.question.push('?')),
+            unmodified
+        );
+        assert_ne!(associated_data(|qas| qas.swap(0, 1)), unmodified);
+        assert_ne!(
+            associated_data(|qas| qas[1].salt = Exactly32Bytes::sample_aced()),
+            unmodified
+        );
+        assert_ne!(
+            associated_data(|qas| {
+                qas[0]
+                    .question
+                    .expected_answer_format
+                    .unsafe_answers
+                    .push("Teddy".to_owned())
+            }),
+            unmodified
+        );
+        assert_ne!(associated_data(|qas| qas.truncate(1)), unmodified);
+    }
+
+    #[test]
+    fn header_changes_if_phonetic_encoding_changes() {
+        assert_ne!(
+            associated_data(|qas| {
+                qas[0].question.expected_answer_format.phonetic_encoding =
+                    Some(PhoneticEncoding::MetaphoneVersion1)
+            }),
+            associated_data(|_| {})
+        );
+    }
+
+    #[test]
+    fn header_changes_if_transliteration_changes() {
+        assert_ne!(
+            associated_data(|qas| {
+                qas[0].question.transliteration =
+                    Some(Transliteration::ToLatinVersion1)
+            }),
+            associated_data(|_| {})
+        );
+    }
+
+    #[test]
+    fn associated_data_depends_on_schemes_and_counts() {
+        let questions = vec![SecurityQuestionAndSalt::sample()];
+        let header = |min_correct_answers,
+                      kdf_scheme,
+                      encryption_scheme,
+                      sealing_mode| {
+            SealedSecretHeader {
+                security_questions_and_salts: &questions,
+                min_correct_answers,
+                kdf_scheme,
+                encryption_scheme,
+                sealing_mode,
+            }
+            .associated_data()
+        };
+        let kdf = SecurityQuestionsKdfScheme::default();
+        let enc = EncryptionScheme::default();
+        let kdf_version1 = SecurityQuestionsKdfScheme::version1();
+        let enc_version2 = EncryptionScheme::version2();
+        let unmodified = header(1, &kdf, &enc, SealingMode::Combinations);
+        assert_ne!(
+            header(2, &kdf, &enc, SealingMode::Combinations),
+            unmodified
+        );
+        assert_ne!(
+            header(1, &kdf_version1, &enc, SealingMode::Combinations),
+            unmodified
+        );
+        assert_ne!(
+            header(1, &kdf, &enc_version2, SealingMode::Combinations),
+            unmodified
+        );
+        assert_ne!(header(1, &kdf, &enc, SealingMode::Shamir), unmodified);
+        assert_ne!(header(1, &kdf, &enc, SealingMode::Envelope), unmodified);
+        let enc_padded = enc.clone().with_padding(PaddingPolicy::sample());
+        assert_ne!(
+            header(1, &kdf, &enc_padded, SealingMode::Combinations),
+            unmodified
+        );
+        let enc_padded_other =
+            enc.clone().with_padding(PaddingPolicy::sample_other());
+        assert_ne!(
+            header(1, &kdf, &enc_padded_other, SealingMode::Combinations),
+            header(1, &kdf, &enc_padded, SealingMode::Combinations)
+        );
+    }
+
+    #[test]
+    fn associated_data_depends_on_kdf_scheme_parameters() {
+        let questions = vec![SecurityQuestionAndSalt::sample()];
+        let header = |kdf: SecurityQuestionsKDFSchemeVersion2| {
+            SealedSecretHeader {
+                security_questions_and_salts: &questions,
+                min_correct_answers: 1,
+                kdf_scheme: &SecurityQuestionsKdfScheme::Version2(kdf),
+                encryption_scheme: &EncryptionScheme::default(),
+                sealing_mode: SealingMode::Combinations,
+            }
+            .associated_data()
+        };
+        let kdf = SecurityQuestionsKDFSchemeVersion2::default();
+        let unmodified = header(kdf.clone());
+
+        let stretching = kdf.answer_stretching;
+        for answer_stretching in [
+            SecurityQuestionsAnswerStretchingArgon2id {
+                memory_cost_kib: stretching.memory_cost_kib + 1,
+                ..stretching
+            },
+            SecurityQuestionsAnswerStretchingArgon2id {
+                iterations: stretching.iterations + 1,
+                ..stretching
+            },
+            SecurityQuestionsAnswerStretchingArgon2id {
+                parallelism: stretching.parallelism + 1,
+                ..stretching
+            },
+        ] {
+            assert_ne!(
+                header(SecurityQuestionsKDFSchemeVersion2 {
+                    answer_stretching,
+                    ..kdf.clone()
+                }),
+                unmodified
+            );
+        }
+
+        assert_ne!(
+            header(SecurityQuestionsKDFSchemeVersion2 {
+                kdf_encryption_keys_from_key_exchange_keys:
+                    SecurityQuestionsEncryptionKeysKdf::ByXorEntropies(
+                        SecurityQuestionsEncryptionKeysByXorEntropies
+                    ),
+                ..kdf.clone()
+            }),
+            unmodified
+        );
+
+        let normalized = |normalization| {
+            SecurityQuestionsKDFSchemeVersion2 {
+            entropies_from_questions_answer_and_salt:
+                SecurityQuestionsEntropiesKdf::NormalizedByQuestionId(
+                    SecurityQuestionsKeyExchangeKeysFromQandAsNormalizedByQuestionId::new(
+                        normalization,
+                    ),
+                ),
+            ..kdf.clone()
+        }
+        };
+        let normalized_unmodified =
+            header(normalized(AnswerNormalization::sample()));
+        assert_ne!(normalized_unmodified, unmodified);
+        for normalization in [
+            AnswerNormalization::sample_other(),
+            AnswerNormalization::new([AnswerNormalizationStep::CaseFold]),
+            AnswerNormalization::new([
+                AnswerNormalizationStep::CaseFold,
+                AnswerNormalizationStep::StripChars {
+                    chars: "-".to_owned(),
+                },
+            ]),
+            AnswerNormalization::locale_lower_trim(AnswerLocale::Turkish),
+        ] {
+            assert_ne!(
+                header(normalized(normalization)),
+                normalized_unmodified
+            );
+        }
+        assert_ne!(
+            header(normalized(AnswerNormalization::locale_lower_trim(
+                AnswerLocale::Turkish
+            ))),
+            header(normalized(AnswerNormalization::locale_lower_trim(
+                AnswerLocale::Azerbaijani
+            )))
+        );
+    }
+
+    #[test]
+    fn encryption_roundtrip() {
+        with_header(
+            |_| {},
+            |header| {
+                let encrypted =
+                    header.encrypt("open zesame", EncryptionKey::sample());
+                assert_eq!(
+                    header.decrypt(encrypted, EncryptionKey::sample()).unwrap(),
+                    b"open zesame"
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn decrypt_with_other_key_is_failed_to_decrypt() {
+        with_header(
+            |_| {},
+            |header| {
+                let encrypted =
+                    header.encrypt("open zesame", EncryptionKey::sample());
+                assert_eq!(
+                    header.decrypt(encrypted, EncryptionKey::sample_other()),
+                    Err(Error::FailedToDecryptSealedSecret)
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn decrypt_with_tampered_header_is_tampered() {
+        let encrypted = with_header(
+            |_| {},
+            |header| header.encrypt("open zesame", EncryptionKey::sample()),
+        );
+        with_header(
+            |qas| qas[0].question.question.push('?'),
+            |header| {
+                assert_eq!(
+                    header.decrypt(encrypted, EncryptionKey::sample()),
+                    Err(Error::SealedSecretTampered)
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn decrypt_tampered_encryption_is_tampered() {
+        with_header(
+            |_| {},
+            |header| {
+                let mut encrypted =
+                    header.encrypt("open zesame", EncryptionKey::sample());
+                *encrypted.last_mut().unwrap() ^= 1;
+                assert_eq!(
+                    header.decrypt(encrypted, EncryptionKey::sample()),
+                    Err(Error::SealedSecretTampered)
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn decrypt_too_short_is_err() {
+        with_header(
+            |_| {},
+            |header| {
+                assert!(matches!(
+                    header.decrypt([0u8; 31], EncryptionKey::sample()),
+                    Err(Error::InvalidSealedSecret { .. })
+                ));
+            },
+        );
+    }
+}
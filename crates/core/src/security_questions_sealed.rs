@@ -36,6 +36,10 @@ pub const DEFAULT_MIN_CORRECT_ANSWERS: usize = 4;
 /// - Requires at least `MIN_CORRECT_ANSWERS` correct answers for successful
 ///   decryption
 ///
+/// Alternatively, with [`SealingMode::Shamir`], the secret is encrypted once
+/// with a data key split into one share per question, see
+/// [`seal_with_mode`](Self::seal_with_mode).
+///
 /// # Examples
 ///
 /// ## Basic Usage
@@ -124,11 +128,11 @@ pub const DEFAULT_MIN_CORRECT_ANSWERS: usize = 4;
 /// // Define incorrect answer for question 0 - we will use it later to
 /// // demonstrate that we can still decrypt the secret with 3 correct answers
 /// // and 1 incorrect answer
-/// let qas0_incorrect = SecurityQuestionAnswerAndSalt::by_answering_freeform(
-///     q0.clone(),
-///     |_q, _format| "Incorrect answer for Q0".to_owned(),
-/// )
-/// .unwrap();
+/// // Reuse the salt of `qas0`, salts used to seal must be used to open
+/// let qas0_incorrect = SecurityQuestionAnswerAndSalt {
+///     answer: "Incorrect answer for Q0".into(),
+///     ..qas0.clone()
+/// };
 ///
 /// let qas_q0_incorrect = SecurityQuestionsAnswersAndSalts::<Q>::from([
 ///     qas0_incorrect.clone(),
@@ -205,8 +209,12 @@ pub const DEFAULT_MIN_CORRECT_ANSWERS: usize = 4;
 ///   many wrong answers
 /// - [`UnrelatedQuestionProvided`](Error::UnrelatedQuestionProvided): Question
 ///   not in original set
+/// - [`SaltMismatch`](Error::SaltMismatch): Answers were not provided with the
+///   salts used to seal the secret
 /// - [`FailedToConvertBytesToSecret`](Error::FailedToConvertBytesToSecret):
 ///   Secret deserialization failed
+/// - [`SealedSecretTampered`](Error::SealedSecretTampered): The questions,
+///   salts, schemes or encryptions were modified after sealing
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
 pub struct SecurityQuestionsSealed<
     Secret: IsSecret,
@@ -215,7 +223,16 @@ pub struct SecurityQuestionsSealed<
 > {
     /// Holds the type of the secret, used for serialization
     #[serde(skip)]
-    phantom: std::marker::PhantomData<Secret>,
+    pub(crate) phantom: std::marker::PhantomData<Secret>,
+
+    /// The version of the format of the sealed secret, secrets sealed before
+    /// it was recorded are [`SealedSecretVersion::Version1`].
+    ///
+    /// From [`SealedSecretVersion::Version2`] the encryptions authenticate
+    /// the questions, salts, schemes and counts, so that tampering with them
+    /// is detected when opening.
+    #[serde(default = "SealedSecretVersion::version1")]
+    pub version: SealedSecretVersion,
 
     /// The security questions and their cryptographic salts.
     ///
@@ -243,6 +260,35 @@ pub struct SecurityQuestionsSealed<
     /// with a different key derived from various combinations of question
     /// answers. This redundancy enables fault-tolerant decryption.
     pub encryptions: IndexSet<HexBytes>,
+
+    /// The ids of the questions of the combination of answers each of the
+    /// [`encryptions`](field@Self::encryptions) was encrypted with, in the
+    /// same order as `encryptions`.
+    ///
+    /// This allows opening to try only the matching key for each encryption,
+    /// instead of every key for every encryption. Empty for secrets sealed
+    /// before the combinations were recorded, in which case every key is
+    /// tried with every encryption.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub combinations: Vec<Vec<u16>>,
+
+    /// How the secret was sealed, with [`SealingMode::Shamir`]
+    /// `encryptions` contains a single encryption, with a data key recovered
+    /// from [`shares`](field@Self::shares).
+    #[serde(default, skip_serializing_if = "SealingMode::is_combinations")]
+    pub sealing_mode: SealingMode,
+
+    /// The masked Shamir share of the data key of each question, in the
+    /// order of `security_questions_and_salts`. Empty unless sealed with
+    /// [`SealingMode::Shamir`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub shares: Vec<Exactly32Bytes>,
+
+    /// The data key `encryptions` was encrypted with, wrapped with the key
+    /// of each of the [`combinations`](field@Self::combinations), in the same
+    /// order. Empty unless sealed with [`SealingMode::Envelope`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub wrapped_keys: Vec<HexBytes>,
 }
 
 impl<
@@ -309,6 +355,8 @@ impl<
     /// Returns an error if:
     /// - [`InvalidQuestionsAndAnswersCount`](Error::InvalidQuestionsAndAnswersCount):
     ///   Wrong number of questions provided
+    /// - [`DuplicateAnswersToQuestion`](Error::DuplicateAnswersToQuestion):
+    ///   More than one question with the same id provided
     /// - [`FailedToConvertSecretToBytes`](Error::FailedToConvertSecretToBytes):
     ///   Secret serialization failed
     /// - Cryptographic operations fail during key derivation or encryption
@@ -372,81 +420,118 @@ impl<
     /// - Higher memory/time costs provide better security against brute force
     ///   attacks
     /// - Ensure the encryption scheme is appropriate for your security model
-    fn with_schemes(
+    pub fn with_schemes(
         secret: Secret,
         with: SecurityQuestionsAnswersAndSalts<QUESTION_COUNT>,
         kdf_scheme: SecurityQuestionsKdfScheme,
         encryption_scheme: EncryptionScheme,
     ) -> Result<Self> {
-        let questions_answers_and_salts = with;
+        Self::with_schemes_and_mode(
+            secret,
+            with,
+            kdf_scheme,
+            encryption_scheme,
+            SealingMode::default(),
+        )
+    }
 
-        // Clone the security questions from the answers and salts, we need to
-        // store them in the sealed secret
-        let security_questions_and_salts = questions_answers_and_salts
-            .iter()
-            .map(|qa| qa.question_and_salt())
-            .collect::<IndexSet<SecurityQuestionAndSalt>>();
+    /// Encrypts a secret using security questions with default schemes,
+    /// sealed according to `sealing_mode`.
+    ///
+    /// With [`SealingMode::Shamir`] the sealed secret grows linearly, rather
+    /// than combinatorially, with the number of questions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svar_core::*;
+    ///
+    /// let answers = SecurityQuestionsAnswersAndSalts::sample();
+    /// let sealed = SecurityQuestionsSealed::<String, 6, 4>::seal_with_mode(
+    ///     "secret".to_owned(),
+    ///     answers.clone(),
+    ///     SealingMode::Shamir,
+    /// )?;
+    /// assert_eq!(sealed.encryptions.len(), 1);
+    /// assert_eq!(sealed.shares.len(), 6);
+    ///
+    /// let mut answers = answers;
+    /// answers[0].answer = "wrong".into();
+    /// answers[1].answer = "wrong".into();
+    /// assert_eq!(sealed.open(answers)?, "secret");
+    /// # Ok::<(), svar_core::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same errors as [`seal`](Self::seal), plus
+    /// [`TooManyQuestionsForShamir`](Error::TooManyQuestionsForShamir) if
+    /// sealing more than 255 questions with [`SealingMode::Shamir`].
+    pub fn seal_with_mode(
+        secret: Secret,
+        with: SecurityQuestionsAnswersAndSalts<QUESTION_COUNT>,
+        sealing_mode: SealingMode,
+    ) -> Result<Self> {
+        Self::with_schemes_and_mode(
+            secret,
+            with,
+            SecurityQuestionsKdfScheme::default(),
+            EncryptionScheme::default(),
+            sealing_mode,
+        )
+    }
+
+    /// Encrypts a secret using security questions with custom cryptographic
+    /// schemes, sealed according to `sealing_mode`.
+    ///
+    /// See [`with_schemes`](Self::with_schemes) and
+    /// [`seal_with_mode`](Self::seal_with_mode) for details.
+    pub fn with_schemes_and_mode(
+        secret: Secret,
+        with: SecurityQuestionsAnswersAndSalts<QUESTION_COUNT>,
+        kdf_scheme: SecurityQuestionsKdfScheme,
+        encryption_scheme: EncryptionScheme,
+        sealing_mode: SealingMode,
+    ) -> Result<Self> {
+        let SealedParts {
+            version,
+            security_questions_and_salts,
+            encryptions,
+            combinations,
+            shares,
+            wrapped_keys,
+        } = SealedParts::seal(
+            secret,
+            with.as_slice(),
+            MIN_CORRECT_ANSWERS,
+            &kdf_scheme,
+            &encryption_scheme,
+            sealing_mode,
+        )?;
 
         let security_questions_and_salts =
             SecurityQuestionsAndSalts::<QUESTION_COUNT>::try_from_iter(
                 security_questions_and_salts,
             )?;
 
-        // Derive the encryption keys from the questions, answers and salts
-        let encryption_keys = kdf_scheme
-            .derive_encryption_keys_from_questions_answers_and_salts::<QUESTION_COUNT, MIN_CORRECT_ANSWERS>(questions_answers_and_salts)?;
-
-        let secret_bytes = secret.to_bytes().map_err(|e| {
-            Error::FailedToConvertSecretToBytes {
-                underlying: e.to_string(),
-            }
-        })?;
-
-        // Encrypt the secret with each of the derived encryption keys
-        let encryptions = encryption_keys
-            .into_iter()
-            .map(|encryption_key| {
-                encryption_scheme.encrypt(&secret_bytes, encryption_key)
-            })
-            .map(HexBytes::from)
-            .collect::<IndexSet<HexBytes>>();
-
         // Create the sealed secret with the security questions, encryptions,
         // KDF scheme and encryption scheme
         let sealed = Self {
             phantom: std::marker::PhantomData,
+            version,
             security_questions_and_salts,
             encryptions,
             kdf_scheme,
             encryption_scheme,
+            combinations,
+            sealing_mode,
+            shares,
+            wrapped_keys,
         };
 
         Ok(sealed)
     }
 
-    /// Checks if the provided answers to security questions are relevant by
-    /// checking if they answer the questions that were used to encrypt the
-    /// secret.
-    fn are_all_answers_relevant(
-        &self,
-        answers_to_question: &SecurityQuestionsAnswersAndSalts<QUESTION_COUNT>,
-    ) -> Result<()> {
-        let irrelevant_question = answers_to_question.iter().find(|qa| {
-            !self
-                .security_questions_and_salts
-                .iter()
-                .any(|saved| saved.question == qa.question)
-        });
-
-        if let Some(qa) = irrelevant_question {
-            return Err(Error::UnrelatedQuestionProvided {
-                question: qa.question.to_string(),
-            });
-        }
-
-        Ok(())
-    }
-
     /// Decrypts the sealed secret using answers to security questions.
     ///
     /// This method attempts to decrypt the sealed secret by trying different
@@ -539,7 +624,7 @@ impl<
     /// // Create wrong answers but with same questions
     /// let mut wrong_answers = correct_questions.clone();
     /// for answer_and_salt in wrong_answers.iter_mut() {
-    ///     answer_and_salt.answer = "wrong answer".to_string();
+    ///     answer_and_salt.answer = "wrong answer".into();
     /// }
     ///
     /// match sealed.decrypt(wrong_answers) {
@@ -605,6 +690,8 @@ impl<
     ///   original questions used for encryption
     /// * FailedToDecryptSealedSecret: Too many incorrect answers provided
     ///   (fewer than MIN_CORRECT_ANSWERS correct)
+    /// * SaltMismatch: Decryption failed and the salt of at least one answer
+    ///   differs from the salt used to seal the secret
     /// * FailedToConvertBytesToSecret: Decryption succeeded but secret
     ///   deserialization failed
     ///
@@ -613,7 +700,8 @@ impl<
     /// The decryption process:
     /// 1. Validates that all provided questions match the original questions
     /// 2. Derives decryption keys from all possible combinations of answers
-    /// 3. Attempts to decrypt each encrypted version with each derived key
+    /// 3. Attempts to decrypt the encrypted version of each combination with
+    ///    the key derived for it
     /// 4. Returns the first successful decryption and deserialization
     ///
     /// # Security Notes
@@ -628,53 +716,140 @@ impl<
         &self,
         with: SecurityQuestionsAnswersAndSalts<QUESTION_COUNT>,
     ) -> Result<Secret> {
-        let answers_to_question = with;
+        self.open_unvalidated(with.as_slice())
+    }
 
-        self.are_all_answers_relevant(&answers_to_question)?;
+    /// Decrypts the sealed secret using answers to only some of the security
+    /// questions, allowing the user to skip questions they do not remember
+    /// the answer to, instead of having to provide a made up answer.
+    ///
+    /// At least `MIN_CORRECT_ANSWERS` answers must be provided, and at least
+    /// `MIN_CORRECT_ANSWERS` of them must be correct. Only the keys for the
+    /// combinations of questions fully covered by `answers` are derived, so
+    /// skipping questions also makes opening faster.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svar_core::*;
+    ///
+    /// let answers = SecurityQuestionsAnswersAndSalts::sample();
+    /// let sealed = SecurityQuestionsSealed::<String, 6, 4>::seal(
+    ///     "secret".to_owned(),
+    ///     answers.clone(),
+    /// )?;
+    ///
+    /// // Skip the first two questions
+    /// let decrypted = sealed.open_with_subset(answers.iter().skip(2).cloned())?;
+    /// assert_eq!(decrypted, "secret");
+    /// # Ok::<(), svar_core::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * UnrelatedQuestionProvided: One or more questions do not match the
+    ///   original questions used for encryption
+    /// * DuplicateAnswersToQuestion: More than one answer was provided for a
+    ///   question
+    /// * TooFewAnswers: Fewer than MIN_CORRECT_ANSWERS answers were provided
+    /// * FailedToDecryptSealedSecret: Too many incorrect answers provided
+    ///   (fewer than MIN_CORRECT_ANSWERS correct)
+    /// * SaltMismatch: Decryption failed and the salt of at least one answer
+    ///   differs from the salt used to seal the secret
+    /// * FailedToConvertBytesToSecret: Decryption succeeded but secret
+    ///   deserialization failed
+    pub fn open_with_subset(
+        &self,
+        answers: impl IntoIterator<Item = SecurityQuestionAnswerAndSalt>,
+    ) -> Result<Secret> {
+        IsSecurityQuestionsSealed::open_with_subset(self, answers)
+    }
 
-        let decryption_keys = self
-            .kdf_scheme
-            .derive_encryption_keys_from_questions_answers_and_salts::<
-                QUESTION_COUNT,
-                MIN_CORRECT_ANSWERS
-            >(answers_to_question)?;
-
-        let decryption_scheme = &self.encryption_scheme;
-
-        let mut successful_decryption_failure_deserializing: Option<Error> =
-            None;
-
-        for decryption_key in decryption_keys.into_iter() {
-            for encrypted in self.encryptions.iter() {
-                if let Ok(decrypted) = decryption_scheme
-                    .decrypt(encrypted.as_ref(), decryption_key.clone())
-                {
-                    match Secret::from_bytes(decrypted) {
-                        Ok(secret) => return Ok(secret),
-                        Err(deserialize_fail) => {
-                            successful_decryption_failure_deserializing =
-                                Some(Error::FailedToConvertBytesToSecret {
-                                    underlying: deserialize_fail.to_string(),
-                                });
-                        }
-                    }
-                }
-                // Else continue to the next encrypted/key combination
-            }
-        }
+    /// Decrypts the sealed secret just like
+    /// [`open_with_subset`](Self::open_with_subset), but also returns a
+    /// [`SecurityQuestionsAnswersReport`] of which answers were correct, which
+    /// were incorrect and which questions were not answered.
+    ///
+    /// This allows apps to prompt the user to update the answers they did not
+    /// remember, right after they successfully opened the secret.
+    ///
+    /// Unlike [`open`](Self::open), which stops at the first key that
+    /// decrypts the secret, this tries the key of every combination of
+    /// answers, the KDF is however only run once per answer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svar_core::*;
+    ///
+    /// let sealed = SecurityQuestionsSealed::<String, 6, 4>::sample();
+    /// let mut answers = SecurityQuestionsAnswersAndSalts::sample()
+    ///     .iter()
+    ///     .cloned()
+    ///     .collect::<Vec<_>>();
+    /// answers[0].answer = "forgotten".into();
+    /// let skipped = answers.pop().unwrap();
+    ///
+    /// let (secret, report) = sealed.open_with_report(answers.clone())?;
+    /// assert_eq!(secret, "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo wrong");
+    /// assert_eq!(
+    ///     report.incorrect_question_ids(),
+    ///     vec![answers[0].question.id]
+    /// );
+    /// assert_eq!(
+    ///     report.not_provided_question_ids(),
+    ///     vec![skipped.question.id]
+    /// );
+    /// # Ok::<(), svar_core::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same errors as [`open_with_subset`](Self::open_with_subset).
+    pub fn open_with_report(
+        &self,
+        answers: impl IntoIterator<Item = SecurityQuestionAnswerAndSalt>,
+    ) -> Result<(Secret, SecurityQuestionsAnswersReport)> {
+        IsSecurityQuestionsSealed::open_with_report(self, answers)
+    }
 
-        // Failure
-        if let Some(deserialize_err) =
-            successful_decryption_failure_deserializing
-        {
-            // We actual did successful **decrypt** at least one combination,
-            // but we failed to deserialize the bytes into the Secret type,
-            // so instead of throwing a generic `FailedToDecryptSealedSecret`
-            // error we throw the deserialization one.
-            Err(deserialize_err)
-        } else {
-            Err(Error::FailedToDecryptSealedSecret)
-        }
+    /// Decrypts the sealed secret using answers keyed by question id, the
+    /// salts used to seal the secret are looked up from the stored
+    /// `security_questions_and_salts`, so the caller does not need to keep
+    /// track of them.
+    ///
+    /// Just like [`open_with_subset`](Self::open_with_subset) any subset of
+    /// at least `MIN_CORRECT_ANSWERS` questions can be answered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svar_core::*;
+    ///
+    /// let sealed = SecurityQuestionsSealed::<String, 6, 4>::sample();
+    /// let answers = SecurityQuestionsAnswersAndSalts::sample()
+    ///     .iter()
+    ///     .map(|qas| (qas.question.id, qas.answer.clone()))
+    ///     .collect::<Vec<_>>();
+    ///
+    /// let decrypted = sealed.open_with_answers(answers)?;
+    /// assert_eq!(
+    ///     decrypted,
+    ///     "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo wrong"
+    /// );
+    /// # Ok::<(), svar_core::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * UnknownQuestionId: No question with the provided id was used to seal
+    ///   the secret
+    /// * Any error of [`open_with_subset`](Self::open_with_subset)
+    pub fn open_with_answers<Answer: Into<SecurityQuestionAnswer>>(
+        &self,
+        answers: impl IntoIterator<Item = (u16, Answer)>,
+    ) -> Result<Secret> {
+        IsSecurityQuestionsSealed::open_with_answers(self, answers)
     }
 
     /// Just an alias for `open` method. See [`open`](Self::open) for details.
@@ -686,6 +861,56 @@ impl<
     }
 }
 
+impl<
+    Secret: IsSecret,
+    const QUESTION_COUNT: usize,
+    const MIN_CORRECT_ANSWERS: usize,
+> IsSecurityQuestionsSealed
+    for SecurityQuestionsSealed<Secret, QUESTION_COUNT, MIN_CORRECT_ANSWERS>
+{
+    type Secret = Secret;
+
+    fn version(&self) -> SealedSecretVersion {
+        self.version
+    }
+
+    fn security_questions_and_salts(&self) -> &[SecurityQuestionAndSalt] {
+        self.security_questions_and_salts.as_slice()
+    }
+
+    fn min_correct_answers(&self) -> usize {
+        MIN_CORRECT_ANSWERS
+    }
+
+    fn kdf_scheme(&self) -> &SecurityQuestionsKdfScheme {
+        &self.kdf_scheme
+    }
+
+    fn encryption_scheme(&self) -> &EncryptionScheme {
+        &self.encryption_scheme
+    }
+
+    fn encryptions(&self) -> &IndexSet<HexBytes> {
+        &self.encryptions
+    }
+
+    fn combinations(&self) -> &[Vec<u16>] {
+        &self.combinations
+    }
+
+    fn sealing_mode(&self) -> SealingMode {
+        self.sealing_mode
+    }
+
+    fn shares(&self) -> &[Exactly32Bytes] {
+        &self.shares
+    }
+
+    fn wrapped_keys(&self) -> &[HexBytes] {
+        &self.wrapped_keys
+    }
+}
+
 /// Sample implementation for `SecurityQuestionsSealed<String, 6, 4>`.
 ///
 /// Provides sample instances for testing and demonstration purposes. These
@@ -874,6 +1099,81 @@ mod tests {
         assert_eq!(decrypted, secret);
     }
 
+    #[test]
+    fn seal_uses_kdf_version2_by_default() {
+        let sealed = Sut::sample();
+        assert!(matches!(
+            sealed.kdf_scheme,
+            SecurityQuestionsKdfScheme::Version2(_)
+        ));
+        let json = serde_json::to_value(&sealed).unwrap();
+        assert_eq!(
+            json["kdf_scheme"]["Version2"]["answer_stretching"],
+            serde_json::json!({
+                "memory_cost_kib": 19456,
+                "iterations": 2,
+                "parallelism": 1
+            })
+        );
+    }
+
+    #[test]
+    fn seal_open_roundtrip_kdf_version1() {
+        let secret = "such secret much wow".to_owned();
+        let questions_answers_and_salts =
+            SecurityQuestionsAnswersAndSalts::sample();
+        let sealed = Sut::with_schemes(
+            secret.clone(),
+            questions_answers_and_salts.clone(),
+            SecurityQuestionsKdfScheme::version1(),
+            EncryptionScheme::default(),
+        )
+        .unwrap();
+        let json = serde_json::to_string(&sealed).unwrap();
+        let sealed: Sut = serde_json::from_str(&json).unwrap();
+        let decrypted = sealed.open(questions_answers_and_salts).unwrap();
+        assert_eq!(decrypted, secret);
+    }
+
+    #[test]
+    fn seal_open_roundtrip_encryption_version2() {
+        let secret = "such secret much wow".to_owned();
+        let mut answers = SecurityQuestionsAnswersAndSalts::sample();
+        let sealed = Sut::with_schemes(
+            secret.clone(),
+            answers.clone(),
+            SecurityQuestionsKdfScheme::default(),
+            EncryptionScheme::version2(),
+        )
+        .unwrap();
+        let json = serde_json::to_value(&sealed).unwrap();
+        assert_eq!(json["encryption_scheme"]["version"], 2);
+        let sealed: Sut = serde_json::from_value(json).unwrap();
+        assert_eq!(sealed.encryption_scheme, EncryptionScheme::version2());
+
+        answers[0].answer = "wrong answer".into();
+        let decrypted = sealed.open(answers).unwrap();
+        assert_eq!(decrypted, secret);
+    }
+
+    #[test]
+    fn seal_open_roundtrip_encryption_version3() {
+        let secret = "such secret much wow".to_owned();
+        let answers = SecurityQuestionsAnswersAndSalts::sample();
+        let sealed = Sut::with_schemes_and_mode(
+            secret.clone(),
+            answers.clone(),
+            SecurityQuestionsKdfScheme::default(),
+            EncryptionScheme::version3(),
+            SealingMode::Shamir,
+        )
+        .unwrap();
+        let json = serde_json::to_string(&sealed).unwrap();
+        let sealed: Sut = serde_json::from_str(&json).unwrap();
+        assert_eq!(sealed.encryption_scheme, EncryptionScheme::version3());
+        assert_eq!(sealed.open(answers).unwrap(), secret);
+    }
+
     #[test]
     fn seal_secret_type_fails_to_serialize_to_bytes() {
         #[derive(Debug)]
@@ -881,13 +1181,13 @@ mod tests {
         impl IsSecret for Secret {
             fn to_bytes(
                 &self,
-            ) -> std::result::Result<Vec<u8>, Box<dyn std::error::Error>>
+            ) -> std::result::Result<SecretBytes, Box<dyn std::error::Error>>
             {
                 Err("meant to fail for test".into())
             }
 
             fn from_bytes(
-                _: Vec<u8>,
+                _: SecretBytes,
             ) -> std::result::Result<Self, Box<dyn std::error::Error>>
             {
                 unreachable!()
@@ -915,13 +1215,13 @@ mod tests {
         impl IsSecret for Secret {
             fn to_bytes(
                 &self,
-            ) -> std::result::Result<Vec<u8>, Box<dyn std::error::Error>>
+            ) -> std::result::Result<SecretBytes, Box<dyn std::error::Error>>
             {
-                Ok(vec![0xde, 0xad, 0xbe, 0xef])
+                Ok(SecretBytes::sample())
             }
 
             fn from_bytes(
-                _: Vec<u8>,
+                _: SecretBytes,
             ) -> std::result::Result<Self, Box<dyn std::error::Error>>
             {
                 Err("meant to fail for test".into())
@@ -1008,12 +1308,11 @@ mod tests {
         // Define incorrect answer for question 0 - we will use it later to
         // demonstrate that we can still decrypt the secret with 3 correct
         // answers and 1 incorrect answer
-        let qas0_incorrect =
-            SecurityQuestionAnswerAndSalt::by_answering_freeform(
-                q0.clone(),
-                |_q, _format| "Incorrect answer for Q0".to_owned(),
-            )
-            .unwrap();
+        // Reuse the salt of `qas0`, salts used to seal must be used to open
+        let qas0_incorrect = SecurityQuestionAnswerAndSalt {
+            answer: "Incorrect answer for Q0".into(),
+            ..qas0.clone()
+        };
 
         let qas_q0_incorrect = SecurityQuestionsAnswersAndSalts::<Q>::from([
             qas0_incorrect.clone(),
@@ -1030,25 +1329,928 @@ mod tests {
     }
 
     #[test]
-    fn test_that_encrypt_is_just_an_alias_for_seal() {
-        let secret = "such secret much wow".to_owned();
-        let questions_answers_and_salts =
-            SecurityQuestionsAnswersAndSalts::sample();
-        let sealed_by_encrypt =
-            Sut::encrypt(secret.clone(), questions_answers_and_salts.clone())
-                .unwrap();
-        let sealed_by_seal: SecurityQuestionsSealed<String, 6, 4> =
-            Sut::seal(secret.clone(), questions_answers_and_salts.clone())
-                .unwrap();
-
-        let decrypted_by_open = sealed_by_encrypt
-            .open(questions_answers_and_salts.clone())
-            .unwrap();
-        let decrypted_by_decrypt = sealed_by_seal
-            .decrypt(questions_answers_and_salts.clone())
+    fn open_with_subset_of_min_correct_answers() {
+        let sealed = Sut::sample();
+        let answers = SecurityQuestionsAnswersAndSalts::sample();
+        let decrypted = sealed
+            .open_with_subset([5, 0, 3, 1].map(|index| answers[index].clone()))
             .unwrap();
-        assert_eq!(decrypted_by_open, secret);
-        assert_eq!(decrypted_by_decrypt, secret);
-        assert_eq!(decrypted_by_decrypt, decrypted_by_open);
+        assert_eq!(
+            decrypted,
+            "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo wrong"
+        );
+    }
+
+    #[test]
+    fn open_with_subset_one_skipped_one_wrong() {
+        let sealed = Sut::sample();
+        let answers = SecurityQuestionsAnswersAndSalts::sample();
+        let mut subset = answers.iter().skip(1).cloned().collect_vec();
+        subset[0].answer = "wrong answer".into();
+        let decrypted = sealed.open_with_subset(subset).unwrap();
+        assert_eq!(
+            decrypted,
+            "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo wrong"
+        );
+    }
+
+    #[test]
+    fn open_with_subset_too_many_wrong() {
+        let sealed = Sut::sample();
+        let answers = SecurityQuestionsAnswersAndSalts::sample();
+        let mut subset = answers.iter().skip(1).cloned().collect_vec();
+        subset[0].answer = "wrong answer".into();
+        subset[1].answer = "wrong answer".into();
+        assert_eq!(
+            sealed.open_with_subset(subset),
+            Err(Error::FailedToDecryptSealedSecret)
+        );
+    }
+
+    #[test]
+    fn open_with_subset_too_few_answers() {
+        let sealed = Sut::sample();
+        let answers = SecurityQuestionsAnswersAndSalts::sample();
+        assert_eq!(
+            sealed.open_with_subset(answers.iter().take(3).cloned()),
+            Err(Error::TooFewAnswers {
+                expected_at_least: 4,
+                found: 3
+            })
+        );
+    }
+
+    #[test]
+    fn open_with_subset_duplicate_answers() {
+        let sealed = Sut::sample();
+        let answers = SecurityQuestionsAnswersAndSalts::sample();
+        assert_eq!(
+            sealed.open_with_subset(
+                [0, 1, 2, 1].map(|index| answers[index].clone())
+            ),
+            Err(Error::DuplicateAnswersToQuestion {
+                question_id: answers[1].question.id
+            })
+        );
+    }
+
+    #[test]
+    fn open_with_subset_unrelated_question() {
+        let sealed = Sut::sample();
+        let answers = SecurityQuestionsAnswersAndSalts::sample();
+        let mut subset = answers.iter().take(4).cloned().collect_vec();
+        subset
+            .push(SecurityQuestionsAnswersAndSalts::sample_other()[0].clone());
+        assert!(matches!(
+            sealed.open_with_subset(subset),
+            Err(Error::UnrelatedQuestionProvided { .. })
+        ));
+    }
+
+    #[test]
+    fn open_with_answers_looks_up_salts() {
+        let sealed = Sut::sample();
+        let answers = SecurityQuestionsAnswersAndSalts::sample()
+            .iter()
+            .map(|qas| (qas.question.id, qas.answer.clone()))
+            .collect_vec();
+        assert_eq!(
+            sealed.open_with_answers(answers).unwrap(),
+            "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo wrong"
+        );
+    }
+
+    #[test]
+    fn open_with_answers_subset_one_wrong() {
+        let sealed = Sut::sample();
+        let answers = SecurityQuestionsAnswersAndSalts::sample();
+        let decrypted = sealed
+            .open_with_answers([
+                (answers[0].question.id, "wrong answer".into()),
+                (answers[2].question.id, answers[2].answer.clone()),
+                (answers[3].question.id, answers[3].answer.clone()),
+                (answers[4].question.id, answers[4].answer.clone()),
+                (answers[5].question.id, answers[5].answer.clone()),
+            ])
+            .unwrap();
+        assert_eq!(
+            decrypted,
+            "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo wrong"
+        );
+    }
+
+    #[test]
+    fn open_with_answers_unknown_question_id() {
+        let sealed = Sut::sample();
+        assert_eq!(
+            sealed.open_with_answers([(1337, "Oxford")]),
+            Err(Error::UnknownQuestionId { question_id: 1337 })
+        );
+    }
+
+    #[test]
+    fn open_with_fresh_salts_is_salt_mismatch() {
+        let sealed = Sut::sample();
+        let mut answers = SecurityQuestionsAnswersAndSalts::sample();
+        answers[1].salt = Exactly32Bytes::generate();
+        answers[2].salt = Exactly32Bytes::generate();
+        answers[3].salt = Exactly32Bytes::generate();
+        assert_eq!(
+            sealed.open(answers.clone()),
+            Err(Error::SaltMismatch {
+                question_id: answers[1].question.id
+            })
+        );
+        assert_eq!(
+            sealed.open_with_subset(answers.iter().cloned()),
+            Err(Error::SaltMismatch {
+                question_id: answers[1].question.id
+            })
+        );
+    }
+
+    #[test]
+    fn open_with_one_fresh_salt_still_decrypts() {
+        let sealed = Sut::sample();
+        let mut answers = SecurityQuestionsAnswersAndSalts::sample();
+        answers[0].salt = Exactly32Bytes::generate();
+        assert!(sealed.open(answers).is_ok());
+    }
+
+    #[test]
+    fn open_with_report_all_correct() {
+        let sealed = Sut::sample();
+        let answers = SecurityQuestionsAnswersAndSalts::sample();
+        let (secret, report) =
+            sealed.open_with_report(answers.iter().cloned()).unwrap();
+        assert_eq!(secret, "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo wrong");
+        assert_eq!(
+            report.correct_question_ids(),
+            answers.iter().map(|qas| qas.question.id).collect_vec()
+        );
+    }
+
+    #[test]
+    fn open_with_report_two_incorrect_one_skipped() {
+        const Q: usize = 6;
+        const A: usize = 3;
+        let answers = SecurityQuestionsAnswersAndSalts::sample();
+        let sealed = SecurityQuestionsSealed::<String, Q, A>::seal(
+            "secret".to_owned(),
+            answers.clone(),
+        )
+        .unwrap();
+
+        let mut subset = answers.iter().skip(1).cloned().collect_vec();
+        subset[0].answer = "wrong answer".into();
+        subset[3].answer = "wrong answer".into();
+
+        let (secret, report) = sealed.open_with_report(subset).unwrap();
+        assert_eq!(secret, "secret");
+        assert_eq!(
+            report.incorrect_question_ids(),
+            vec![answers[1].question.id, answers[4].question.id]
+        );
+        assert_eq!(
+            report.correct_question_ids(),
+            vec![
+                answers[2].question.id,
+                answers[3].question.id,
+                answers[5].question.id
+            ]
+        );
+        assert_eq!(
+            report.not_provided_question_ids(),
+            vec![answers[0].question.id]
+        );
+        assert!(report.ambiguous_question_ids().is_empty());
+    }
+
+    #[test]
+    fn open_with_report_too_many_wrong() {
+        let sealed = Sut::sample();
+        let mut answers = SecurityQuestionsAnswersAndSalts::sample();
+        answers[0].answer = "wrong answer".into();
+        answers[1].answer = "wrong answer".into();
+        answers[2].answer = "wrong answer".into();
+        assert_eq!(
+            sealed.open_with_report(answers.iter().cloned()),
+            Err(Error::FailedToDecryptSealedSecret)
+        );
+    }
+
+    #[test]
+    fn seal_records_combination_of_each_encryption() {
+        let answers = SecurityQuestionsAnswersAndSalts::sample();
+        let sealed = Sut::sample();
+        assert_eq!(sealed.combinations.len(), 15); // 6 choose 4
+        assert_eq!(sealed.combinations.len(), sealed.encryptions.len());
+        assert_eq!(
+            sealed.combinations[0],
+            answers
+                .iter()
+                .take(4)
+                .map(|qas| qas.question.id)
+                .collect_vec()
+        );
+    }
+
+    #[test]
+    fn each_encryption_is_decrypted_by_key_of_its_combination() {
+        let sealed = Sut::sample();
+        let answers = SecurityQuestionsAnswersAndSalts::sample();
+        let keys = sealed
+            .kdf_scheme
+            .derive_encryption_keys_by_combination_from_subset_of_questions_answers_and_salts(
+                answers.as_slice(),
+                4,
+            )
+            .unwrap();
+        for (combination, encrypted) in
+            sealed.combinations.iter().zip(sealed.encryptions.iter())
+        {
+            let key = keys.get(combination).unwrap().clone();
+            assert!(sealed.decrypt_encryption(encrypted, key).is_ok());
+        }
+    }
+
+    #[test]
+    fn open_answers_in_any_order() {
+        let sealed = Sut::sample();
+        let mut answers = SecurityQuestionsAnswersAndSalts::sample();
+        answers.reverse();
+        answers[0].answer = "wrong answer".into();
+        assert_eq!(
+            sealed.open(answers.clone()).unwrap(),
+            "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo wrong"
+        );
+        assert!(sealed.open_with_subset(answers.iter().cloned()).is_ok());
+    }
+
+    #[test]
+    fn open_without_recorded_combinations() {
+        let mut sealed = Sut::sample();
+        sealed.combinations.clear();
+        let json = serde_json::to_value(&sealed).unwrap();
+        assert!(json.get("combinations").is_none());
+
+        let mut answers = SecurityQuestionsAnswersAndSalts::sample();
+        answers[2].answer = "wrong answer".into();
+        assert_eq!(
+            sealed.open(answers).unwrap(),
+            "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo wrong"
+        );
+    }
+
+    #[test]
+    fn json_roundtrip_keeps_combinations() {
+        let sealed = Sut::sample();
+        let json = serde_json::to_string(&sealed).unwrap();
+        let deserialized: Sut = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.combinations, sealed.combinations);
+    }
+
+    #[test]
+    fn seal_duplicate_question_ids_is_err() {
+        let mut answers = SecurityQuestionsAnswersAndSalts::sample();
+        answers[1].question.id = answers[0].question.id;
+        assert_eq!(
+            Sut::seal("secret".to_owned(), answers.clone()),
+            Err(Error::DuplicateAnswersToQuestion {
+                question_id: answers[0].question.id
+            })
+        );
+    }
+
+    #[test]
+    fn seal_is_version2() {
+        let sealed = Sut::sample();
+        assert_eq!(sealed.version, SealedSecretVersion::Version2);
+        let json = serde_json::to_value(&sealed).unwrap();
+        assert_eq!(json["version"], 2);
+    }
+
+    #[test]
+    fn json_without_version_is_version1() {
+        let json = include_str!(
+            "fixtures/svar_core__security_questions_sealed__tests__serialize.json"
+        );
+        let sut: Sut = serde_json::from_str(json).unwrap();
+        assert_eq!(sut.version, SealedSecretVersion::Version1);
+    }
+
+    fn open_sample_answers(sealed: &Sut) -> Result<String> {
+        sealed.open_with_answers(
+            SecurityQuestionsAnswersAndSalts::sample()
+                .iter()
+                .map(|qas| (qas.question.id, qas.answer.clone())),
+        )
+    }
+
+    #[test]
+    fn open_edited_question_is_tampered() {
+        let mut sealed = Sut::sample();
+        sealed.security_questions_and_salts[0]
+            .question
+            .question
+            .push_str(" Really?");
+        assert_eq!(
+            open_sample_answers(&sealed),
+            Err(Error::SealedSecretTampered)
+        );
+    }
+
+    #[test]
+    fn open_reordered_questions_is_tampered() {
+        let mut sealed = Sut::sample();
+        sealed.security_questions_and_salts.swap(0, 1);
+        assert_eq!(
+            sealed.open(SecurityQuestionsAnswersAndSalts::sample()),
+            Err(Error::SealedSecretTampered)
+        );
+    }
+
+    #[test]
+    fn open_swapped_encryption_scheme_is_tampered() {
+        let mut sealed = Sut::sample();
+        sealed.encryption_scheme = EncryptionScheme::version2();
+        assert_eq!(
+            sealed.open(SecurityQuestionsAnswersAndSalts::sample()),
+            Err(Error::SealedSecretTampered)
+        );
+    }
+
+    #[test]
+    fn open_modified_encryption_is_tampered() {
+        let mut sealed = Sut::sample();
+        sealed.encryptions = sealed
+            .encryptions
+            .iter()
+            .map(|encrypted| {
+                let mut bytes = encrypted.as_ref().to_vec();
+                *bytes.last_mut().unwrap() ^= 1;
+                HexBytes::from(bytes)
+            })
+            .collect();
+        assert_eq!(
+            sealed.open(SecurityQuestionsAnswersAndSalts::sample()),
+            Err(Error::SealedSecretTampered)
+        );
+    }
+
+    #[test]
+    fn open_with_report_tampered() {
+        let mut sealed = Sut::sample();
+        sealed.encryption_scheme = EncryptionScheme::version3();
+        assert_eq!(
+            sealed.open_with_report(
+                SecurityQuestionsAnswersAndSalts::sample().iter().cloned()
+            ),
+            Err(Error::SealedSecretTampered)
+        );
+    }
+
+    #[test]
+    fn open_tampered_with_too_many_wrong_answers_is_failed_to_decrypt() {
+        let mut sealed = Sut::sample();
+        sealed.encryption_scheme = EncryptionScheme::version3();
+        let mut answers = SecurityQuestionsAnswersAndSalts::sample();
+        answers[0].answer = "wrong".into();
+        answers[1].answer = "wrong".into();
+        answers[2].answer = "wrong".into();
+        assert_eq!(
+            sealed.open(answers),
+            Err(Error::FailedToDecryptSealedSecret)
+        );
+    }
+
+    fn sample_shamir() -> Sut {
+        Sut::seal_with_mode(
+            "secret".to_owned(),
+            SecurityQuestionsAnswersAndSalts::sample(),
+            SealingMode::Shamir,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn seal_shamir_grows_linearly() {
+        let sealed = sample_shamir();
+        assert_eq!(sealed.sealing_mode, SealingMode::Shamir);
+        assert_eq!(sealed.encryptions.len(), 1);
+        assert_eq!(sealed.shares.len(), 6);
+        assert!(sealed.combinations.is_empty());
+    }
+
+    #[test]
+    fn seal_shamir_two_wrong_answers() {
+        let sealed = sample_shamir();
+        let mut answers = SecurityQuestionsAnswersAndSalts::sample();
+        answers[1].answer = "wrong answer".into();
+        answers[4].answer = "wrong answer".into();
+        assert_eq!(sealed.open(answers).unwrap(), "secret");
+    }
+
+    #[test]
+    fn seal_shamir_too_many_wrong() {
+        let sealed = sample_shamir();
+        let mut answers = SecurityQuestionsAnswersAndSalts::sample();
+        answers[0].answer = "wrong answer".into();
+        answers[1].answer = "wrong answer".into();
+        answers[2].answer = "wrong answer".into();
+        assert_eq!(
+            sealed.open(answers),
+            Err(Error::FailedToDecryptSealedSecret)
+        );
+    }
+
+    #[test]
+    fn seal_shamir_open_with_subset() {
+        let sealed = sample_shamir();
+        let answers = SecurityQuestionsAnswersAndSalts::sample();
+        let subset = [5, 0, 3, 1].map(|index| answers[index].clone());
+        assert_eq!(sealed.open_with_subset(subset).unwrap(), "secret");
+    }
+
+    #[test]
+    fn seal_shamir_fresh_salts_is_salt_mismatch() {
+        let sealed = sample_shamir();
+        let mut answers = SecurityQuestionsAnswersAndSalts::sample();
+        for answer_and_salt in answers.iter_mut() {
+            answer_and_salt.salt = Exactly32Bytes::generate();
+        }
+        assert_eq!(
+            sealed.open(answers.clone()),
+            Err(Error::SaltMismatch {
+                question_id: answers[0].question.id
+            })
+        );
+    }
+
+    #[test]
+    fn seal_shamir_open_with_report() {
+        let sealed = sample_shamir();
+        let answers = SecurityQuestionsAnswersAndSalts::sample();
+        let mut subset = answers.iter().skip(1).cloned().collect_vec();
+        subset[2].answer = "wrong answer".into();
+
+        let (secret, report) = sealed.open_with_report(subset).unwrap();
+        assert_eq!(secret, "secret");
+        assert_eq!(
+            report.incorrect_question_ids(),
+            vec![answers[3].question.id]
+        );
+        assert_eq!(
+            report.correct_question_ids(),
+            vec![
+                answers[1].question.id,
+                answers[2].question.id,
+                answers[4].question.id,
+                answers[5].question.id
+            ]
+        );
+        assert_eq!(
+            report.not_provided_question_ids(),
+            vec![answers[0].question.id]
+        );
+    }
+
+    #[test]
+    fn seal_shamir_kdf_version1() {
+        let answers = SecurityQuestionsAnswersAndSalts::sample();
+        let sealed = Sut::with_schemes_and_mode(
+            "secret".to_owned(),
+            answers.clone(),
+            SecurityQuestionsKdfScheme::version1(),
+            EncryptionScheme::default(),
+            SealingMode::Shamir,
+        )
+        .unwrap();
+        assert_eq!(sealed.open(answers).unwrap(), "secret");
+    }
+
+    #[test]
+    fn seal_shamir_json_roundtrip() {
+        let sealed = sample_shamir();
+        let json = serde_json::to_value(&sealed).unwrap();
+        assert_eq!(json["sealing_mode"], "Shamir");
+        assert_eq!(json["shares"].as_array().unwrap().len(), 6);
+        let deserialized: Sut = serde_json::from_value(json).unwrap();
+        assert_eq!(deserialized, sealed);
+        assert_eq!(
+            deserialized
+                .open(SecurityQuestionsAnswersAndSalts::sample())
+                .unwrap(),
+            "secret"
+        );
+    }
+
+    #[test]
+    fn seal_shamir_edited_question_is_tampered() {
+        let mut sealed = sample_shamir();
+        sealed.security_questions_and_salts[3]
+            .question
+            .question
+            .push_str(" Really?");
+        assert_eq!(
+            sealed.open_with_answers(
+                SecurityQuestionsAnswersAndSalts::sample()
+                    .iter()
+                    .map(|qas| (qas.question.id, qas.answer.clone())),
+            ),
+            Err(Error::SealedSecretTampered)
+        );
+    }
+
+    #[test]
+    fn seal_shamir_open_with_report_tampered() {
+        let mut sealed = sample_shamir();
+        sealed.encryption_scheme = EncryptionScheme::version2();
+        assert_eq!(
+            sealed.open_with_report(
+                SecurityQuestionsAnswersAndSalts::sample().iter().cloned()
+            ),
+            Err(Error::SealedSecretTampered)
+        );
+    }
+
+    fn sample_envelope() -> Sut {
+        Sut::seal_with_mode(
+            "secret".to_owned(),
+            SecurityQuestionsAnswersAndSalts::sample(),
+            SealingMode::Envelope,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn seal_envelope_encrypts_once() {
+        let sealed = sample_envelope();
+        assert_eq!(sealed.sealing_mode, SealingMode::Envelope);
+        assert_eq!(sealed.encryptions.len(), 1);
+        assert_eq!(sealed.wrapped_keys.len(), 15);
+        assert_eq!(sealed.combinations.len(), 15);
+        assert!(sealed.shares.is_empty());
+    }
+
+    #[test]
+    fn seal_envelope_size_does_not_grow_with_combinations() {
+        let secret = "a".repeat(100_000);
+        let sealed = Sut::seal_with_mode(
+            secret.clone(),
+            SecurityQuestionsAnswersAndSalts::sample(),
+            SealingMode::Envelope,
+        )
+        .unwrap();
+        let encrypted_len = sealed.encryptions[0].as_ref().len();
+        assert!(encrypted_len < secret.len() + 100);
+        assert!(
+            sealed
+                .wrapped_keys
+                .iter()
+                .all(|wrapped| wrapped.as_ref().len() < 100)
+        );
+        assert_eq!(
+            sealed.open(SecurityQuestionsAnswersAndSalts::sample()),
+            Ok(secret)
+        );
+    }
+
+    #[test]
+    fn seal_envelope_two_wrong_answers() {
+        let sealed = sample_envelope();
+        let mut answers = SecurityQuestionsAnswersAndSalts::sample();
+        answers[0].answer = "wrong answer".into();
+        answers[5].answer = "wrong answer".into();
+        assert_eq!(sealed.open(answers).unwrap(), "secret");
+    }
+
+    #[test]
+    fn seal_envelope_too_many_wrong() {
+        let sealed = sample_envelope();
+        let mut answers = SecurityQuestionsAnswersAndSalts::sample();
+        answers[0].answer = "wrong answer".into();
+        answers[1].answer = "wrong answer".into();
+        answers[2].answer = "wrong answer".into();
+        assert_eq!(
+            sealed.open(answers),
+            Err(Error::FailedToDecryptSealedSecret)
+        );
+    }
+
+    #[test]
+    fn seal_envelope_each_wrapped_key_unwraps_data_key() {
+        let sealed = sample_envelope();
+        let answers = SecurityQuestionsAnswersAndSalts::sample();
+        let keys = sealed
+            .kdf_scheme
+            .derive_encryption_keys_by_combination_from_subset_of_questions_answers_and_salts(
+                answers.as_slice(),
+                4,
+            )
+            .unwrap();
+        let data_keys = sealed
+            .combinations
+            .iter()
+            .zip(sealed.wrapped_keys.iter())
+            .map(|(combination, wrapped)| {
+                let key = keys.get(combination).unwrap();
+                sealed.unwrap_data_key(wrapped, key).unwrap()
+            })
+            .collect::<IndexSet<_>>();
+        assert_eq!(data_keys.len(), 1);
+    }
+
+    #[test]
+    fn seal_envelope_open_with_report() {
+        let sealed = sample_envelope();
+        let mut answers = SecurityQuestionsAnswersAndSalts::sample();
+        answers[2].answer = "wrong answer".into();
+        let (secret, report) =
+            sealed.open_with_report(answers.iter().cloned()).unwrap();
+        assert_eq!(secret, "secret");
+        assert_eq!(
+            report.incorrect_question_ids(),
+            vec![answers[2].question.id]
+        );
+    }
+
+    #[test]
+    fn seal_envelope_json_roundtrip() {
+        let sealed = sample_envelope();
+        let json = serde_json::to_value(&sealed).unwrap();
+        assert_eq!(json["sealing_mode"], "Envelope");
+        let deserialized: Sut = serde_json::from_value(json).unwrap();
+        assert_eq!(deserialized, sealed);
+        assert_eq!(
+            deserialized
+                .open(SecurityQuestionsAnswersAndSalts::sample())
+                .unwrap(),
+            "secret"
+        );
+    }
+
+    #[test]
+    fn seal_envelope_tampered_wrapped_key_is_tampered() {
+        let mut sealed = sample_envelope();
+        sealed.wrapped_keys = sealed
+            .wrapped_keys
+            .iter()
+            .map(|wrapped| {
+                let mut bytes = wrapped.as_ref().to_vec();
+                *bytes.last_mut().unwrap() ^= 1;
+                HexBytes::from(bytes)
+            })
+            .collect();
+        assert_eq!(
+            sealed.open(SecurityQuestionsAnswersAndSalts::sample()),
+            Err(Error::SealedSecretTampered)
+        );
+    }
+
+    #[test]
+    fn seal_envelope_without_recorded_combinations() {
+        let mut sealed = sample_envelope();
+        sealed.combinations.clear();
+        assert_eq!(
+            sealed
+                .open(SecurityQuestionsAnswersAndSalts::sample())
+                .unwrap(),
+            "secret"
+        );
+    }
+
+    #[test]
+    fn seal_combinations_does_not_serialize_sealing_mode_or_shares() {
+        let json = serde_json::to_value(Sut::sample()).unwrap();
+        assert!(json.get("sealing_mode").is_none());
+        assert!(json.get("shares").is_none());
+        assert!(json.get("wrapped_keys").is_none());
+    }
+
+    #[test]
+    fn seal_shamir_missing_shares_is_err() {
+        let mut sealed = sample_shamir();
+        sealed.shares.pop();
+        assert!(matches!(
+            sealed.open(SecurityQuestionsAnswersAndSalts::sample()),
+            Err(Error::InvalidSealedSecret { .. })
+        ));
+    }
+
+    #[test]
+    fn test_that_encrypt_is_just_an_alias_for_seal() {
+        let secret = "such secret much wow".to_owned();
+        let questions_answers_and_salts =
+            SecurityQuestionsAnswersAndSalts::sample();
+        let sealed_by_encrypt =
+            Sut::encrypt(secret.clone(), questions_answers_and_salts.clone())
+                .unwrap();
+        let sealed_by_seal: SecurityQuestionsSealed<String, 6, 4> =
+            Sut::seal(secret.clone(), questions_answers_and_salts.clone())
+                .unwrap();
+
+        let decrypted_by_open = sealed_by_encrypt
+            .open(questions_answers_and_salts.clone())
+            .unwrap();
+        let decrypted_by_decrypt = sealed_by_seal
+            .decrypt(questions_answers_and_salts.clone())
+            .unwrap();
+        assert_eq!(decrypted_by_open, secret);
+        assert_eq!(decrypted_by_decrypt, secret);
+        assert_eq!(decrypted_by_decrypt, decrypted_by_open);
+    }
+
+    fn seal_padded(secret: &str, padding: PaddingPolicy) -> Result<Sut> {
+        Sut::with_schemes(
+            secret.to_owned(),
+            SecurityQuestionsAnswersAndSalts::sample(),
+            SecurityQuestionsKdfScheme::version1(),
+            EncryptionScheme::default().with_padding(padding),
+        )
+    }
+
+    #[test]
+    fn seal_padded_hides_secret_length() {
+        let twelve_words = ["zoo"; 11].join(" ") + " wrong";
+        let twenty_four_words = ["zoo"; 23].join(" ") + " vote";
+        let encryption_len = |secret: &str| {
+            seal_padded(secret, PaddingPolicy::sample())
+                .unwrap()
+                .encryptions
+                .first()
+                .unwrap()
+                .as_ref()
+                .len()
+        };
+        assert_eq!(
+            encryption_len(&twelve_words),
+            encryption_len(&twenty_four_words)
+        );
+    }
+
+    #[test]
+    fn open_padded_removes_padding() {
+        for padding in [PaddingPolicy::sample(), PaddingPolicy::sample_other()]
+        {
+            let sealed = seal_padded("open zesame", padding).unwrap();
+            assert_eq!(
+                sealed.open(SecurityQuestionsAnswersAndSalts::sample()),
+                Ok("open zesame".to_owned())
+            );
+        }
+    }
+
+    #[test]
+    fn seal_too_long_for_fixed_padding_is_err() {
+        let secret = "a".repeat(64);
+        assert_eq!(
+            seal_padded(&secret, PaddingPolicy::sample_other()),
+            Err(Error::SecretTooLongForPadding { max: 63, found: 64 })
+        );
+    }
+
+    #[test]
+    fn padded_json_roundtrip() {
+        let sealed =
+            seal_padded("open zesame", PaddingPolicy::sample()).unwrap();
+        let json = serde_json::to_string(&sealed).unwrap();
+        let deserialized: Sut = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, sealed);
+        assert_eq!(
+            deserialized.open(SecurityQuestionsAnswersAndSalts::sample()),
+            Ok("open zesame".to_owned())
+        );
+    }
+
+    #[test]
+    fn open_with_removed_padding_is_tampered() {
+        let mut sealed =
+            seal_padded("open zesame", PaddingPolicy::sample()).unwrap();
+        sealed.encryption_scheme = EncryptionScheme::default();
+        assert_eq!(
+            sealed.open(SecurityQuestionsAnswersAndSalts::sample()),
+            Err(Error::SealedSecretTampered)
+        );
+    }
+
+    #[test]
+    fn open_with_nfkc_normalized_answers() {
+        let kdf_scheme = SecurityQuestionsKdfScheme::Version2(
+            SecurityQuestionsKDFSchemeVersion2 {
+                entropies_from_questions_answer_and_salt:
+                    SecurityQuestionsEntropiesKdf::NfkcLowerTrimUtf8ByQuestionId(
+                        SecurityQuestionsKeyExchangeKeysFromQandAsNfkcLowerTrimUtf8ByQuestionId::default(),
+                    ),
+                answer_stretching:
+                    SecurityQuestionsAnswerStretchingArgon2id::new(64, 1, 1),
+                ..SecurityQuestionsKDFSchemeVersion2::default()
+            },
+        );
+        let sealed = Sut::with_schemes(
+            "open zesame".to_owned(),
+            SecurityQuestionsAnswersAndSalts::sample(),
+            kdf_scheme,
+            EncryptionScheme::default(),
+        )
+        .unwrap();
+
+        // Typed on other keyboards, more than `QUESTION_COUNT -
+        // MIN_CORRECT_ANSWERS` answers differ before normalization
+        let mut answers = SecurityQuestionsAnswersAndSalts::sample();
+        answers[0].answer =
+            "\u{FF2D}\u{FF29}\u{FF34}, year \u{FF14}, Python".into();
+        answers[1].answer = "London, \u{FF11}\u{FF19}\u{FF17}\u{FF13}".into();
+        answers[2].answer =
+            "Jean-Michel Jarre, Paris La De\u{301}fense, 1990".into();
+        answers[5].answer = "T\u{14D}ky\u{14D}, 1989".into();
+
+        assert_eq!(sealed.open(answers), Ok("open zesame".to_owned()));
+    }
+
+    #[test]
+    fn open_with_custom_normalization_after_json_roundtrip() {
+        let normalization = AnswerNormalization::new([
+            AnswerNormalizationStep::CaseFold,
+            AnswerNormalizationStep::RemoveArticles {
+                articles: vec!["the".to_owned(), "a".to_owned()],
+            },
+            AnswerNormalizationStep::CollapseNumbers,
+            AnswerNormalizationStep::StripChars {
+                chars: " -,.".to_owned(),
+            },
+        ]);
+        let kdf_scheme = SecurityQuestionsKdfScheme::Version2(
+            SecurityQuestionsKDFSchemeVersion2 {
+                entropies_from_questions_answer_and_salt:
+                    SecurityQuestionsEntropiesKdf::NormalizedByQuestionId(
+                        SecurityQuestionsKeyExchangeKeysFromQandAsNormalizedByQuestionId::new(
+                            normalization.clone(),
+                        ),
+                    ),
+                answer_stretching:
+                    SecurityQuestionsAnswerStretchingArgon2id::new(64, 1, 1),
+                ..SecurityQuestionsKDFSchemeVersion2::default()
+            },
+        );
+        let sealed = Sut::with_schemes(
+            "open zesame".to_owned(),
+            SecurityQuestionsAnswersAndSalts::sample(),
+            kdf_scheme,
+            EncryptionScheme::default(),
+        )
+        .unwrap();
+
+        // The normalization is stored in the sealed JSON...
+        let json = serde_json::to_string(&sealed).unwrap();
+        assert!(json.contains(&serde_json::to_string(&normalization).unwrap()));
+        let sealed: Sut = serde_json::from_str(&json).unwrap();
+
+        // ... and replayed when opening, more than `QUESTION_COUNT -
+        // MIN_CORRECT_ANSWERS` answers differ before normalization
+        let mut answers = SecurityQuestionsAnswersAndSalts::sample();
+        answers[0].answer = "mit year 04 python".into();
+        answers[1].answer = "london 1973".into();
+        answers[2].answer =
+            "Jean Michel Jarre Paris La D\u{E9}fense 1990".into();
+        answers[4].answer = "Behind shed in oak tree forrest".into();
+
+        assert_eq!(sealed.open(answers), Ok("open zesame".to_owned()));
+    }
+
+    #[test]
+    fn open_with_transliterated_answer_after_json_roundtrip() {
+        let mut answers = SecurityQuestionsAnswersAndSalts::sample();
+        answers[3].question = answers[3]
+            .question
+            .clone()
+            .with_transliteration(Transliteration::ToLatinVersion1);
+        answers[3].answer = "Сергей Петров".into();
+        let kdf_scheme = SecurityQuestionsKdfScheme::Version2(
+            SecurityQuestionsKDFSchemeVersion2 {
+                answer_stretching:
+                    SecurityQuestionsAnswerStretchingArgon2id::new(64, 1, 1),
+                ..SecurityQuestionsKDFSchemeVersion2::default()
+            },
+        );
+        let sealed = Sut::with_schemes(
+            "open zesame".to_owned(),
+            answers.clone(),
+            kdf_scheme,
+            EncryptionScheme::default(),
+        )
+        .unwrap();
+        let json = serde_json::to_string(&sealed).unwrap();
+        let sealed: Sut = serde_json::from_str(&json).unwrap();
+
+        // Exactly `MIN_CORRECT_ANSWERS` answers, so the transliterated one
+        // must be correct
+        answers[3].answer = "Sergei Petrov".into();
+        assert_eq!(
+            sealed.open_with_subset(
+                [0, 1, 2, 3].map(|index| answers[index].clone())
+            ),
+            Ok("open zesame".to_owned())
+        );
     }
 }
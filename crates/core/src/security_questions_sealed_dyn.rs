@@ -0,0 +1,1174 @@
+use crate::prelude::*;
+
+/// A secret encrypted using security questions and their answers, just like
+/// [`SecurityQuestionsSealed`], but with the number of questions and the
+/// minimum number of correct answers known only at runtime.
+///
+/// Unlike [`SecurityQuestionsSealed`] the question count and the minimum
+/// number of correct answers are stored in the serialized form and validated
+/// when deserialized, so a service storing secrets sealed with different
+/// parameters can deserialize them without knowing the parameters up front.
+///
+/// Converts to and from [`SecurityQuestionsSealed`] when the sizes match.
+///
+/// # Examples
+///
+/// ```
+/// use svar_core::*;
+///
+/// let answers = SecurityQuestionsAnswersAndSalts::sample();
+/// let sealed = SecurityQuestionsSealedDyn::<String>::seal(
+///     "secret".to_owned(),
+///     answers.iter().cloned(),
+///     4,
+/// )?;
+///
+/// let json = serde_json::to_value(&sealed).unwrap();
+/// assert_eq!(json["question_count"], 6);
+/// assert_eq!(json["min_correct_answers"], 4);
+///
+/// let sealed: SecurityQuestionsSealedDyn<String> =
+///     serde_json::from_value(json).unwrap();
+/// assert_eq!(sealed.open(answers.iter().cloned())?, "secret");
+///
+/// let sealed = SecurityQuestionsSealed::<String, 6, 4>::try_from(sealed)?;
+/// assert_eq!(sealed.open(answers)?, "secret");
+/// # Ok::<(), svar_core::Error>(())
+/// ```
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(try_from = "UnvalidatedSecurityQuestionsSealedDyn")]
+pub struct SecurityQuestionsSealedDyn<Secret: IsSecret> {
+    /// Holds the type of the secret, used for serialization
+    #[serde(skip)]
+    phantom: std::marker::PhantomData<Secret>,
+
+    /// The version of the format of the sealed secret, see
+    /// [`version`](field@SecurityQuestionsSealed::version) of
+    /// [`SecurityQuestionsSealed`].
+    #[serde(default = "SealedSecretVersion::version1")]
+    version: SealedSecretVersion,
+
+    /// The number of security questions.
+    question_count: usize,
+
+    /// The minimum number of correct answers required to open the secret.
+    min_correct_answers: usize,
+
+    /// The security questions and their cryptographic salts.
+    security_questions_and_salts: Vec<SecurityQuestionAndSalt>,
+
+    /// The Key Derivation Function (KDF) algorithm configuration.
+    kdf_scheme: SecurityQuestionsKdfScheme,
+
+    /// The encryption algorithm configuration.
+    encryption_scheme: EncryptionScheme,
+
+    /// The encrypted secret, once per combination of answers.
+    encryptions: IndexSet<HexBytes>,
+
+    /// The ids of the questions of the combination of answers each of the
+    /// encryptions was encrypted with, see
+    /// [`SecurityQuestionsSealed::combinations`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    combinations: Vec<Vec<u16>>,
+
+    /// How the secret was sealed.
+    #[serde(default, skip_serializing_if = "SealingMode::is_combinations")]
+    sealing_mode: SealingMode,
+
+    /// The masked Shamir share of the data key of each question, see
+    /// [`SecurityQuestionsSealed::shares`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    shares: Vec<Exactly32Bytes>,
+
+    /// The data key wrapped with the key of each combination, see
+    /// [`wrapped_keys`](field@SecurityQuestionsSealed::wrapped_keys) of
+    /// [`SecurityQuestionsSealed`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    wrapped_keys: Vec<HexBytes>,
+}
+
+#[derive(Deserialize)]
+struct UnvalidatedSecurityQuestionsSealedDyn {
+    #[serde(default = "SealedSecretVersion::version1")]
+    version: SealedSecretVersion,
+    question_count: usize,
+    min_correct_answers: usize,
+    security_questions_and_salts: Vec<SecurityQuestionAndSalt>,
+    kdf_scheme: SecurityQuestionsKdfScheme,
+    encryption_scheme: EncryptionScheme,
+    encryptions: IndexSet<HexBytes>,
+    #[serde(default)]
+    combinations: Vec<Vec<u16>>,
+    #[serde(default)]
+    sealing_mode: SealingMode,
+    #[serde(default)]
+    shares: Vec<Exactly32Bytes>,
+    #[serde(default)]
+    wrapped_keys: Vec<HexBytes>,
+}
+
+fn invalid(underlying: impl Into<String>) -> Error {
+    Error::InvalidSealedSecret {
+        underlying: underlying.into(),
+    }
+}
+
+impl UnvalidatedSecurityQuestionsSealedDyn {
+    /// The maximum number of questions of a secret sealed with one
+    /// encryption or wrapped key per combination of questions, bounding the
+    /// number of combinations to count.
+    const MAX_COMBINATIONS_QUESTION_COUNT: usize = 64;
+
+    /// The number of combinations of `min_correct_answers` out of
+    /// `question_count` questions, failing if there are too many questions.
+    fn expected_combinations_count(&self) -> Result<usize> {
+        if self.question_count > Self::MAX_COMBINATIONS_QUESTION_COUNT {
+            return Err(invalid(format!(
+                "Expected at most {} questions, found {}",
+                Self::MAX_COMBINATIONS_QUESTION_COUNT,
+                self.question_count
+            )));
+        }
+        combinations_count(self.question_count, self.min_correct_answers)
+    }
+
+    /// Validates a secret sealed with [`SealingMode::Combinations`], which
+    /// has one encryption per combination of questions.
+    fn validate_combinations(
+        &self,
+        question_ids: &IndexSet<u16>,
+    ) -> Result<()> {
+        if !self.shares.is_empty() {
+            return Err(invalid("Unexpected shares"));
+        }
+        if !self.wrapped_keys.is_empty() {
+            return Err(invalid("Unexpected wrapped keys"));
+        }
+        let expected_encryptions = self.expected_combinations_count()?;
+        if self.encryptions.len() != expected_encryptions {
+            return Err(invalid(format!(
+                "Expected {expected_encryptions} encryptions, found {}",
+                self.encryptions.len()
+            )));
+        }
+        self.validate_recorded_combinations(
+            question_ids,
+            self.encryptions.len(),
+        )
+    }
+
+    /// Validates a secret sealed with [`SealingMode::Envelope`], which has a
+    /// single encryption and one wrapped key per combination of questions.
+    fn validate_wrapped_keys(
+        &self,
+        question_ids: &IndexSet<u16>,
+    ) -> Result<()> {
+        if !self.shares.is_empty() {
+            return Err(invalid("Unexpected shares"));
+        }
+        if self.encryptions.len() != 1 {
+            return Err(invalid(format!(
+                "Expected 1 encryption, found {}",
+                self.encryptions.len()
+            )));
+        }
+        let expected_wrapped_keys = self.expected_combinations_count()?;
+        if self.wrapped_keys.len() != expected_wrapped_keys {
+            return Err(invalid(format!(
+                "Expected {expected_wrapped_keys} wrapped keys, found {}",
+                self.wrapped_keys.len()
+            )));
+        }
+        self.validate_recorded_combinations(
+            question_ids,
+            self.wrapped_keys.len(),
+        )
+    }
+
+    /// Validates that the combinations, if recorded, are one per each of
+    /// `recorded_count` encryptions or wrapped keys and consist of
+    /// `min_correct_answers` distinct ids of the questions.
+    fn validate_recorded_combinations(
+        &self,
+        question_ids: &IndexSet<u16>,
+        recorded_count: usize,
+    ) -> Result<()> {
+        if self.combinations.is_empty() {
+            return Ok(());
+        }
+        if self.combinations.len() != recorded_count {
+            return Err(invalid(format!(
+                "Expected {recorded_count} combinations, found {}",
+                self.combinations.len()
+            )));
+        }
+        if let Some(combination) =
+            self.combinations.iter().find(|combination| {
+                combination.len() != self.min_correct_answers
+                    || !combination.iter().all_unique()
+                    || !combination.iter().all(|id| question_ids.contains(id))
+            })
+        {
+            return Err(invalid(format!(
+                "Invalid combination: {combination:?}"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Validates a secret sealed with [`SealingMode::Shamir`], which has a
+    /// single encryption and one share per question.
+    fn validate_shares(&self) -> Result<()> {
+        if self.question_count > ShamirShare::MAX_SHARE_COUNT {
+            return Err(Error::TooManyQuestionsForShamir {
+                max: ShamirShare::MAX_SHARE_COUNT,
+                found: self.question_count,
+            });
+        }
+        if !self.combinations.is_empty() {
+            return Err(invalid("Unexpected combinations"));
+        }
+        if !self.wrapped_keys.is_empty() {
+            return Err(invalid("Unexpected wrapped keys"));
+        }
+        if self.encryptions.len() != 1 {
+            return Err(invalid(format!(
+                "Expected 1 encryption, found {}",
+                self.encryptions.len()
+            )));
+        }
+        if self.shares.len() != self.question_count {
+            return Err(invalid(format!(
+                "Expected {} shares, found {}",
+                self.question_count,
+                self.shares.len()
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl<Secret: IsSecret> TryFrom<UnvalidatedSecurityQuestionsSealedDyn>
+    for SecurityQuestionsSealedDyn<Secret>
+{
+    type Error = Error;
+
+    fn try_from(value: UnvalidatedSecurityQuestionsSealedDyn) -> Result<Self> {
+        let question_count = value.question_count;
+        let min_correct_answers = value.min_correct_answers;
+
+        if value.security_questions_and_salts.len() != question_count {
+            return Err(Error::InvalidQuestionsAndSaltCount {
+                expected: question_count,
+                found: value.security_questions_and_salts.len(),
+            });
+        }
+        if min_correct_answers == 0 || min_correct_answers > question_count {
+            return Err(Error::InvalidMinCorrectAnswers {
+                min_correct_answers,
+                question_count,
+            });
+        }
+        let question_ids = value
+            .security_questions_and_salts
+            .iter()
+            .map(|saved| saved.question.id)
+            .collect::<IndexSet<_>>();
+        if question_ids.len() != question_count {
+            return Err(invalid("Duplicate question ids"));
+        }
+        match value.sealing_mode {
+            SealingMode::Combinations => {
+                value.validate_combinations(&question_ids)?
+            }
+            SealingMode::Shamir => value.validate_shares()?,
+            SealingMode::Envelope => {
+                value.validate_wrapped_keys(&question_ids)?
+            }
+        }
+
+        Ok(Self {
+            phantom: std::marker::PhantomData,
+            version: value.version,
+            question_count,
+            min_correct_answers,
+            security_questions_and_salts: value.security_questions_and_salts,
+            kdf_scheme: value.kdf_scheme,
+            encryption_scheme: value.encryption_scheme,
+            encryptions: value.encryptions,
+            combinations: value.combinations,
+            sealing_mode: value.sealing_mode,
+            shares: value.shares,
+            wrapped_keys: value.wrapped_keys,
+        })
+    }
+}
+
+impl<Secret: IsSecret> SecurityQuestionsSealedDyn<Secret> {
+    /// Encrypts a secret using answers to security questions with default
+    /// schemes, requiring `min_correct_answers` correct answers to open it.
+    ///
+    /// See [`SecurityQuestionsSealed::seal`] for details.
+    ///
+    /// # Errors
+    ///
+    /// * InvalidMinCorrectAnswers: `min_correct_answers` is zero or greater
+    ///   than the number of answers
+    /// * DuplicateAnswersToQuestion: More than one question with the same id
+    ///   provided
+    /// * FailedToConvertSecretToBytes: Secret serialization failed
+    pub fn seal(
+        secret: Secret,
+        with: impl IntoIterator<Item = SecurityQuestionAnswerAndSalt>,
+        min_correct_answers: usize,
+    ) -> Result<Self> {
+        Self::with_schemes(
+            secret,
+            with,
+            min_correct_answers,
+            SecurityQuestionsKdfScheme::default(),
+            EncryptionScheme::default(),
+        )
+    }
+
+    /// Just an alias for `seal` method. See [`seal`](Self::seal) for details.
+    pub fn encrypt(
+        secret: Secret,
+        with: impl IntoIterator<Item = SecurityQuestionAnswerAndSalt>,
+        min_correct_answers: usize,
+    ) -> Result<Self> {
+        Self::seal(secret, with, min_correct_answers)
+    }
+
+    /// Encrypts a secret using answers to security questions with custom
+    /// cryptographic schemes.
+    ///
+    /// See [`SecurityQuestionsSealed::with_schemes`] for details.
+    pub fn with_schemes(
+        secret: Secret,
+        with: impl IntoIterator<Item = SecurityQuestionAnswerAndSalt>,
+        min_correct_answers: usize,
+        kdf_scheme: SecurityQuestionsKdfScheme,
+        encryption_scheme: EncryptionScheme,
+    ) -> Result<Self> {
+        Self::with_schemes_and_mode(
+            secret,
+            with,
+            min_correct_answers,
+            kdf_scheme,
+            encryption_scheme,
+            SealingMode::default(),
+        )
+    }
+
+    /// Encrypts a secret using answers to security questions with default
+    /// schemes, sealed according to `sealing_mode`.
+    ///
+    /// See [`SecurityQuestionsSealed::seal_with_mode`] for details.
+    pub fn seal_with_mode(
+        secret: Secret,
+        with: impl IntoIterator<Item = SecurityQuestionAnswerAndSalt>,
+        min_correct_answers: usize,
+        sealing_mode: SealingMode,
+    ) -> Result<Self> {
+        Self::with_schemes_and_mode(
+            secret,
+            with,
+            min_correct_answers,
+            SecurityQuestionsKdfScheme::default(),
+            EncryptionScheme::default(),
+            sealing_mode,
+        )
+    }
+
+    /// Encrypts a secret using answers to security questions with custom
+    /// cryptographic schemes, sealed according to `sealing_mode`.
+    ///
+    /// See [`SecurityQuestionsSealed::with_schemes_and_mode`] for details.
+    pub fn with_schemes_and_mode(
+        secret: Secret,
+        with: impl IntoIterator<Item = SecurityQuestionAnswerAndSalt>,
+        min_correct_answers: usize,
+        kdf_scheme: SecurityQuestionsKdfScheme,
+        encryption_scheme: EncryptionScheme,
+        sealing_mode: SealingMode,
+    ) -> Result<Self> {
+        let questions_answers_and_salts = with.into_iter().collect_vec();
+
+        let SealedParts {
+            version,
+            security_questions_and_salts,
+            encryptions,
+            combinations,
+            shares,
+            wrapped_keys,
+        } = SealedParts::seal(
+            secret,
+            &questions_answers_and_salts,
+            min_correct_answers,
+            &kdf_scheme,
+            &encryption_scheme,
+            sealing_mode,
+        )?;
+
+        Ok(Self {
+            phantom: std::marker::PhantomData,
+            version,
+            question_count: security_questions_and_salts.len(),
+            min_correct_answers,
+            security_questions_and_salts,
+            kdf_scheme,
+            encryption_scheme,
+            encryptions,
+            combinations,
+            sealing_mode,
+            shares,
+            wrapped_keys,
+        })
+    }
+
+    /// The version of the format of the sealed secret.
+    pub fn version(&self) -> SealedSecretVersion {
+        self.version
+    }
+
+    /// The number of security questions.
+    pub fn question_count(&self) -> usize {
+        self.question_count
+    }
+
+    /// The minimum number of correct answers required to open the secret.
+    pub fn min_correct_answers(&self) -> usize {
+        self.min_correct_answers
+    }
+
+    /// The security questions and their cryptographic salts.
+    pub fn security_questions_and_salts(&self) -> &[SecurityQuestionAndSalt] {
+        &self.security_questions_and_salts
+    }
+
+    /// The Key Derivation Function (KDF) algorithm configuration.
+    pub fn kdf_scheme(&self) -> &SecurityQuestionsKdfScheme {
+        &self.kdf_scheme
+    }
+
+    /// The encryption algorithm configuration.
+    pub fn encryption_scheme(&self) -> &EncryptionScheme {
+        &self.encryption_scheme
+    }
+
+    /// The encrypted secret, once per combination of answers.
+    pub fn encryptions(&self) -> &IndexSet<HexBytes> {
+        &self.encryptions
+    }
+
+    /// The ids of the questions of the combination of answers each of the
+    /// [`encryptions`](Self::encryptions) was encrypted with.
+    pub fn combinations(&self) -> &[Vec<u16>] {
+        &self.combinations
+    }
+
+    /// How the secret was sealed.
+    pub fn sealing_mode(&self) -> SealingMode {
+        self.sealing_mode
+    }
+
+    /// The masked Shamir share of the data key of each question, see
+    /// [`shares`](field@SecurityQuestionsSealed::shares) of
+    /// [`SecurityQuestionsSealed`].
+    pub fn shares(&self) -> &[Exactly32Bytes] {
+        &self.shares
+    }
+
+    /// The data key wrapped with the key of each combination, see
+    /// [`wrapped_keys`](field@SecurityQuestionsSealed::wrapped_keys) of
+    /// [`SecurityQuestionsSealed`].
+    pub fn wrapped_keys(&self) -> &[HexBytes] {
+        &self.wrapped_keys
+    }
+
+    /// Decrypts the sealed secret using answers to at least
+    /// [`min_correct_answers`](Self::min_correct_answers) of the security
+    /// questions.
+    ///
+    /// Behaves like [`SecurityQuestionsSealed::open_with_subset`], see it for
+    /// details and errors.
+    pub fn open(
+        &self,
+        with: impl IntoIterator<Item = SecurityQuestionAnswerAndSalt>,
+    ) -> Result<Secret> {
+        IsSecurityQuestionsSealed::open_with_subset(self, with)
+    }
+
+    /// Just an alias for `open` method. See [`open`](Self::open) for details.
+    pub fn decrypt(
+        &self,
+        with: impl IntoIterator<Item = SecurityQuestionAnswerAndSalt>,
+    ) -> Result<Secret> {
+        self.open(with)
+    }
+
+    /// Decrypts the sealed secret using answers keyed by question id, looking
+    /// up the salts used to seal the secret.
+    ///
+    /// Behaves like [`SecurityQuestionsSealed::open_with_answers`], see it for
+    /// details and errors.
+    pub fn open_with_answers<Answer: Into<SecurityQuestionAnswer>>(
+        &self,
+        answers: impl IntoIterator<Item = (u16, Answer)>,
+    ) -> Result<Secret> {
+        IsSecurityQuestionsSealed::open_with_answers(self, answers)
+    }
+
+    /// Decrypts the sealed secret and reports which answers were correct.
+    ///
+    /// Behaves like [`SecurityQuestionsSealed::open_with_report`], see it for
+    /// details and errors.
+    pub fn open_with_report(
+        &self,
+        answers: impl IntoIterator<Item = SecurityQuestionAnswerAndSalt>,
+    ) -> Result<(Secret, SecurityQuestionsAnswersReport)> {
+        IsSecurityQuestionsSealed::open_with_report(self, answers)
+    }
+}
+
+impl<Secret: IsSecret> IsSecurityQuestionsSealed
+    for SecurityQuestionsSealedDyn<Secret>
+{
+    type Secret = Secret;
+
+    fn version(&self) -> SealedSecretVersion {
+        self.version
+    }
+
+    fn security_questions_and_salts(&self) -> &[SecurityQuestionAndSalt] {
+        &self.security_questions_and_salts
+    }
+
+    fn min_correct_answers(&self) -> usize {
+        self.min_correct_answers
+    }
+
+    fn kdf_scheme(&self) -> &SecurityQuestionsKdfScheme {
+        &self.kdf_scheme
+    }
+
+    fn encryption_scheme(&self) -> &EncryptionScheme {
+        &self.encryption_scheme
+    }
+
+    fn encryptions(&self) -> &IndexSet<HexBytes> {
+        &self.encryptions
+    }
+
+    fn combinations(&self) -> &[Vec<u16>] {
+        &self.combinations
+    }
+
+    fn sealing_mode(&self) -> SealingMode {
+        self.sealing_mode
+    }
+
+    fn shares(&self) -> &[Exactly32Bytes] {
+        &self.shares
+    }
+
+    fn wrapped_keys(&self) -> &[HexBytes] {
+        &self.wrapped_keys
+    }
+}
+
+impl<
+    Secret: IsSecret,
+    const QUESTION_COUNT: usize,
+    const MIN_CORRECT_ANSWERS: usize,
+> From<SecurityQuestionsSealed<Secret, QUESTION_COUNT, MIN_CORRECT_ANSWERS>>
+    for SecurityQuestionsSealedDyn<Secret>
+{
+    fn from(
+        value: SecurityQuestionsSealed<
+            Secret,
+            QUESTION_COUNT,
+            MIN_CORRECT_ANSWERS,
+        >,
+    ) -> Self {
+        Self {
+            phantom: std::marker::PhantomData,
+            version: value.version,
+            question_count: QUESTION_COUNT,
+            min_correct_answers: MIN_CORRECT_ANSWERS,
+            security_questions_and_salts: value
+                .security_questions_and_salts
+                .to_vec(),
+            kdf_scheme: value.kdf_scheme,
+            encryption_scheme: value.encryption_scheme,
+            encryptions: value.encryptions,
+            combinations: value.combinations,
+            sealing_mode: value.sealing_mode,
+            shares: value.shares,
+            wrapped_keys: value.wrapped_keys,
+        }
+    }
+}
+
+impl<
+    Secret: IsSecret,
+    const QUESTION_COUNT: usize,
+    const MIN_CORRECT_ANSWERS: usize,
+> TryFrom<SecurityQuestionsSealedDyn<Secret>>
+    for SecurityQuestionsSealed<Secret, QUESTION_COUNT, MIN_CORRECT_ANSWERS>
+{
+    type Error = Error;
+
+    fn try_from(value: SecurityQuestionsSealedDyn<Secret>) -> Result<Self> {
+        if value.min_correct_answers != MIN_CORRECT_ANSWERS {
+            return Err(invalid(format!(
+                "Expected min correct answers {MIN_CORRECT_ANSWERS}, found {}",
+                value.min_correct_answers
+            )));
+        }
+        let security_questions_and_salts =
+            SecurityQuestionsAndSalts::<QUESTION_COUNT>::try_from_iter(
+                value.security_questions_and_salts,
+            )?;
+
+        Ok(Self {
+            phantom: std::marker::PhantomData,
+            version: value.version,
+            security_questions_and_salts,
+            kdf_scheme: value.kdf_scheme,
+            encryption_scheme: value.encryption_scheme,
+            encryptions: value.encryptions,
+            combinations: value.combinations,
+            sealing_mode: value.sealing_mode,
+            shares: value.shares,
+            wrapped_keys: value.wrapped_keys,
+        })
+    }
+}
+
+impl HasSampleValues for SecurityQuestionsSealedDyn<String> {
+    /// [`SecurityQuestionsSealed::sample`] as a dynamically sized sealed
+    /// secret.
+    fn sample() -> Self {
+        SecurityQuestionsSealed::<String, 6, 4>::sample().into()
+    }
+
+    /// [`SecurityQuestionsSealed::sample_other`] as a dynamically sized
+    /// sealed secret.
+    fn sample_other() -> Self {
+        SecurityQuestionsSealed::<String, 6, 4>::sample_other().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Sut = SecurityQuestionsSealedDyn<String>;
+
+    fn answers() -> Vec<SecurityQuestionAnswerAndSalt> {
+        SecurityQuestionsAnswersAndSalts::sample().to_vec()
+    }
+
+    #[test]
+    fn equality() {
+        let sut = Sut::sample();
+        assert_eq!(sut, sut.clone());
+    }
+
+    #[test]
+    fn inequality() {
+        assert_ne!(Sut::sample(), Sut::sample_other());
+    }
+
+    #[test]
+    fn seal_open_roundtrip() {
+        let sut = Sut::seal("secret".to_owned(), answers(), 3).unwrap();
+        assert_eq!(sut.question_count(), 6);
+        assert_eq!(sut.min_correct_answers(), 3);
+        assert_eq!(sut.encryptions().len(), 20); // 6 choose 3
+
+        let mut answers = answers();
+        answers[0].answer = "wrong".into();
+        answers[1].answer = "wrong".into();
+        answers[2].answer = "wrong".into();
+        assert_eq!(sut.open(answers.clone()).unwrap(), "secret");
+
+        answers[3].answer = "wrong".into();
+        assert_eq!(sut.open(answers), Err(Error::FailedToDecryptSealedSecret));
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        let sut = Sut::sample();
+        let json = serde_json::to_string(&sut).unwrap();
+        let deserialized: Sut = serde_json::from_str(&json).unwrap();
+        assert_eq!(sut, deserialized);
+        assert_eq!(
+            deserialized.open(answers()).unwrap(),
+            "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo wrong"
+        );
+    }
+
+    #[test]
+    fn same_keys_as_const_generic() {
+        let sealed = SecurityQuestionsSealed::<String, 6, 4>::sample();
+        let sut = Sut::with_schemes(
+            "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo wrong".to_owned(),
+            answers(),
+            4,
+            sealed.kdf_scheme.clone(),
+            sealed.encryption_scheme.clone(),
+        )
+        .unwrap();
+        assert_eq!(sut.combinations(), sealed.combinations.as_slice());
+
+        // Encryptions are randomized by their nonce, so instead we open
+        // the const generic one with the keys of the dynamic one.
+        let dyn_as_const =
+            SecurityQuestionsSealed::<String, 6, 4>::try_from(sut).unwrap();
+        assert_eq!(
+            dyn_as_const.open(SecurityQuestionsAnswersAndSalts::sample()),
+            sealed.open(SecurityQuestionsAnswersAndSalts::sample())
+        );
+    }
+
+    #[test]
+    fn from_and_to_const_generic() {
+        let sealed = SecurityQuestionsSealed::<String, 6, 4>::sample();
+        let sut = Sut::from(sealed.clone());
+        assert_eq!(sut.question_count(), 6);
+        assert_eq!(sut.min_correct_answers(), 4);
+        let back =
+            SecurityQuestionsSealed::<String, 6, 4>::try_from(sut).unwrap();
+        assert_eq!(back, sealed);
+    }
+
+    #[test]
+    fn to_const_generic_wrong_question_count_is_err() {
+        let result =
+            SecurityQuestionsSealed::<String, 5, 4>::try_from(Sut::sample());
+        assert_eq!(
+            result,
+            Err(Error::InvalidQuestionsAndSaltCount {
+                expected: 5,
+                found: 6
+            })
+        );
+    }
+
+    #[test]
+    fn to_const_generic_wrong_min_correct_answers_is_err() {
+        let result =
+            SecurityQuestionsSealed::<String, 6, 3>::try_from(Sut::sample());
+        assert!(matches!(result, Err(Error::InvalidSealedSecret { .. })));
+    }
+
+    #[test]
+    fn seal_zero_min_correct_answers_is_err() {
+        assert_eq!(
+            Sut::seal("secret".to_owned(), answers(), 0),
+            Err(Error::InvalidMinCorrectAnswers {
+                min_correct_answers: 0,
+                question_count: 6
+            })
+        );
+    }
+
+    #[test]
+    fn seal_phonetic_encoding_with_kdf_version1_is_err() {
+        let mut answers = answers();
+        answers[1].question.expected_answer_format.phonetic_encoding =
+            Some(PhoneticEncoding::MetaphoneVersion1);
+        assert_eq!(
+            Sut::with_schemes(
+                "secret".to_owned(),
+                answers.clone(),
+                4,
+                SecurityQuestionsKdfScheme::version1(),
+                EncryptionScheme::default(),
+            ),
+            Err(Error::AnswerProcessingNotSupportedByKdfScheme {
+                question_id: answers[1].question.id
+            })
+        );
+    }
+
+    fn deserialize_modified(
+        modify: impl FnOnce(&mut serde_json::Value),
+    ) -> Result<Sut, serde_json::Error> {
+        let mut json = serde_json::to_value(Sut::sample()).unwrap();
+        modify(&mut json);
+        serde_json::from_value(json)
+    }
+
+    #[test]
+    fn deserialize_wrong_question_count_is_err() {
+        let result = deserialize_modified(|json| {
+            json["question_count"] = 5.into();
+        });
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Invalid questions and salt count")
+        );
+    }
+
+    #[test]
+    fn deserialize_invalid_min_correct_answers_is_err() {
+        let result = deserialize_modified(|json| {
+            json["min_correct_answers"] = 7.into();
+        });
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Invalid min correct answers")
+        );
+    }
+
+    #[test]
+    fn deserialize_wrong_encryptions_count_is_err() {
+        let result = deserialize_modified(|json| {
+            json["min_correct_answers"] = 3.into();
+        });
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Expected 20 encryptions, found 15")
+        );
+    }
+
+    /// Replaces the questions of a serialized sealed secret with
+    /// `question_count` questions with distinct ids, requiring
+    /// `min_correct_answers` of them.
+    fn set_question_count(
+        json: &mut serde_json::Value,
+        question_count: usize,
+        min_correct_answers: usize,
+    ) {
+        let saved = json["security_questions_and_salts"][0].clone();
+        json["security_questions_and_salts"] = (0..question_count)
+            .map(|id| {
+                let mut saved = saved.clone();
+                saved["question"]["id"] = id.into();
+                saved
+            })
+            .collect();
+        json["question_count"] = question_count.into();
+        json["min_correct_answers"] = min_correct_answers.into();
+    }
+
+    #[test]
+    fn deserialize_too_many_questions_is_err() {
+        let result = deserialize_modified(|json| {
+            set_question_count(json, 70, 35);
+        });
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Expected at most 64 questions, found 70")
+        );
+    }
+
+    #[test]
+    fn deserialize_combinations_count_overflow_is_err() {
+        let result = deserialize_modified(|json| {
+            set_question_count(json, 64, 32);
+        });
+        assert!(result.unwrap_err().to_string().contains("overflows"));
+    }
+
+    #[test]
+    fn deserialize_envelope_too_many_questions_is_err() {
+        let result = deserialize_modified(|json| {
+            set_question_count(json, 70, 35);
+            json["sealing_mode"] =
+                serde_json::to_value(SealingMode::Envelope).unwrap();
+            json["encryptions"].as_array_mut().unwrap().truncate(1);
+        });
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Expected at most 64 questions, found 70")
+        );
+    }
+
+    #[test]
+    fn deserialize_invalid_combination_is_err() {
+        let result = deserialize_modified(|json| {
+            json["combinations"][0] = serde_json::json!([1, 1, 1, 1]);
+        });
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Invalid combination")
+        );
+    }
+
+    #[test]
+    fn shamir_seal_open_roundtrip() {
+        let sut = Sut::seal_with_mode(
+            "secret".to_owned(),
+            answers(),
+            3,
+            SealingMode::Shamir,
+        )
+        .unwrap();
+        assert_eq!(sut.encryptions().len(), 1);
+        assert_eq!(sut.shares().len(), 6);
+
+        let json = serde_json::to_string(&sut).unwrap();
+        let sut: Sut = serde_json::from_str(&json).unwrap();
+
+        let mut answers = answers();
+        answers[0].answer = "wrong".into();
+        answers[5].answer = "wrong".into();
+        answers[3].answer = "wrong".into();
+        assert_eq!(sut.open(answers).unwrap(), "secret");
+    }
+
+    #[test]
+    fn shamir_to_const_generic() {
+        let sut = Sut::seal_with_mode(
+            "secret".to_owned(),
+            answers(),
+            4,
+            SealingMode::Shamir,
+        )
+        .unwrap();
+        let sealed =
+            SecurityQuestionsSealed::<String, 6, 4>::try_from(sut).unwrap();
+        assert_eq!(sealed.sealing_mode, SealingMode::Shamir);
+        assert_eq!(
+            sealed
+                .open(SecurityQuestionsAnswersAndSalts::sample())
+                .unwrap(),
+            "secret"
+        );
+    }
+
+    #[test]
+    fn deserialize_shamir_wrong_share_count_is_err() {
+        let sut = Sut::seal_with_mode(
+            "secret".to_owned(),
+            answers(),
+            4,
+            SealingMode::Shamir,
+        )
+        .unwrap();
+        let mut json = serde_json::to_value(sut).unwrap();
+        json["shares"].as_array_mut().unwrap().pop();
+        let result = serde_json::from_value::<Sut>(json);
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Expected 6 shares, found 5")
+        );
+    }
+
+    #[test]
+    fn deserialize_shares_with_combinations_mode_is_err() {
+        let result = deserialize_modified(|json| {
+            json["shares"] = serde_json::json!([Exactly32Bytes::sample_aced()]);
+        });
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Unexpected shares")
+        );
+    }
+
+    #[test]
+    fn envelope_seal_open_roundtrip() {
+        let sut = Sut::seal_with_mode(
+            "secret".to_owned(),
+            answers(),
+            3,
+            SealingMode::Envelope,
+        )
+        .unwrap();
+        assert_eq!(sut.encryptions().len(), 1);
+        assert_eq!(sut.wrapped_keys().len(), 20);
+
+        let json = serde_json::to_string(&sut).unwrap();
+        let sut: Sut = serde_json::from_str(&json).unwrap();
+
+        let mut answers = answers();
+        answers[1].answer = "wrong".into();
+        answers[2].answer = "wrong".into();
+        answers[4].answer = "wrong".into();
+        assert_eq!(sut.open(answers).unwrap(), "secret");
+    }
+
+    #[test]
+    fn envelope_to_const_generic() {
+        let sut = Sut::seal_with_mode(
+            "secret".to_owned(),
+            answers(),
+            4,
+            SealingMode::Envelope,
+        )
+        .unwrap();
+        let sealed =
+            SecurityQuestionsSealed::<String, 6, 4>::try_from(sut).unwrap();
+        assert_eq!(sealed.wrapped_keys.len(), 15);
+        assert_eq!(
+            sealed
+                .open(SecurityQuestionsAnswersAndSalts::sample())
+                .unwrap(),
+            "secret"
+        );
+    }
+
+    #[test]
+    fn deserialize_envelope_wrong_wrapped_key_count_is_err() {
+        let sut = Sut::seal_with_mode(
+            "secret".to_owned(),
+            answers(),
+            4,
+            SealingMode::Envelope,
+        )
+        .unwrap();
+        let mut json = serde_json::to_value(sut).unwrap();
+        json["wrapped_keys"].as_array_mut().unwrap().pop();
+        let result = serde_json::from_value::<Sut>(json);
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Expected 15 wrapped keys, found 14")
+        );
+    }
+
+    #[test]
+    fn deserialize_wrapped_keys_with_combinations_mode_is_err() {
+        let result = deserialize_modified(|json| {
+            json["wrapped_keys"] = serde_json::json!(["deadbeef"]);
+        });
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Unexpected wrapped keys")
+        );
+    }
+
+    #[test]
+    fn version_roundtrips_through_const_generic() {
+        let sealed = Sut::sample();
+        assert_eq!(sealed.version(), SealedSecretVersion::Version2);
+        let sealed =
+            SecurityQuestionsSealed::<String, 6, 4>::try_from(sealed).unwrap();
+        assert_eq!(sealed.version, SealedSecretVersion::Version2);
+    }
+
+    #[test]
+    fn deserialize_without_version_is_version1() {
+        let result = deserialize_modified(|json| {
+            json.as_object_mut().unwrap().remove("version");
+        })
+        .unwrap();
+        assert_eq!(result.version(), SealedSecretVersion::Version1);
+    }
+
+    #[test]
+    fn open_json_with_reordered_questions_is_tampered() {
+        let result = deserialize_modified(|json| {
+            json["security_questions_and_salts"]
+                .as_array_mut()
+                .unwrap()
+                .swap(2, 3);
+        })
+        .unwrap();
+        assert_eq!(result.open(answers()), Err(Error::SealedSecretTampered));
+    }
+
+    #[test]
+    fn open_json_with_swapped_encryption_scheme_is_tampered() {
+        let result = deserialize_modified(|json| {
+            json["encryption_scheme"] =
+                serde_json::to_value(EncryptionScheme::version3()).unwrap();
+        })
+        .unwrap();
+        assert_eq!(result.open(answers()), Err(Error::SealedSecretTampered));
+    }
+
+    #[test]
+    fn open_json_with_added_transliteration_is_tampered() {
+        let result = deserialize_modified(|json| {
+            json["security_questions_and_salts"][0]["question"]
+                ["transliteration"] =
+                serde_json::to_value(Transliteration::ToLatinVersion1).unwrap();
+        })
+        .unwrap();
+        let answers = answers()
+            .into_iter()
+            .map(|qas| (qas.question.id, qas.answer));
+        assert_eq!(
+            result.open_with_answers(answers),
+            Err(Error::SealedSecretTampered)
+        );
+    }
+
+    #[test]
+    fn open_json_with_modified_normalization_is_tampered() {
+        let SecurityQuestionsKdfScheme::Version2(kdf) =
+            Sut::sample().kdf_scheme().clone()
+        else {
+            unreachable!("sample uses KDF Version2")
+        };
+        let kdf_scheme = |steps: &[AnswerNormalizationStep]| {
+            SecurityQuestionsKdfScheme::Version2(
+                SecurityQuestionsKDFSchemeVersion2 {
+                    entropies_from_questions_answer_and_salt:
+                        SecurityQuestionsEntropiesKdf::NormalizedByQuestionId(
+                            SecurityQuestionsKeyExchangeKeysFromQandAsNormalizedByQuestionId::new(
+                                AnswerNormalization::new(steps.iter().cloned()),
+                            ),
+                        ),
+                    ..kdf.clone()
+                },
+            )
+        };
+        let steps = AnswerNormalization::lower_trim().steps().to_vec();
+        let sealed = Sut::with_schemes(
+            "secret".to_owned(),
+            answers(),
+            4,
+            kdf_scheme(&steps),
+            EncryptionScheme::default(),
+        )
+        .unwrap();
+
+        // Collapsing whitespace after all whitespace has been stripped does
+        // not change the keys, only the header.
+        let mut json = serde_json::to_value(sealed).unwrap();
+        json["kdf_scheme"] = serde_json::to_value(kdf_scheme(
+            &[steps, vec![AnswerNormalizationStep::CollapseWhitespace]]
+                .concat(),
+        ))
+        .unwrap();
+        let tampered = serde_json::from_value::<Sut>(json).unwrap();
+        assert_eq!(tampered.open(answers()), Err(Error::SealedSecretTampered));
+    }
+
+    #[test]
+    fn deserialize_without_combinations() {
+        let result = deserialize_modified(|json| {
+            json.as_object_mut().unwrap().remove("combinations");
+        })
+        .unwrap();
+        assert!(result.combinations().is_empty());
+        assert!(result.open(answers()).is_ok());
+    }
+}
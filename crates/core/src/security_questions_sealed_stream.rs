@@ -0,0 +1,666 @@
+use crate::prelude::*;
+
+use std::io::{ErrorKind, Read, Write};
+
+/// A secret too large to be held in memory, e.g. a file, sealed as a stream.
+///
+/// The plaintext is read from any [`Read`] in chunks of
+/// [`CHUNK_LEN`](Self::CHUNK_LEN) bytes, each encrypted with a random data
+/// key and written to any [`Write`]. The data key is sealed with the answers
+/// to the security questions, as a [`SecurityQuestionsSealedDyn`], in the
+/// header of the stream.
+///
+/// In the spirit of the STREAM construction, each chunk authenticates its
+/// index and whether it is the last chunk as associated data, so chunks
+/// cannot be reordered, dropped or appended without being detected.
+///
+/// Since chunks are written as soon as they are decrypted, the output of
+/// [`open`](Self::open) failing because the stream has been tampered with
+/// or truncated may contain the chunks preceding the failure, which must be
+/// discarded.
+///
+/// # Format
+///
+/// - The magic bytes `svarstrm` followed by the format version as one byte.
+/// - The byte count of the header as big endian `u32`, followed by the header,
+///   the JSON of the sealed data key.
+/// - The chunks, each one byte which is `1` for the last chunk and `0`
+///   otherwise, the byte count of the encrypted chunk as big endian `u32`, and
+///   the encrypted chunk. The plaintext of the last chunk is followed by one
+///   terminator byte, so that it is never empty.
+///
+/// # Examples
+///
+/// ```
+/// use svar_core::*;
+///
+/// let answers = SecurityQuestionsAnswersAndSalts::sample();
+/// let plaintext = vec![0xab; 100_000];
+///
+/// let mut sealed = Vec::new();
+/// SecurityQuestionsSealedStream::seal(
+///     plaintext.as_slice(),
+///     &mut sealed,
+///     answers.iter().cloned(),
+///     4,
+/// )?;
+///
+/// // The questions are read from the header, before answering them
+/// let mut reader = sealed.as_slice();
+/// let stream = SecurityQuestionsSealedStream::read_header(&mut reader)?;
+/// assert_eq!(stream.security_questions_and_salts().len(), 6);
+///
+/// let mut opened = Vec::new();
+/// stream.open(reader, &mut opened, answers.iter().cloned())?;
+/// assert_eq!(opened, plaintext);
+/// # Ok::<(), svar_core::Error>(())
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SecurityQuestionsSealedStream {
+    /// The random key each chunk is encrypted with, sealed with the answers
+    /// to the security questions.
+    sealed_data_key: SecurityQuestionsSealedDyn<EncryptionKey>,
+}
+
+fn read_failed(error: std::io::Error) -> Error {
+    if error.kind() == ErrorKind::UnexpectedEof {
+        invalid("Unexpected end of stream")
+    } else {
+        Error::FailedToReadStream {
+            underlying: error.to_string(),
+        }
+    }
+}
+
+fn write_failed(error: std::io::Error) -> Error {
+    Error::FailedToWriteStream {
+        underlying: error.to_string(),
+    }
+}
+
+fn invalid(underlying: impl Into<String>) -> Error {
+    Error::InvalidSealedStream {
+        underlying: underlying.into(),
+    }
+}
+
+fn read_u32(reader: &mut impl Read) -> Result<u32> {
+    let mut bytes = [0u8; 4];
+    reader.read_exact(&mut bytes).map_err(read_failed)?;
+    Ok(u32::from_be_bytes(bytes))
+}
+
+/// Reads up to `len` bytes, fewer only if the end of `reader` is reached.
+fn read_up_to(reader: &mut impl Read, len: usize) -> Result<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(len);
+    reader
+        .take(len as u64)
+        .read_to_end(&mut bytes)
+        .map_err(read_failed)?;
+    Ok(bytes)
+}
+
+/// Returns `true` if there is nothing left to read from `reader`.
+fn is_at_end(reader: &mut impl Read) -> Result<bool> {
+    let mut byte = [0u8; 1];
+    loop {
+        match reader.read(&mut byte) {
+            Ok(read) => return Ok(read == 0),
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(read_failed(e)),
+        }
+    }
+}
+
+impl SecurityQuestionsSealedStream {
+    /// The byte count of the plaintext of each chunk, but the last, which
+    /// might be shorter.
+    pub const CHUNK_LEN: usize = 64 * 1024;
+
+    /// Identifies a sealed stream.
+    const MAGIC: &'static [u8; 8] = b"svarstrm";
+
+    /// The version of the format of the stream.
+    const FORMAT_VERSION: u8 = 1;
+
+    /// Upper bound of the byte count of the header, so that a corrupt
+    /// stream cannot make us allocate arbitrary amounts of memory.
+    const MAX_HEADER_LEN: usize = 64 * 1024 * 1024;
+
+    /// Upper bound of the byte count of an encrypted chunk, leaving room for
+    /// the nonce and authentication tag of any encryption scheme.
+    const MAX_ENCRYPTED_CHUNK_LEN: usize = Self::CHUNK_LEN + 64;
+
+    /// Appended to the plaintext of the last chunk, since encryption schemes
+    /// require a non-empty plaintext.
+    const LAST_CHUNK_TERMINATOR: u8 = 0x80;
+
+    /// Prefix of the associated data of each chunk.
+    const CHUNK_DOMAIN_TAG: &'static [u8] = b"svar/sealed_stream/chunk/v1";
+
+    /// Reads all of `plaintext` and writes it sealed to `sealed`, using
+    /// default schemes, requiring `min_correct_answers` correct answers to
+    /// open it.
+    ///
+    /// Returns the header written to `sealed`.
+    ///
+    /// # Errors
+    ///
+    /// Fails like [`SecurityQuestionsSealedDyn::seal`], or if reading from
+    /// `plaintext` or writing to `sealed` fails.
+    pub fn seal(
+        plaintext: impl Read,
+        sealed: impl Write,
+        with: impl IntoIterator<Item = SecurityQuestionAnswerAndSalt>,
+        min_correct_answers: usize,
+    ) -> Result<Self> {
+        Self::with_schemes(
+            plaintext,
+            sealed,
+            with,
+            min_correct_answers,
+            SecurityQuestionsKdfScheme::default(),
+            EncryptionScheme::default(),
+        )
+    }
+
+    /// Like [`seal`](Self::seal) but with custom cryptographic schemes.
+    pub fn with_schemes(
+        mut plaintext: impl Read,
+        mut sealed: impl Write,
+        with: impl IntoIterator<Item = SecurityQuestionAnswerAndSalt>,
+        min_correct_answers: usize,
+        kdf_scheme: SecurityQuestionsKdfScheme,
+        encryption_scheme: EncryptionScheme,
+    ) -> Result<Self> {
+        let data_key = EncryptionKey::generate();
+        let sealed_data_key = SecurityQuestionsSealedDyn::with_schemes(
+            data_key.clone(),
+            with,
+            min_correct_answers,
+            kdf_scheme,
+            encryption_scheme,
+        )?;
+        let stream = Self { sealed_data_key };
+
+        stream.write_header(&mut sealed)?;
+        stream.seal_chunks(&mut plaintext, &mut sealed, data_key)?;
+        sealed.flush().map_err(write_failed)?;
+
+        Ok(stream)
+    }
+
+    /// Reads the header from the start of `sealed`, leaving it at the first
+    /// chunk, to be passed to [`open`](Self::open).
+    ///
+    /// # Errors
+    ///
+    /// * InvalidSealedStream: `sealed` is not a sealed stream or the header is
+    ///   invalid
+    /// * FailedToReadStream: Reading from `sealed` failed
+    pub fn read_header(sealed: &mut impl Read) -> Result<Self> {
+        let mut magic = [0u8; 9];
+        sealed.read_exact(&mut magic).map_err(read_failed)?;
+        if &magic[..8] != Self::MAGIC {
+            return Err(invalid("Not a sealed stream"));
+        }
+        if magic[8] != Self::FORMAT_VERSION {
+            return Err(invalid(format!(
+                "Unsupported format version: {}",
+                magic[8]
+            )));
+        }
+
+        let header_len = read_u32(sealed)? as usize;
+        if header_len > Self::MAX_HEADER_LEN {
+            return Err(invalid(format!(
+                "Header too large: {header_len} bytes"
+            )));
+        }
+        let header = read_up_to(sealed, header_len)?;
+        if header.len() != header_len {
+            return Err(invalid("Unexpected end of stream"));
+        }
+
+        serde_json::from_slice(&header)
+            .map(|sealed_data_key| Self { sealed_data_key })
+            .map_err(|e| invalid(e.to_string()))
+    }
+
+    /// Checks if `sealed` starts like a sealed stream, without consuming
+    /// it, e.g. to tell a sealed stream apart from other formats.
+    pub fn is_sealed_stream(sealed: impl AsRef<[u8]>) -> bool {
+        sealed.as_ref().starts_with(Self::MAGIC)
+    }
+
+    /// The sealed data key, stored in the header.
+    pub fn sealed_data_key(
+        &self,
+    ) -> &SecurityQuestionsSealedDyn<EncryptionKey> {
+        &self.sealed_data_key
+    }
+
+    /// The security questions and their salts, which must be answered to
+    /// open the stream.
+    pub fn security_questions_and_salts(&self) -> &[SecurityQuestionAndSalt] {
+        self.sealed_data_key.security_questions_and_salts()
+    }
+
+    /// Opens the data key using answers to at least `min_correct_answers` of
+    /// the questions and writes the plaintext of the chunks read from
+    /// `sealed`, positioned after the header, to `plaintext`.
+    ///
+    /// # Errors
+    ///
+    /// Fails like [`SecurityQuestionsSealedDyn::open`], and:
+    /// * SealedSecretTampered: A chunk has been modified or reordered
+    /// * InvalidSealedStream: `sealed` has been truncated or extended
+    /// * FailedToReadStream: Reading from `sealed` failed
+    /// * FailedToWriteStream: Writing to `plaintext` failed
+    pub fn open(
+        &self,
+        sealed: impl Read,
+        plaintext: impl Write,
+        with: impl IntoIterator<Item = SecurityQuestionAnswerAndSalt>,
+    ) -> Result<()> {
+        let data_key = self.sealed_data_key.open(with)?;
+        self.open_chunks(sealed, plaintext, data_key)
+    }
+
+    /// Like [`open`](Self::open) but also returns which answers were
+    /// correct, see [`SecurityQuestionsSealed::open_with_report`].
+    pub fn open_with_report(
+        &self,
+        sealed: impl Read,
+        plaintext: impl Write,
+        with: impl IntoIterator<Item = SecurityQuestionAnswerAndSalt>,
+    ) -> Result<SecurityQuestionsAnswersReport> {
+        let (data_key, report) = self.sealed_data_key.open_with_report(with)?;
+        self.open_chunks(sealed, plaintext, data_key)?;
+        Ok(report)
+    }
+
+    /// The encryption scheme of the sealed data key, without padding, since
+    /// only the last chunk is shorter than [`CHUNK_LEN`](Self::CHUNK_LEN).
+    fn chunk_encryption_scheme(&self) -> EncryptionScheme {
+        self.sealed_data_key
+            .encryption_scheme()
+            .clone()
+            .with_padding(PaddingPolicy::None)
+    }
+
+    /// The associated data of the chunk at `index`.
+    fn chunk_associated_data(index: u64, is_last: bool) -> Vec<u8> {
+        let mut data = Self::CHUNK_DOMAIN_TAG.to_vec();
+        data.extend(index.to_be_bytes());
+        data.push(is_last as u8);
+        data
+    }
+
+    fn write_header(&self, sealed: &mut impl Write) -> Result<()> {
+        let header = serde_json::to_vec(&self.sealed_data_key)
+            .expect("Sealed secret is always serializable");
+        sealed.write_all(Self::MAGIC).map_err(write_failed)?;
+        sealed
+            .write_all(&[Self::FORMAT_VERSION])
+            .map_err(write_failed)?;
+        sealed
+            .write_all(&(header.len() as u32).to_be_bytes())
+            .map_err(write_failed)?;
+        sealed.write_all(&header).map_err(write_failed)
+    }
+
+    /// Encrypts the chunks of `plaintext`, reading one chunk ahead to know
+    /// which chunk is the last, an empty plaintext is a single last chunk.
+    fn seal_chunks(
+        &self,
+        plaintext: &mut impl Read,
+        sealed: &mut impl Write,
+        data_key: EncryptionKey,
+    ) -> Result<()> {
+        let mut chunk = read_up_to(plaintext, Self::CHUNK_LEN)?;
+        let mut index = 0u64;
+        loop {
+            let next = read_up_to(plaintext, Self::CHUNK_LEN)?;
+            let is_last = next.is_empty();
+            if is_last {
+                chunk.push(Self::LAST_CHUNK_TERMINATOR);
+            }
+
+            let encrypted =
+                self.chunk_encryption_scheme().encrypt_with_associated_data(
+                    &chunk,
+                    data_key.clone(),
+                    Self::chunk_associated_data(index, is_last),
+                );
+            chunk.zeroize();
+
+            sealed.write_all(&[is_last as u8]).map_err(write_failed)?;
+            sealed
+                .write_all(&(encrypted.len() as u32).to_be_bytes())
+                .map_err(write_failed)?;
+            sealed.write_all(&encrypted).map_err(write_failed)?;
+
+            if is_last {
+                return Ok(());
+            }
+            chunk = next;
+            index += 1;
+        }
+    }
+
+    fn open_chunks(
+        &self,
+        mut sealed: impl Read,
+        mut plaintext: impl Write,
+        data_key: EncryptionKey,
+    ) -> Result<()> {
+        let mut index = 0u64;
+        loop {
+            let mut flag = [0u8; 1];
+            sealed.read_exact(&mut flag).map_err(read_failed)?;
+            let is_last = match flag[0] {
+                0 => false,
+                1 => true,
+                other => {
+                    return Err(invalid(format!(
+                        "Invalid chunk flag: {other}"
+                    )));
+                }
+            };
+
+            let encrypted_len = read_u32(&mut sealed)? as usize;
+            if encrypted_len > Self::MAX_ENCRYPTED_CHUNK_LEN {
+                return Err(invalid(format!(
+                    "Chunk too large: {encrypted_len} bytes"
+                )));
+            }
+            let encrypted = read_up_to(&mut sealed, encrypted_len)?;
+            if encrypted.len() != encrypted_len {
+                return Err(invalid("Unexpected end of stream"));
+            }
+
+            let mut decrypted = self
+                .chunk_encryption_scheme()
+                .decrypt_with_associated_data(
+                    encrypted,
+                    data_key.clone(),
+                    Self::chunk_associated_data(index, is_last),
+                )
+                .map_err(|_| Error::SealedSecretTampered)?;
+            if is_last && decrypted.pop() != Some(Self::LAST_CHUNK_TERMINATOR) {
+                decrypted.zeroize();
+                return Err(Error::SealedSecretTampered);
+            }
+            let written = plaintext.write_all(&decrypted).map_err(write_failed);
+            decrypted.zeroize();
+            written?;
+
+            if is_last {
+                if !is_at_end(&mut sealed)? {
+                    return Err(invalid("Unexpected data after last chunk"));
+                }
+                return plaintext.flush().map_err(write_failed);
+            }
+            index += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Sut = SecurityQuestionsSealedStream;
+
+    fn answers() -> Vec<SecurityQuestionAnswerAndSalt> {
+        SecurityQuestionsAnswersAndSalts::sample().to_vec()
+    }
+
+    /// Seals `plaintext`, using the cheap KDF version 1 to keep tests fast.
+    fn seal(plaintext: &[u8]) -> Vec<u8> {
+        let mut sealed = Vec::new();
+        Sut::with_schemes(
+            plaintext,
+            &mut sealed,
+            answers(),
+            4,
+            SecurityQuestionsKdfScheme::version1(),
+            EncryptionScheme::default(),
+        )
+        .unwrap();
+        sealed
+    }
+
+    fn open(sealed: &[u8]) -> Result<Vec<u8>> {
+        let mut reader = sealed;
+        let stream = Sut::read_header(&mut reader)?;
+        let mut opened = Vec::new();
+        stream.open(reader, &mut opened, answers())?;
+        Ok(opened)
+    }
+
+    /// The byte ranges of the chunks of `sealed`.
+    fn chunk_ranges(sealed: &[u8]) -> Vec<std::ops::Range<usize>> {
+        let mut reader = sealed;
+        Sut::read_header(&mut reader).unwrap();
+        let mut start = sealed.len() - reader.len();
+        let mut ranges = Vec::new();
+        while start < sealed.len() {
+            let len = u32::from_be_bytes(
+                sealed[start + 1..start + 5].try_into().unwrap(),
+            ) as usize;
+            ranges.push(start..start + 5 + len);
+            start += 5 + len;
+        }
+        ranges
+    }
+
+    #[test]
+    fn roundtrip_lengths() {
+        for len in [
+            0,
+            1,
+            Sut::CHUNK_LEN - 1,
+            Sut::CHUNK_LEN,
+            Sut::CHUNK_LEN + 1,
+            3 * Sut::CHUNK_LEN + 17,
+        ] {
+            let plaintext = (0..len).map(|i| i as u8).collect_vec();
+            assert_eq!(open(&seal(&plaintext)).unwrap(), plaintext);
+        }
+    }
+
+    #[test]
+    fn chunk_count() {
+        assert_eq!(chunk_ranges(&seal(&[])).len(), 1);
+        assert_eq!(chunk_ranges(&seal(&[0xab; Sut::CHUNK_LEN])).len(), 1);
+        assert_eq!(
+            chunk_ranges(&seal(&vec![0xab; 2 * Sut::CHUNK_LEN + 1])).len(),
+            3
+        );
+    }
+
+    #[test]
+    fn roundtrip_default_schemes() {
+        let mut sealed = Vec::new();
+        Sut::seal(&b"open zesame"[..], &mut sealed, answers(), 4).unwrap();
+        assert_eq!(open(&sealed).unwrap(), b"open zesame");
+    }
+
+    #[test]
+    fn roundtrip_encryption_version2() {
+        let mut sealed = Vec::new();
+        Sut::with_schemes(
+            &vec![0xab; Sut::CHUNK_LEN + 1][..],
+            &mut sealed,
+            answers(),
+            4,
+            SecurityQuestionsKdfScheme::version1(),
+            EncryptionScheme::version2(),
+        )
+        .unwrap();
+        assert_eq!(open(&sealed).unwrap(), vec![0xab; Sut::CHUNK_LEN + 1]);
+    }
+
+    #[test]
+    fn open_two_wrong_answers() {
+        let sealed = seal(b"open zesame");
+        let mut reader = sealed.as_slice();
+        let stream = Sut::read_header(&mut reader).unwrap();
+        let mut answers = answers();
+        answers[1].answer = "wrong".into();
+        answers[3].answer = "wrong".into();
+        let mut opened = Vec::new();
+        let report = stream
+            .open_with_report(reader, &mut opened, answers.clone())
+            .unwrap();
+        assert_eq!(opened, b"open zesame");
+        assert_eq!(
+            report.incorrect_question_ids(),
+            vec![answers[1].question.id, answers[3].question.id]
+        );
+    }
+
+    #[test]
+    fn open_too_many_wrong_answers_is_err() {
+        let sealed = seal(b"open zesame");
+        let mut reader = sealed.as_slice();
+        let stream = Sut::read_header(&mut reader).unwrap();
+        let mut answers = answers();
+        answers[0].answer = "wrong".into();
+        answers[1].answer = "wrong".into();
+        answers[2].answer = "wrong".into();
+        assert_eq!(
+            stream.open(reader, Vec::new(), answers),
+            Err(Error::FailedToDecryptSealedSecret)
+        );
+    }
+
+    #[test]
+    fn read_header_not_sealed_stream_is_err() {
+        assert_eq!(
+            Sut::read_header(&mut &b"not a sealed stream"[..]),
+            Err(invalid("Not a sealed stream"))
+        );
+    }
+
+    #[test]
+    fn read_header_unsupported_version_is_err() {
+        let mut sealed = seal(b"open zesame");
+        sealed[8] = 2;
+        assert_eq!(
+            Sut::read_header(&mut sealed.as_slice()),
+            Err(invalid("Unsupported format version: 2"))
+        );
+    }
+
+    #[test]
+    fn read_header_truncated_is_err() {
+        let sealed = seal(b"open zesame");
+        assert_eq!(
+            Sut::read_header(&mut &sealed[..20]),
+            Err(invalid("Unexpected end of stream"))
+        );
+    }
+
+    #[test]
+    fn is_sealed_stream() {
+        assert!(Sut::is_sealed_stream(seal(b"open zesame")));
+        assert!(!Sut::is_sealed_stream(b"{}"));
+    }
+
+    #[test]
+    fn dropped_last_chunk_is_err() {
+        let sealed = seal(&vec![0xab; 2 * Sut::CHUNK_LEN]);
+        let ranges = chunk_ranges(&sealed);
+        assert_eq!(
+            open(&sealed[..ranges[1].start]),
+            Err(invalid("Unexpected end of stream"))
+        );
+    }
+
+    #[test]
+    fn truncated_chunk_is_err() {
+        let sealed = seal(b"open zesame");
+        assert_eq!(
+            open(&sealed[..sealed.len() - 1]),
+            Err(invalid("Unexpected end of stream"))
+        );
+    }
+
+    #[test]
+    fn appended_data_is_err() {
+        let mut sealed = seal(b"open zesame");
+        sealed.push(0);
+        assert_eq!(
+            open(&sealed),
+            Err(invalid("Unexpected data after last chunk"))
+        );
+    }
+
+    #[test]
+    fn reordered_chunks_is_tampered() {
+        let sealed =
+            seal(&(0..3 * Sut::CHUNK_LEN).map(|i| i as u8).collect_vec());
+        let ranges = chunk_ranges(&sealed);
+        let mut reordered = sealed[..ranges[0].start].to_vec();
+        reordered.extend(&sealed[ranges[1].clone()]);
+        reordered.extend(&sealed[ranges[0].clone()]);
+        reordered.extend(&sealed[ranges[2].clone()]);
+        assert_eq!(open(&reordered), Err(Error::SealedSecretTampered));
+    }
+
+    #[test]
+    fn flipped_last_flag_is_tampered() {
+        let mut sealed = seal(&vec![0xab; 2 * Sut::CHUNK_LEN]);
+        let ranges = chunk_ranges(&sealed);
+        // Mark the first chunk as the last, dropping the others
+        sealed[ranges[0].start] = 1;
+        sealed.truncate(ranges[0].end);
+        assert_eq!(open(&sealed), Err(Error::SealedSecretTampered));
+    }
+
+    #[test]
+    fn modified_chunk_is_tampered() {
+        let mut sealed = seal(b"open zesame");
+        *sealed.last_mut().unwrap() ^= 1;
+        assert_eq!(open(&sealed), Err(Error::SealedSecretTampered));
+    }
+
+    #[test]
+    fn chunks_of_other_stream_is_tampered() {
+        let sealed = seal(b"open zesame");
+        let other = seal(b"open zesame");
+        let ranges = chunk_ranges(&sealed);
+        let mut spliced = sealed[..ranges[0].start].to_vec();
+        spliced.extend(&other[chunk_ranges(&other)[0].clone()]);
+        assert_eq!(open(&spliced), Err(Error::SealedSecretTampered));
+    }
+
+    #[test]
+    fn invalid_chunk_flag_is_err() {
+        let mut sealed = seal(b"open zesame");
+        let ranges = chunk_ranges(&sealed);
+        sealed[ranges[0].start] = 2;
+        assert_eq!(open(&sealed), Err(invalid("Invalid chunk flag: 2")));
+    }
+
+    #[test]
+    fn roundtrip_padded_encryption_scheme() {
+        let plaintext = vec![0xab; Sut::CHUNK_LEN + 1];
+        let mut sealed = Vec::new();
+        Sut::with_schemes(
+            plaintext.as_slice(),
+            &mut sealed,
+            answers(),
+            4,
+            SecurityQuestionsKdfScheme::version1(),
+            EncryptionScheme::default().with_padding(PaddingPolicy::Bucket {
+                bucket_size: std::num::NonZeroU32::new(1024 * 1024).unwrap(),
+            }),
+        )
+        .unwrap();
+        assert_eq!(open(&sealed).unwrap(), plaintext);
+    }
+}
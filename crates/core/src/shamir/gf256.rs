@@ -0,0 +1,80 @@
+/// Multiplication in GF(256) with the AES reduction polynomial
+/// x^8 + x^4 + x^3 + x + 1, without data dependent branches or table lookups,
+/// since the operands are secret.
+pub(crate) fn gf256_mul(a: u8, b: u8) -> u8 {
+    let mut a = a;
+    let mut b = b;
+    let mut product = 0u8;
+    for _ in 0..8 {
+        // All ones if the lowest bit of `b` is set, else all zeros.
+        product ^= a & (b & 1).wrapping_neg();
+        let carry = (a >> 7).wrapping_neg();
+        a = (a << 1) ^ (0x1b & carry);
+        b >>= 1;
+    }
+    product
+}
+
+/// Multiplicative inverse in GF(256), computed as `a^254`. The inverse of `0`
+/// is undefined, for which `0` is returned.
+pub(crate) fn gf256_inv(a: u8) -> u8 {
+    // a^254 = a^(2 + 4 + 8 + 16 + 32 + 64 + 128)
+    let mut result = 1u8;
+    let mut square = a;
+    for _ in 1..8 {
+        square = gf256_mul(square, square);
+        result = gf256_mul(result, square);
+    }
+    result
+}
+
+/// Division in GF(256), `b` must not be `0`.
+pub(crate) fn gf256_div(a: u8, b: u8) -> u8 {
+    debug_assert_ne!(b, 0, "Division by zero in GF(256)");
+    gf256_mul(a, gf256_inv(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mul_known() {
+        // From FIPS-197, section 4.2
+        assert_eq!(gf256_mul(0x57, 0x83), 0xc1);
+        assert_eq!(gf256_mul(0x57, 0x13), 0xfe);
+    }
+
+    #[test]
+    fn mul_identity_and_zero() {
+        for a in 0..=u8::MAX {
+            assert_eq!(gf256_mul(a, 1), a);
+            assert_eq!(gf256_mul(a, 0), 0);
+        }
+    }
+
+    #[test]
+    fn mul_commutative() {
+        for a in (0..=u8::MAX).step_by(7) {
+            for b in (0..=u8::MAX).step_by(11) {
+                assert_eq!(gf256_mul(a, b), gf256_mul(b, a));
+            }
+        }
+    }
+
+    #[test]
+    fn inv_of_every_non_zero() {
+        for a in 1..=u8::MAX {
+            assert_eq!(gf256_mul(a, gf256_inv(a)), 1);
+        }
+    }
+
+    #[test]
+    fn div_is_inverse_of_mul() {
+        for a in (0..=u8::MAX).step_by(5) {
+            for b in (1..=u8::MAX).step_by(3) {
+                assert_eq!(gf256_div(gf256_mul(a, b), b), a);
+            }
+        }
+    }
+}
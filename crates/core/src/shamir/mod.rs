@@ -0,0 +1,5 @@
+mod gf256;
+mod shamir_share;
+
+pub(crate) use gf256::*;
+pub(crate) use shamir_share::*;
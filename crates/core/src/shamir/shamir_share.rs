@@ -0,0 +1,218 @@
+use crate::prelude::*;
+
+use hkdf::Hkdf;
+use sha2::Sha256;
+
+/// A share of a 32 bytes secret split using Shamir's Secret Sharing over
+/// GF(256), every byte of the secret is the constant term of its own random
+/// polynomial, and `y` holds the value of each polynomial at `x`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) struct ShamirShare {
+    /// The x coordinate of the share, never `0` since the value of the
+    /// polynomials at `0` is the secret.
+    pub(crate) x: u8,
+
+    /// The value of the polynomial of each byte of the secret at `x`.
+    pub(crate) y: Exactly32Bytes,
+}
+
+impl ShamirShare {
+    /// The maximum number of shares, limited by the non zero x coordinates
+    /// available in GF(256).
+    pub(crate) const MAX_SHARE_COUNT: usize = u8::MAX as usize;
+
+    /// Domain separation tag used as HKDF salt when deriving the mask of a
+    /// share from the entropy of an answer.
+    pub(crate) const MASK_DOMAIN_TAG: &'static [u8] =
+        b"svar/security_questions/shamir_share_mask/v1";
+
+    /// Splits `secret` into `share_count` shares with x coordinates
+    /// `1..=share_count`, any `threshold` of which can recover it, see
+    /// [`combine`](Self::combine).
+    pub(crate) fn split(
+        secret: &Exactly32Bytes,
+        share_count: usize,
+        threshold: usize,
+    ) -> Vec<Self> {
+        assert!(threshold >= 1);
+        assert!(threshold <= share_count);
+        assert!(share_count <= Self::MAX_SHARE_COUNT);
+
+        // The coefficients of degree 1 to threshold - 1 of every polynomial.
+        let mut coefficients = (1..threshold)
+            .map(|_| Exactly32Bytes::generate())
+            .collect_vec();
+
+        let shares = (1..=share_count as u8)
+            .map(|x| {
+                let mut y = [0u8; 32];
+                for (index, byte) in y.iter_mut().enumerate() {
+                    // Horner's method, starting with the highest degree.
+                    *byte = coefficients
+                        .iter()
+                        .rev()
+                        .map(|coefficient| coefficient.bytes()[index])
+                        .chain(std::iter::once(secret.bytes()[index]))
+                        .fold(0u8, |acc, coefficient| {
+                            gf256_mul(acc, x) ^ coefficient
+                        });
+                }
+                Self {
+                    x,
+                    y: Exactly32Bytes::from(y),
+                }
+            })
+            .collect_vec();
+
+        coefficients.iter_mut().for_each(Zeroize::zeroize);
+        shares
+    }
+
+    /// Evaluates the polynomials passing through `shares` at `x` using
+    /// Lagrange interpolation. The x coordinates of `shares` must be unique.
+    pub(crate) fn interpolate<'a>(
+        shares: impl IntoIterator<Item = &'a Self>,
+        x: u8,
+    ) -> Exactly32Bytes {
+        let shares = shares.into_iter().collect_vec();
+        let mut result = [0u8; 32];
+        for share in shares.iter() {
+            // The Lagrange basis polynomial of `share` evaluated at `x`.
+            let basis = shares.iter().filter(|other| other.x != share.x).fold(
+                1u8,
+                |acc, other| {
+                    gf256_mul(acc, gf256_div(x ^ other.x, share.x ^ other.x))
+                },
+            );
+            for (byte, y) in result.iter_mut().zip(share.y.bytes()) {
+                *byte ^= gf256_mul(basis, *y);
+            }
+        }
+        Exactly32Bytes::from(result)
+    }
+
+    /// Recovers the secret from at least `threshold` shares of it, fewer
+    /// shares or shares of different secrets result in an unrelated value.
+    pub(crate) fn combine<'a>(
+        shares: impl IntoIterator<Item = &'a Self>,
+    ) -> Exactly32Bytes {
+        Self::interpolate(shares, 0)
+    }
+
+    /// The mask XOR-ed with a share before it is stored, derived from the
+    /// entropy of the answer to the question the share belongs to.
+    ///
+    /// We intentionally do not use authenticated encryption for the shares,
+    /// that would allow an attacker to verify each answer on its own, instead
+    /// of having to guess `threshold` answers at once.
+    fn mask(entropy: &SecurityQuestionEntropy) -> Exactly32Bytes {
+        let hkdf = Hkdf::<Sha256>::new(
+            Some(Self::MASK_DOMAIN_TAG),
+            entropy.entropy.bytes(),
+        );
+        let mut okm = [0u8; 32];
+        hkdf.expand(&entropy.question_id.to_be_bytes(), &mut okm)
+            .unwrap();
+        let mask = Exactly32Bytes::from(okm);
+        okm.zeroize();
+        mask
+    }
+
+    /// The value of this share masked with the entropy of the answer to its
+    /// question, safe to store.
+    pub(crate) fn masked(
+        &self,
+        entropy: &SecurityQuestionEntropy,
+    ) -> Exactly32Bytes {
+        self.y.xor(&Self::mask(entropy))
+    }
+
+    /// Unmasks the share with x coordinate `x` from its `masked` value, which
+    /// results in garbage if `entropy` was derived from an incorrect answer.
+    pub(crate) fn unmasked(
+        x: u8,
+        masked: &Exactly32Bytes,
+        entropy: &SecurityQuestionEntropy,
+    ) -> Self {
+        Self {
+            x,
+            y: masked.xor(&Self::mask(entropy)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Sut = ShamirShare;
+
+    #[test]
+    fn split_combine_any_threshold_subset() {
+        let secret = Exactly32Bytes::generate();
+        let shares = Sut::split(&secret, 5, 3);
+        assert_eq!(shares.iter().map(|s| s.x).collect_vec(), [1, 2, 3, 4, 5]);
+        for combination in shares.iter().combinations(3) {
+            assert_eq!(Sut::combine(combination), secret);
+        }
+        assert_eq!(Sut::combine(&shares), secret);
+    }
+
+    #[test]
+    fn too_few_shares_do_not_recover_secret() {
+        let secret = Exactly32Bytes::generate();
+        let shares = Sut::split(&secret, 5, 3);
+        assert_ne!(Sut::combine(&shares[..2]), secret);
+    }
+
+    #[test]
+    fn threshold_one_shares_are_secret() {
+        let secret = Exactly32Bytes::sample_aced();
+        let shares = Sut::split(&secret, 3, 1);
+        assert!(shares.iter().all(|share| share.y == secret));
+    }
+
+    #[test]
+    fn max_share_count() {
+        let secret = Exactly32Bytes::generate();
+        let shares = Sut::split(&secret, Sut::MAX_SHARE_COUNT, 2);
+        assert_eq!(shares.last().unwrap().x, u8::MAX);
+        assert_eq!(Sut::combine([&shares[0], &shares[254]]), secret);
+    }
+
+    #[test]
+    fn interpolate_recovers_other_shares() {
+        let secret = Exactly32Bytes::generate();
+        let shares = Sut::split(&secret, 5, 2);
+        assert_eq!(Sut::interpolate(&shares[..2], 4), shares[3].y);
+    }
+
+    #[test]
+    fn incorrect_share_does_not_recover_secret() {
+        let secret = Exactly32Bytes::generate();
+        let mut shares = Sut::split(&secret, 3, 2);
+        shares[0].y = Exactly32Bytes::sample_babe();
+        assert_ne!(Sut::combine(&shares[..2]), secret);
+        assert_eq!(Sut::combine(&shares[1..]), secret);
+    }
+
+    #[test]
+    fn mask_roundtrip() {
+        let entropy =
+            SecurityQuestionEntropy::new(1, Exactly32Bytes::sample_cafe());
+        let share = Sut::split(&Exactly32Bytes::sample_aced(), 2, 2)[0];
+        let masked = share.masked(&entropy);
+        assert_ne!(masked, share.y);
+        assert_eq!(Sut::unmasked(share.x, &masked, &entropy), share);
+    }
+
+    #[test]
+    fn mask_is_bound_to_question_id() {
+        let share = Sut::split(&Exactly32Bytes::sample_aced(), 2, 2)[0];
+        let entropy =
+            SecurityQuestionEntropy::new(1, Exactly32Bytes::sample_cafe());
+        let other =
+            SecurityQuestionEntropy::new(2, Exactly32Bytes::sample_cafe());
+        assert_ne!(share.masked(&entropy), share.masked(&other));
+    }
+}